@@ -1,12 +1,16 @@
 use std::error::Error;
+use std::future::Future;
 
 use futures_lite::stream::StreamExt;
 use lapin::{BasicProperties, Channel, Connection, ConnectionProperties, Consumer, ExchangeKind};
-use lapin::options::{BasicAckOptions, BasicCancelOptions, BasicConsumeOptions, BasicPublishOptions, BasicRejectOptions, ExchangeDeclareOptions, QueueBindOptions, QueueDeclareOptions, QueuePurgeOptions};
+use lapin::options::{BasicAckOptions, BasicCancelOptions, BasicConsumeOptions, BasicNackOptions, BasicPublishOptions, BasicRejectOptions, ExchangeDeclareOptions, QueueBindOptions, QueueDeclareOptions, QueuePurgeOptions};
 use lapin::protocol::constants::REPLY_SUCCESS;
-use lapin::types::FieldTable;
+use lapin::types::{AMQPValue, FieldTable};
 use tokio::spawn;
 
+const DELIVERY_ATTEMPTS_HEADER: &str = "x-delivery-attempts";
+const MAX_DELIVERY_ATTEMPTS: u32 = 5;
+
 #[derive(Clone)]
 pub struct MessageQueue {
     channel: Channel,
@@ -14,6 +18,13 @@ pub struct MessageQueue {
 }
 
 impl MessageQueue {
+    fn delivery_attempts(properties: &BasicProperties) -> u32 {
+        properties.headers().as_ref()
+            .and_then(|headers| headers.inner().get(DELIVERY_ATTEMPTS_HEADER))
+            .and_then(AMQPValue::as_long_uint)
+            .unwrap_or(1)
+    }
+
     pub async fn connect(hostname: &str, port: &str) -> Result<Self, Box<dyn Error>> {
         let uri = &format!("amqp://{}:{}", hostname, port);
         let connection = Connection::connect(uri, ConnectionProperties::default()).await?;
@@ -68,6 +79,40 @@ impl MessageQueue {
         Ok(())
     }
 
+    pub async fn bind_exclusive_queue(
+        &self,
+        exchange_name: &str,
+        exchange_type: &str,
+        routing_key: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        self.channel
+            .exchange_declare(
+                exchange_name,
+                ExchangeKind::Custom(String::from(exchange_type)),
+                ExchangeDeclareOptions::default(),
+                FieldTable::default(),
+            )
+            .await?;
+        let queue = self.channel
+            .queue_declare(
+                "",
+                QueueDeclareOptions { exclusive: true, auto_delete: true, ..QueueDeclareOptions::default() },
+                FieldTable::default(),
+            )
+            .await?;
+        let queue_name = queue.name().to_string();
+        self.channel
+            .queue_bind(
+                &queue_name,
+                exchange_name,
+                routing_key,
+                QueueBindOptions::default(),
+                FieldTable::default(),
+            )
+            .await?;
+        Ok(queue_name)
+    }
+
     pub async fn clear_queue(&self, queue_name: &str) -> Result<(), Box<dyn Error>> {
         match self.channel.queue_purge(queue_name, QueuePurgeOptions::default()).await {
             Ok(_) => Ok(()),
@@ -80,7 +125,7 @@ impl MessageQueue {
         exchange_name: &str,
         routing_key: &str,
         message: &[u8],
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.channel
             .basic_publish(
                 exchange_name,
@@ -128,6 +173,59 @@ impl MessageQueue {
         Ok(())
     }
 
+    pub async fn start_consuming_async<F, R>(
+        &mut self,
+        queue_name: &str,
+        callback: F,
+    ) -> Result<(), Box<dyn Error>>
+        where
+            F: Fn(Vec<u8>) -> R + Send + Sync + 'static,
+            R: Future<Output=Result<(), Box<dyn Error + Send + Sync>>> + Send,
+    {
+        let consumer = self.channel
+            .basic_consume(
+                queue_name,
+                "default",
+                BasicConsumeOptions::default(),
+                FieldTable::default(),
+            )
+            .await?;
+        self.consumer = Some(consumer.clone());
+        let channel = self.channel.clone();
+        let queue_name = String::from(queue_name);
+
+        spawn(async move {
+            println!("consumer with name '{}' started", &consumer.tag().as_str());
+
+            while let Some(delivery) = consumer.clone().next().await {
+                if let Ok(delivery) = delivery {
+                    if let Ok(()) = callback(delivery.data.clone()).await {
+                        let _ = channel.basic_ack(delivery.delivery_tag, BasicAckOptions::default()).await;
+                        continue;
+                    }
+                    // a failed message is published again with its attempts counted, so that a poison message is
+                    // rejected without requeueing (and dead-lettered if the queue has a policy for it) instead of looping
+                    let attempts = Self::delivery_attempts(&delivery.properties);
+                    if attempts < MAX_DELIVERY_ATTEMPTS {
+                        let mut headers = delivery.properties.headers().clone().unwrap_or_default();
+                        headers.insert(DELIVERY_ATTEMPTS_HEADER.into(), AMQPValue::LongUInt(attempts + 1));
+                        let properties = delivery.properties.clone().with_headers(headers);
+                        let requeue = channel.basic_publish("", &queue_name, BasicPublishOptions::default(), &delivery.data, properties).await;
+                        let _ = match requeue {
+                            Ok(_) => channel.basic_ack(delivery.delivery_tag, BasicAckOptions::default()).await,
+                            Err(_) => channel.basic_nack(delivery.delivery_tag, BasicNackOptions { multiple: false, requeue: true }).await
+                        };
+                        continue;
+                    }
+                    println!("message from queue '{}' rejected after {} delivery attempts", queue_name, attempts);
+                    let _ = channel.basic_nack(delivery.delivery_tag, BasicNackOptions { multiple: false, requeue: false }).await;
+                }
+            }
+        });
+
+        Ok(())
+    }
+
     pub async fn stop_consuming(&mut self) -> Result<(), Box<dyn Error>> {
         if let Some(consumer) = self.consumer.take() {
             self.channel.basic_cancel(consumer.tag().as_str(), BasicCancelOptions::default()).await?;
@@ -180,6 +278,17 @@ mod tests {
         println!("queue cleared successfully");
     }
 
+    #[tokio::test]
+    async fn test_bind_exclusive() {
+        let message_queue = MessageQueue::connect(HOSTNAME, PORT).await.unwrap();
+
+        let queue_name = message_queue.bind_exclusive_queue(EXCHANGE_NAME, "direct", ROUTING_KEY).await.unwrap();
+        assert!(!queue_name.is_empty());
+        println!("exclusive queue '{}' bounded successfully", queue_name);
+
+        assert_eq!(message_queue.close().await.unwrap(), ());
+    }
+
     #[tokio::test]
     async fn test_publish_consume() {
         let mut message_queue = MessageQueue::connect(HOSTNAME, PORT).await.unwrap();
@@ -220,4 +329,55 @@ mod tests {
 
         assert_eq!(message_queue.stop_consuming().await.unwrap(), ());
     }
-}
+
+    #[tokio::test]
+    async fn test_publish_consume_async() {
+        let message_queue = MessageQueue::connect(HOSTNAME, PORT).await.unwrap();
+
+        timeout(Duration::from_millis(5000), async {
+            message_queue.clear_queue(QUEUE_NAME).await.expect("unable to clear the queue")
+        }).await.unwrap();
+
+        let expected_message_count = 5;
+
+        let messages: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let callback_messages = Arc::clone(&messages);
+
+        for _ in 0..expected_message_count {
+            assert_eq!(message_queue.publish(EXCHANGE_NAME, ROUTING_KEY, TEST_MESSAGE.as_bytes()).await.unwrap(), ());
+        }
+
+        let handle_message = move |msg: Vec<u8>| {
+            let callback_messages = Arc::clone(&callback_messages);
+            async move {
+                let received_message = String::from_utf8(msg).unwrap();
+                assert_eq!(received_message, TEST_MESSAGE);
+                callback_messages.lock().unwrap().push(received_message);
+                Ok(())
+            }
+        };
+
+        let mut consuming_queue = message_queue.clone();
+        consuming_queue.start_consuming_async(QUEUE_NAME, handle_message).await.unwrap();
+
+        let now = SystemTime::now();
+        while messages.lock().unwrap().len() < expected_message_count {
+            if now.elapsed().unwrap().as_millis() > 5000 {
+                panic!("timeout occured")
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+
+        assert_eq!(messages.lock().unwrap().len(), expected_message_count);
+
+        assert_eq!(consuming_queue.stop_consuming().await.unwrap(), ());
+    }
+
+    #[test]
+    fn test_delivery_attempts() {
+        assert_eq!(MessageQueue::delivery_attempts(&BasicProperties::default()), 1);
+        let mut headers = FieldTable::default();
+        headers.insert(DELIVERY_ATTEMPTS_HEADER.into(), AMQPValue::LongUInt(3));
+        assert_eq!(MessageQueue::delivery_attempts(&BasicProperties::default().with_headers(headers)), 3);
+    }
+}
\ No newline at end of file
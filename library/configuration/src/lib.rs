@@ -10,6 +10,17 @@ pub struct Config {
     pub mongo_port: Option<String>,
     pub redis_hostname: Option<String>,
     pub redis_port: Option<String>,
+    pub smtp_hostname: Option<String>,
+    pub smtp_port: Option<String>,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub smtp_sender: Option<String>,
+    pub access_token_ttl: Option<String>,
+    pub refresh_token_ttl: Option<String>,
+    pub email_verification_token_ttl: Option<String>,
+    pub password_reset_token_ttl: Option<String>,
+    pub websocket_ticket_ttl: Option<String>,
+    pub data_export_token_ttl: Option<String>,
     pub service_hostname: Option<String>,
     pub service_port: Option<String>,
 }
@@ -33,6 +44,17 @@ impl Config {
             redis_port: var("REDIS_PORT").ok(),
             mongo_hostname: var("MONGO_HOSTNAME").ok(),
             mongo_port: var("MONGO_PORT").ok(),
+            smtp_hostname: var("SMTP_HOSTNAME").ok(),
+            smtp_port: var("SMTP_PORT").ok(),
+            smtp_username: var("SMTP_USERNAME").ok(),
+            smtp_password: var("SMTP_PASSWORD").ok(),
+            smtp_sender: var("SMTP_SENDER").ok(),
+            access_token_ttl: var("ACCESS_TOKEN_TTL").ok(),
+            refresh_token_ttl: var("REFRESH_TOKEN_TTL").ok(),
+            email_verification_token_ttl: var("EMAIL_VERIFICATION_TOKEN_TTL").ok(),
+            password_reset_token_ttl: var("PASSWORD_RESET_TOKEN_TTL").ok(),
+            websocket_ticket_ttl: var("WEBSOCKET_TICKET_TTL").ok(),
+            data_export_token_ttl: var("DATA_EXPORT_TOKEN_TTL").ok(),
             service_hostname: var(format!("{}_HOSTNAME", service_name)).ok(),
             service_port: var(format!("{}_PORT", service_name)).ok(),
         })
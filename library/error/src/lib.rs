@@ -28,6 +28,85 @@ impl Display for InternalError {
 
 impl Error for InternalError {}
 
+#[derive(Debug)]
+pub enum DomainError {
+    NotFound(String),
+    AlreadyExists(String),
+    Unauthenticated(String),
+    PermissionDenied(String),
+    InvalidArgument(String),
+    FailedPrecondition(String),
+    ResourceExhausted(String, Option<u64>),
+    Unavailable(Box<dyn Error + Send + Sync>),
+    Internal(Box<dyn Error + Send + Sync>),
+}
+
+impl DomainError {
+    pub fn not_found(message: &str) -> Box<dyn Error + Send + Sync> {
+        Box::new(DomainError::NotFound(message.to_string()))
+    }
+
+    pub fn already_exists(message: &str) -> Box<dyn Error + Send + Sync> {
+        Box::new(DomainError::AlreadyExists(message.to_string()))
+    }
+
+    pub fn unauthenticated(message: &str) -> Box<dyn Error + Send + Sync> {
+        Box::new(DomainError::Unauthenticated(message.to_string()))
+    }
+
+    pub fn permission_denied(message: &str) -> Box<dyn Error + Send + Sync> {
+        Box::new(DomainError::PermissionDenied(message.to_string()))
+    }
+
+    pub fn invalid_argument(message: &str) -> Box<dyn Error + Send + Sync> {
+        Box::new(DomainError::InvalidArgument(message.to_string()))
+    }
+
+    pub fn failed_precondition(message: &str) -> Box<dyn Error + Send + Sync> {
+        Box::new(DomainError::FailedPrecondition(message.to_string()))
+    }
+
+    pub fn resource_exhausted(message: &str) -> Box<dyn Error + Send + Sync> {
+        Box::new(DomainError::ResourceExhausted(message.to_string(), None))
+    }
+
+    pub fn resource_exhausted_with_retry(message: &str, retry_after_seconds: u64) -> Box<dyn Error + Send + Sync> {
+        Box::new(DomainError::ResourceExhausted(message.to_string(), Some(retry_after_seconds)))
+    }
+
+    pub fn unavailable(source: Box<dyn Error + Send + Sync>) -> Box<dyn Error + Send + Sync> {
+        Box::new(DomainError::Unavailable(source))
+    }
+
+    pub fn internal(source: Box<dyn Error + Send + Sync>) -> Box<dyn Error + Send + Sync> {
+        Box::new(DomainError::Internal(source))
+    }
+}
+
+impl Display for DomainError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            DomainError::NotFound(message)
+            | DomainError::AlreadyExists(message)
+            | DomainError::Unauthenticated(message)
+            | DomainError::PermissionDenied(message)
+            | DomainError::InvalidArgument(message)
+            | DomainError::FailedPrecondition(message)
+            | DomainError::ResourceExhausted(message, _) => write!(f, "{}", message),
+            DomainError::Unavailable(source) | DomainError::Internal(source) => write!(f, "{}", source),
+        }
+    }
+}
+
+impl Error for DomainError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DomainError::Unavailable(source) | DomainError::Internal(source) => Some(source.as_ref()),
+            _ => None
+        }
+    }
+}
+
 ///
 /// # Arguments
 ///
@@ -77,4 +156,20 @@ mod tests {
         let error = make_error!(name, message);
         assert_eq!(error.to_string(), format!("{} - {}", name, message));
     }
+
+    #[test]
+    fn test_domain_error() {
+        let message = "something not found";
+        let error = DomainError::not_found(message);
+        assert_eq!(error.to_string(), message);
+        assert!(matches!(error.downcast_ref::<DomainError>(), Some(DomainError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_domain_error_source() {
+        let message = "something get wrong";
+        let error = DomainError::unavailable(make_error!("test module", message));
+        assert_eq!(error.to_string(), format!("{} - {}", "test module", message));
+        assert_eq!(error.source().unwrap().to_string(), format!("{} - {}", "test module", message));
+    }
 }
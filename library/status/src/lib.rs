@@ -1,6 +1,9 @@
 use std::error::Error;
 
 use tonic::{Code, Response, Status as GrpcStatus};
+use tonic::metadata::MetadataValue;
+
+use error::DomainError;
 
 pub struct Status<T>(T);
 
@@ -9,6 +12,28 @@ impl<T> Status<T> {
         Err(GrpcStatus::new(Code::Unauthenticated, message))
     }
 
+    pub fn not_found(message: &str) -> Result<Response<T>, GrpcStatus> {
+        Err(GrpcStatus::new(Code::NotFound, message))
+    }
+
+    pub fn already_exists(message: &str) -> Result<Response<T>, GrpcStatus> {
+        Err(GrpcStatus::new(Code::AlreadyExists, message))
+    }
+
+    pub fn permission_denied(message: &str) -> Result<Response<T>, GrpcStatus> {
+        Err(GrpcStatus::new(Code::PermissionDenied, message))
+    }
+
+    pub fn resource_exhausted(message: &str) -> Result<Response<T>, GrpcStatus> {
+        Err(GrpcStatus::new(Code::ResourceExhausted, message))
+    }
+
+    pub fn resource_exhausted_with_retry(message: &str, retry_after_seconds: u64) -> Result<Response<T>, GrpcStatus> {
+        let mut status = GrpcStatus::new(Code::ResourceExhausted, message);
+        status.metadata_mut().insert("retry-after", MetadataValue::from(retry_after_seconds));
+        Err(status)
+    }
+
     pub fn invalid_arguments(args: Vec<&str>) -> Result<Response<T>, GrpcStatus> {
         let message = format!("Invalid Arguments: {}", args.join(","));
         Err(GrpcStatus::new(Code::InvalidArgument, message))
@@ -17,6 +42,33 @@ impl<T> Status<T> {
     pub fn internal(error: Box<dyn Error>) -> Result<Response<T>, GrpcStatus> {
         Err(GrpcStatus::new(Code::Internal, error.to_string()))
     }
+
+    pub fn from_error(error: Box<dyn Error>) -> Result<Response<T>, GrpcStatus> {
+        Err(into_status(error))
+    }
+}
+
+pub fn into_status(error: Box<dyn Error>) -> GrpcStatus {
+    let error = match error.downcast::<GrpcStatus>() {
+        Ok(status) => return *status,
+        Err(error) => error
+    };
+    let code = match error.downcast_ref::<DomainError>() {
+        Some(DomainError::NotFound(_)) => Code::NotFound,
+        Some(DomainError::AlreadyExists(_)) => Code::AlreadyExists,
+        Some(DomainError::Unauthenticated(_)) => Code::Unauthenticated,
+        Some(DomainError::PermissionDenied(_)) => Code::PermissionDenied,
+        Some(DomainError::InvalidArgument(_)) => Code::InvalidArgument,
+        Some(DomainError::FailedPrecondition(_)) => Code::FailedPrecondition,
+        Some(DomainError::ResourceExhausted(_, _)) => Code::ResourceExhausted,
+        Some(DomainError::Unavailable(_)) => Code::Unavailable,
+        Some(DomainError::Internal(_)) | None => Code::Internal
+    };
+    let mut status = GrpcStatus::new(code, error.to_string());
+    if let Some(DomainError::ResourceExhausted(_, Some(retry_after_seconds))) = error.downcast_ref::<DomainError>() {
+        status.metadata_mut().insert("retry-after", MetadataValue::from(*retry_after_seconds));
+    }
+    status
 }
 
 #[cfg(test)]
@@ -29,6 +81,37 @@ mod tests {
         assert_eq!(status.unwrap_err().message(), "test");
     }
 
+    #[test]
+    fn not_found() {
+        let status = Status::<Response<()>>::not_found("test");
+        assert_eq!(status.unwrap_err().code(), Code::NotFound);
+    }
+
+    #[test]
+    fn already_exists() {
+        let status = Status::<Response<()>>::already_exists("test");
+        assert_eq!(status.unwrap_err().code(), Code::AlreadyExists);
+    }
+
+    #[test]
+    fn permission_denied() {
+        let status = Status::<Response<()>>::permission_denied("test");
+        assert_eq!(status.unwrap_err().code(), Code::PermissionDenied);
+    }
+
+    #[test]
+    fn resource_exhausted() {
+        let status = Status::<Response<()>>::resource_exhausted("test");
+        assert_eq!(status.unwrap_err().code(), Code::ResourceExhausted);
+    }
+
+    #[test]
+    fn resource_exhausted_with_retry() {
+        let status = Status::<Response<()>>::resource_exhausted_with_retry("test", 30).unwrap_err();
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert_eq!(status.metadata().get("retry-after").unwrap(), "30");
+    }
+
     #[test]
     fn invalid_argument() {
         let status = Status::<Response<()>>::invalid_arguments(vec!["test"]);
@@ -41,4 +124,46 @@ mod tests {
         let status = Status::<Response<()>>::internal(Box::new(error));
         assert_eq!(status.unwrap_err().message(), error.to_string());
     }
+
+    #[test]
+    fn from_domain_error() {
+        let status = Status::<Response<()>>::from_error(DomainError::not_found("test"));
+        assert_eq!(status.unwrap_err().code(), Code::NotFound);
+        let status = Status::<Response<()>>::from_error(DomainError::unavailable(Box::new(std::fmt::Error)));
+        assert_eq!(status.unwrap_err().code(), Code::Unavailable);
+        let status = Status::<Response<()>>::from_error(DomainError::unauthenticated("test"));
+        assert_eq!(status.unwrap_err().code(), Code::Unauthenticated);
+    }
+
+    #[test]
+    fn from_resource_exhausted_domain_error() {
+        let status = Status::<Response<()>>::from_error(DomainError::resource_exhausted_with_retry("test", 30)).unwrap_err();
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert_eq!(status.metadata().get("retry-after").unwrap(), "30");
+        let status = Status::<Response<()>>::from_error(DomainError::resource_exhausted("test")).unwrap_err();
+        assert!(status.metadata().get("retry-after").is_none());
+    }
+
+    #[test]
+    fn from_grpc_status() {
+        let status = Status::<Response<()>>::from_error(Box::new(GrpcStatus::new(Code::AlreadyExists, "test")));
+        let status = status.unwrap_err();
+        assert_eq!(status.code(), Code::AlreadyExists);
+        assert_eq!(status.message(), "test");
+    }
+
+    #[test]
+    fn from_grpc_status_with_metadata() {
+        let status = Status::<Response<()>>::resource_exhausted_with_retry("test", 30).unwrap_err();
+        let status = Status::<Response<()>>::from_error(Box::new(status)).unwrap_err();
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert_eq!(status.metadata().get("retry-after").unwrap(), "30");
+    }
+
+    #[test]
+    fn from_unknown_error() {
+        let error = std::fmt::Error;
+        let status = Status::<Response<()>>::from_error(Box::new(error));
+        assert_eq!(status.unwrap_err().code(), Code::Internal);
+    }
 }
@@ -5,4 +5,10 @@ fn main() {
         std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path().unwrap());
     }
     tonic_build::compile_protos("../../proto/account.proto").expect("unable to run protoc");
+    tonic_build::compile_protos("../../proto/token.proto").expect("unable to run protoc");
+    tonic_build::compile_protos("../../proto/profile.proto").expect("unable to run protoc");
+    tonic_build::compile_protos("../../proto/matchmaking.proto").expect("unable to run protoc");
+    tonic_build::compile_protos("../../proto/conversation.proto").expect("unable to run protoc");
+    tonic_build::compile_protos("../../proto/safety.proto").expect("unable to run protoc");
+    tonic_build::compile_protos("../../proto/support.proto").expect("unable to run protoc");
 }
\ No newline at end of file
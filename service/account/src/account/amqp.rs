@@ -0,0 +1,90 @@
+use std::str::from_utf8;
+use std::sync::Arc;
+
+use tokio::spawn;
+
+use amqp::MessageQueue;
+
+use crate::account::interactor::AccountInteractor;
+
+const EXCHANGE_NAME: &str = "action";
+const SUSPENDED_QUEUE_NAME: &str = "account.user.suspended";
+const SUSPENDED_ROUTING_KEY: &str = "user.suspended";
+const BANNED_QUEUE_NAME: &str = "account.user.banned";
+const BANNED_ROUTING_KEY: &str = "user.banned";
+const ERASED_QUEUE_NAME: &str = "account.account.erased";
+const ERASED_ROUTING_KEY: &str = "account.erased";
+
+pub struct MessageQueueHandler {
+    interactor: Arc<Box<dyn AccountInteractor + Send + Sync>>,
+    message_queue: MessageQueue,
+}
+
+impl MessageQueueHandler {
+    pub fn new(interactor: Arc<Box<dyn AccountInteractor + Send + Sync>>, message_queue: MessageQueue) -> Self {
+        Self {
+            interactor,
+            message_queue,
+        }
+    }
+
+    pub async fn consume_suspended_users(&mut self) {
+        let _ = self.message_queue.bind_queue(EXCHANGE_NAME, "direct", SUSPENDED_QUEUE_NAME, SUSPENDED_ROUTING_KEY).await;
+
+        let interactor = Arc::clone(&self.interactor);
+        let handle_message = move |msg: &[u8]| {
+            let interactor = Arc::clone(&interactor);
+            if let Ok(value) = from_utf8(msg) {
+                let values: Vec<String> = value.split(",").map(String::from).collect();
+                if values.len() == 2 {
+                    if let Ok(suspension_expiration_date) = values[1].parse::<u64>() {
+                        spawn(async move {
+                            let _ = interactor.suspend_account(&values[0], suspension_expiration_date).await;
+                        });
+                    }
+                }
+            }
+            Ok(())
+        };
+
+        let _ = self.message_queue.start_consuming(SUSPENDED_QUEUE_NAME, Box::new(handle_message)).await;
+    }
+
+    pub async fn consume_banned_users(&mut self) {
+        let _ = self.message_queue.bind_queue(EXCHANGE_NAME, "direct", BANNED_QUEUE_NAME, BANNED_ROUTING_KEY).await;
+
+        let interactor = Arc::clone(&self.interactor);
+        let handle_message = move |msg: &[u8]| {
+            let interactor = Arc::clone(&interactor);
+            if let Ok(value) = from_utf8(msg) {
+                let id = String::from(value);
+                spawn(async move {
+                    let _ = interactor.ban_account(&id).await;
+                });
+            }
+            Ok(())
+        };
+
+        let _ = self.message_queue.start_consuming(BANNED_QUEUE_NAME, Box::new(handle_message)).await;
+    }
+
+    pub async fn consume_erased_accounts(&mut self) {
+        let _ = self.message_queue.bind_queue(EXCHANGE_NAME, "direct", ERASED_QUEUE_NAME, ERASED_ROUTING_KEY).await;
+
+        let interactor = Arc::clone(&self.interactor);
+        let handle_message = move |msg: &[u8]| {
+            let interactor = Arc::clone(&interactor);
+            if let Ok(value) = from_utf8(msg) {
+                let values: Vec<String> = value.split(",").map(String::from).collect();
+                if values.len() == 2 {
+                    spawn(async move {
+                        let _ = interactor.complete_erasure(&values[0], &values[1]).await;
+                    });
+                }
+            }
+            Ok(())
+        };
+
+        let _ = self.message_queue.start_consuming(ERASED_QUEUE_NAME, Box::new(handle_message)).await;
+    }
+}
\ No newline at end of file
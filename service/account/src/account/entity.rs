@@ -1,13 +1,28 @@
+use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum Role {
     User,
     Moderator,
 }
 
+#[derive(Serialize, Deserialize, Default)]
+pub enum Status {
+    #[default]
+    Active,
+    Suspended,
+    Banned,
+}
+
+pub enum Entitlement {
+    Backtrack,
+    SeeWhoLikedYou,
+    UnlimitedLikes,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Account {
     #[serde(rename = "_id")]
@@ -19,6 +34,18 @@ pub struct Account {
     pub premium_expiration_date: u64,
     pub created_at: u64,
     pub updated_at: u64,
+    #[serde(default)]
+    pub status: Status,
+    #[serde(default)]
+    pub suspension_expiration_date: u64,
+    #[serde(default)]
+    pub email_verified: bool,
+    #[serde(default)]
+    pub totp_secret: String,
+    #[serde(default)]
+    pub totp_enabled: bool,
+    #[serde(default)]
+    pub recovery_code_hashes: Vec<String>,
 }
 
 impl Account {
@@ -37,6 +64,88 @@ impl Account {
             premium_expiration_date: 0,
             created_at: now,
             updated_at: now,
+            status: Status::Active,
+            suspension_expiration_date: 0,
+            email_verified: false,
+            totp_secret: String::new(),
+            totp_enabled: false,
+            recovery_code_hashes: vec![],
+        }
+    }
+
+    pub fn normalize_email(email: &str) -> String {
+        email.trim().to_lowercase()
+    }
+
+    pub fn is_premium(&self) -> bool {
+        self.premium_expiration_date > Account::timestamp_now()
+    }
+
+    pub fn entitlements(&self) -> Vec<Entitlement> {
+        if self.is_premium() {
+            return vec![Entitlement::Backtrack, Entitlement::SeeWhoLikedYou, Entitlement::UnlimitedLikes];
+        }
+        vec![]
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RoleChange {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub account_id: String,
+    pub actor_id: String,
+    pub previous_role: Role,
+    pub role: Role,
+    pub created_at: u64,
+}
+
+impl RoleChange {
+    pub fn new(id: &str, account_id: &str, actor_id: &str, previous_role: Role, role: Role) -> Self {
+        Self {
+            id: String::from(id),
+            account_id: String::from(account_id),
+            actor_id: String::from(actor_id),
+            previous_role,
+            role,
+            created_at: Account::timestamp_now(),
+        }
+    }
+}
+
+pub const ERASING_SERVICES: [&str; 6] = ["profile", "matchmaking", "recommendation", "conversation", "safety", "support"];
+
+#[derive(Serialize, Deserialize)]
+pub struct Deletion {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub erasures: HashMap<String, u64>,
+    pub requested_at: u64,
+    pub completed_at: Option<u64>,
+}
+
+impl Deletion {
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: String::from(id),
+            erasures: HashMap::new(),
+            requested_at: Account::timestamp_now(),
+            completed_at: None,
         }
     }
+
+    pub fn is_erased(&self) -> bool {
+        ERASING_SERVICES.iter().all(|service| self.erasures.contains_key(*service))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_email() {
+        assert_eq!(Account::normalize_email("  User@Example.COM "), "user@example.com");
+        assert_eq!(Account::normalize_email("user@example.com"), "user@example.com");
+    }
 }
\ No newline at end of file
@@ -1,40 +1,81 @@
-use error::make_error;
+use std::io::{Cursor, Write};
 
-use crate::account::entity::{Account, Role};
+use serde_json::Value;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+use error::DomainError;
+
+use crate::account::entity::{Account, Deletion, Entitlement, ERASING_SERVICES, Role, Status};
 use crate::account::repository::AccountRepository;
+use crate::export::entity::{Export, ExportStatus};
+use crate::export::repository::ExportRepository;
 use crate::password::hasher::Hasher;
+use crate::token::repository::TokenRepository;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
 #[async_trait::async_trait]
 pub trait AccountInteractor {
     async fn get_account_by_id(&self, id: &str) -> Result<Account, Error>;
+    async fn get_account_by_email(&self, email: &str) -> Result<Account, Error>;
     async fn get_account_by_credentials(&self, email: &str, password: &str) -> Result<Account, Error>;
     async fn create_account(
         &self,
         email: &str,
         password: &str,
-        role: Role,
     ) -> Result<String, Error>;
     async fn update_account(
         &self,
         id: &str,
         email: Option<String>,
         password: Option<String>,
-        role: Option<Role>,
     ) -> Result<Account, Error>;
-    async fn update_premium(&self, id: &str, premium_expiration_date: u64) -> Result<Account, Error>;
+    async fn change_role(&self, id: &str, role: Role, actor_id: &str) -> Result<Account, Error>;
+    async fn verify_email(&self, id: &str, email: &str) -> Result<Account, Error>;
+    async fn update_totp(&self, id: &str, totp_secret: &str, totp_enabled: bool, recovery_code_hashes: Vec<String>) -> Result<Account, Error>;
+    async fn consume_recovery_code(&self, id: &str, recovery_code_hash: &str) -> Result<bool, Error>;
+    async fn grant_premium(&self, id: &str, premium_expiration_date: u64) -> Result<Account, Error>;
+    async fn extend_premium(&self, id: &str, duration: u64) -> Result<Account, Error>;
+    async fn revoke_premium(&self, id: &str) -> Result<Account, Error>;
+    async fn get_entitlements(&self, id: &str) -> Result<(Vec<Entitlement>, u64), Error>;
+    async fn suspend_account(&self, id: &str, suspension_expiration_date: u64) -> Result<Account, Error>;
+    async fn ban_account(&self, id: &str) -> Result<Account, Error>;
+    async fn reinstate_account(&self, id: &str) -> Result<Account, Error>;
     async fn delete_account(&self, id: &str) -> Result<String, Error>;
+    async fn get_deletion_status(&self, id: &str) -> Result<Option<Deletion>, Error>;
+    async fn complete_erasure(&self, id: &str, service: &str) -> Result<Deletion, Error>;
+    async fn export_my_data(&self, id: &str) -> Result<Export, Error>;
+    async fn get_pending_exports(&self) -> Result<Vec<Export>, Error>;
+    async fn generate_export(&self, export_id: &str) -> Result<(), Error>;
+    async fn get_export(&self, id: &str, export_id: &str) -> Result<(Export, Option<String>), Error>;
+    async fn download_export(&self, id: &str, token: &str) -> Result<Vec<u8>, Error>;
 }
 
 pub struct AccountInteractorImpl {
     password_hasher: Box<dyn Hasher + Send + Sync>,
     repository: Box<dyn AccountRepository + Send + Sync>,
+    token_repository: Box<dyn TokenRepository + Send + Sync>,
+    export_repository: Box<dyn ExportRepository + Send + Sync>,
 }
 
 impl AccountInteractorImpl {
-    pub fn new(password_hasher: Box<dyn Hasher + Send + Sync>, repository: Box<dyn AccountRepository + Send + Sync>) -> Box<dyn AccountInteractor + Send + Sync> {
-        Box::new(AccountInteractorImpl { password_hasher, repository })
+    pub fn new(
+        password_hasher: Box<dyn Hasher + Send + Sync>,
+        repository: Box<dyn AccountRepository + Send + Sync>,
+        token_repository: Box<dyn TokenRepository + Send + Sync>,
+        export_repository: Box<dyn ExportRepository + Send + Sync>,
+    ) -> Box<dyn AccountInteractor + Send + Sync> {
+        Box::new(AccountInteractorImpl { password_hasher, repository, token_repository, export_repository })
+    }
+
+    fn write_archive(files: Vec<(String, Vec<u8>)>) -> Result<Vec<u8>, Error> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            writer.start_file(name, FileOptions::default())?;
+            writer.write_all(&content)?;
+        }
+        Ok(writer.finish()?.into_inner())
     }
 }
 
@@ -44,35 +85,204 @@ impl AccountInteractor for AccountInteractorImpl {
         self.repository.get_account_by_id(id).await
     }
 
+    async fn get_account_by_email(&self, email: &str) -> Result<Account, Error> {
+        self.repository.get_account_by_email(email).await
+    }
+
     async fn get_account_by_credentials(&self, email: &str, password: &str) -> Result<Account, Error> {
-        let account = self.repository.get_account_by_email(email).await?;
+        let account = match self.repository.get_account_by_email(email).await {
+            Ok(account) => account,
+            Err(error) if matches!(error.downcast_ref::<DomainError>(), Some(DomainError::NotFound(_))) => return Err(DomainError::unauthenticated("invalid email or password")),
+            Err(error) => return Err(error)
+        };
         if let Ok(_) = self.password_hasher.verify_password(password, &account.password_hash) {
             Ok(account)
         } else {
-            Err(make_error!("invalid password"))
+            Err(DomainError::unauthenticated("invalid email or password"))
         }
     }
 
-    async fn create_account(&self, email: &str, password: &str, role: Role) -> Result<String, Error> {
+    async fn create_account(&self, email: &str, password: &str) -> Result<String, Error> {
         let (hash, salt) = self.password_hasher.hash_password(password)?;
-        self.repository.create_account(&email.to_string(), &hash, &salt, role).await
+        self.repository.create_account(&email.to_string(), &hash, &salt, Role::User).await
     }
 
-    async fn update_account(&self, id: &str, email: Option<String>, password: Option<String>, role: Option<Role>) -> Result<Account, Error> {
+    async fn update_account(&self, id: &str, email: Option<String>, password: Option<String>) -> Result<Account, Error> {
         let (hash, salt) = match password {
             Some(password) => self.password_hasher
                 .hash_password(&password)
                 .map(|(h, s)| (Some(h), Some(s)))?,
             _ => (None, None)
         };
-        self.repository.update_account(id, email, hash, salt, role, None).await
+        self.repository.update_account(id, email, hash, salt, None).await
+    }
+
+    async fn change_role(&self, id: &str, role: Role, actor_id: &str) -> Result<Account, Error> {
+        let account = self.repository.change_role(id, role, actor_id).await?;
+        self.token_repository.revoke_tokens(id).await?;
+        Ok(account)
+    }
+
+    async fn verify_email(&self, id: &str, email: &str) -> Result<Account, Error> {
+        self.repository.verify_email(id, email).await
+    }
+
+    async fn update_totp(&self, id: &str, totp_secret: &str, totp_enabled: bool, recovery_code_hashes: Vec<String>) -> Result<Account, Error> {
+        self.repository.update_totp(id, totp_secret, totp_enabled, recovery_code_hashes).await
+    }
+
+    async fn consume_recovery_code(&self, id: &str, recovery_code_hash: &str) -> Result<bool, Error> {
+        self.repository.consume_recovery_code(id, recovery_code_hash).await
+    }
+
+    async fn grant_premium(&self, id: &str, premium_expiration_date: u64) -> Result<Account, Error> {
+        if premium_expiration_date <= Account::timestamp_now() {
+            return Err(DomainError::invalid_argument("premium expiration date must be in the future"));
+        }
+        self.repository.update_account(id, None, None, None, Some(premium_expiration_date)).await
+    }
+
+    async fn extend_premium(&self, id: &str, duration: u64) -> Result<Account, Error> {
+        let account = self.repository.get_account_by_id(id).await?;
+        let premium_expiration_date = account.premium_expiration_date.max(Account::timestamp_now()) + duration;
+        self.repository.update_account(id, None, None, None, Some(premium_expiration_date)).await
+    }
+
+    async fn revoke_premium(&self, id: &str) -> Result<Account, Error> {
+        let account = self.repository.update_account(id, None, None, None, Some(0)).await?;
+        self.token_repository.revoke_tokens(id).await?;
+        Ok(account)
+    }
+
+    async fn get_entitlements(&self, id: &str) -> Result<(Vec<Entitlement>, u64), Error> {
+        let account = self.repository.get_account_by_id(id).await?;
+        Ok((account.entitlements(), account.premium_expiration_date))
+    }
+
+    async fn suspend_account(&self, id: &str, suspension_expiration_date: u64) -> Result<Account, Error> {
+        if suspension_expiration_date <= Account::timestamp_now() {
+            return Err(DomainError::invalid_argument("suspension expiration date must be in the future"));
+        }
+        let account = self.repository.update_status(id, Status::Suspended, suspension_expiration_date).await?;
+        self.token_repository.revoke_tokens(id).await?;
+        Ok(account)
+    }
+
+    async fn ban_account(&self, id: &str) -> Result<Account, Error> {
+        let account = self.repository.update_status(id, Status::Banned, 0).await?;
+        self.token_repository.revoke_tokens(id).await?;
+        Ok(account)
     }
 
-    async fn update_premium(&self, id: &str, premium_expiration_date: u64) -> Result<Account, Error> {
-        self.repository.update_account(id, None, None, None, None, Some(premium_expiration_date)).await
+    async fn reinstate_account(&self, id: &str) -> Result<Account, Error> {
+        self.repository.update_status(id, Status::Active, 0).await
     }
 
     async fn delete_account(&self, id: &str) -> Result<String, Error> {
-        self.repository.delete_account(id).await
+        let deletion = match self.repository.get_deletion(id).await? {
+            Some(deletion) => deletion,
+            None => {
+                self.repository.get_account_by_id(id).await?;
+                self.repository.request_deletion(id).await?
+            }
+        };
+        if self.repository.get_account_by_id(id).await.is_ok() {
+            self.token_repository.revoke_tokens(id).await?;
+            self.repository.delete_account(id).await?;
+        }
+        self.export_repository.delete_exports(id).await?;
+        if deletion.completed_at.is_none() {
+            self.repository.publish_deleted_account(id).await?;
+        }
+        Ok(String::from(id))
+    }
+
+    async fn get_deletion_status(&self, id: &str) -> Result<Option<Deletion>, Error> {
+        self.repository.get_deletion(id).await
+    }
+
+    async fn complete_erasure(&self, id: &str, service: &str) -> Result<Deletion, Error> {
+        if !ERASING_SERVICES.contains(&service) {
+            return Err(DomainError::invalid_argument("unknown erasing service"));
+        }
+        self.repository.complete_erasure(id, service).await
+    }
+
+    async fn export_my_data(&self, id: &str) -> Result<Export, Error> {
+        self.repository.get_account_by_id(id).await?;
+        self.export_repository.create_export(id).await
+    }
+
+    async fn get_pending_exports(&self) -> Result<Vec<Export>, Error> {
+        self.export_repository.get_pending_exports().await
+    }
+
+    async fn generate_export(&self, export_id: &str) -> Result<(), Error> {
+        let export = self.export_repository.get_export(export_id).await?.ok_or(DomainError::not_found("export not found"))?;
+        if export.status != ExportStatus::Pending {
+            return Ok(());
+        }
+        let archive = async {
+            let mut account = serde_json::to_value(self.repository.get_account_by_id(&export.account_id).await?)?;
+            if let Value::Object(fields) = &mut account {
+                for field in ["password_hash", "password_salt", "totp_secret", "recovery_code_hashes"] {
+                    fields.remove(field);
+                }
+            }
+            let mut files = vec![(String::from("account.json"), serde_json::to_vec(&account)?)];
+            files.extend(self.export_repository.collect_data(&export.account_id).await?);
+            Self::write_archive(files)
+        }.await;
+        let result = match archive {
+            Ok(archive) => self.export_repository.complete_export(export_id, archive).await,
+            Err(error) => Err(error)
+        };
+        if let Err(error) = result {
+            self.export_repository.fail_export(export_id).await?;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    async fn get_export(&self, id: &str, export_id: &str) -> Result<(Export, Option<String>), Error> {
+        let export = match self.export_repository.get_export(export_id).await? {
+            Some(export) if export.account_id == id => export,
+            _ => return Err(DomainError::not_found("export not found"))
+        };
+        if export.status != ExportStatus::Ready {
+            return Ok((export, None));
+        }
+        let token = self.token_repository.generate_data_export_token(id, export_id).await?;
+        Ok((export, Some(token)))
+    }
+
+    async fn download_export(&self, id: &str, token: &str) -> Result<Vec<u8>, Error> {
+        let export_id = self.token_repository.validate_data_export_token(token).await?;
+        match self.export_repository.get_export(&export_id).await? {
+            Some(Export { account_id, status: ExportStatus::Ready, archive_id: Some(archive_id), .. }) if account_id == id => {
+                self.export_repository.get_archive(&archive_id).await
+            }
+            _ => Err(DomainError::failed_precondition("export is not available"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use zip::ZipArchive;
+
+    use super::*;
+
+    #[test]
+    fn write_archive() {
+        let files = vec![(String::from("account.json"), b"{}".to_vec()), (String::from("profile.json"), b"[1,2]".to_vec())];
+        let archive = AccountInteractorImpl::write_archive(files).unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(archive)).unwrap();
+        assert_eq!(archive.len(), 2);
+        let mut content = String::new();
+        archive.by_name("profile.json").unwrap().read_to_string(&mut content).unwrap();
+        assert_eq!(content, "[1,2]");
     }
 }
\ No newline at end of file
@@ -1,15 +1,26 @@
-use mongodb::bson::{bson, Bson, doc};
+use futures::TryStreamExt;
+use mongodb::bson::{bson, Bson, doc, Document};
 use mongodb::bson::oid::ObjectId;
-use mongodb::Collection;
+use mongodb::{Collection, IndexModel};
+use mongodb::error::{ErrorKind, WriteError, WriteFailure};
+use mongodb::options::{Collation, CollationStrength, FindOneOptions, IndexOptions, UpdateOptions};
 
-use error::make_error;
+use amqp::MessageQueue;
+use error::{DomainError, make_error};
 
-use crate::account::entity::{Account, Role};
+use crate::account::entity::{Account, Deletion, Role, RoleChange, Status};
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
+const EXCHANGE_NAME: &str = "action";
+const DELETED_ROUTING_KEY: &str = "account.deleted";
+const DUPLICATE_KEY_CODE: i32 = 11000;
+const DUPLICATE_EMAIL_DOMAIN: &str = "duplicate.invalid";
+
 #[async_trait::async_trait]
 pub trait AccountRepository {
+    async fn resolve_duplicate_emails(&self) -> Result<Vec<String>, Error>;
+    async fn create_indexes(&self) -> Result<(), Error>;
     async fn get_account_by_id(&self, id: &str) -> Result<Account, Error>;
     async fn get_account_by_email(&self, email: &str) -> Result<Account, Error>;
     async fn create_account(
@@ -25,43 +36,114 @@ pub trait AccountRepository {
         email: Option<String>,
         password_hash: Option<String>,
         password_salt: Option<String>,
-        role: Option<Role>,
         premium_expiration_date: Option<u64>,
     ) -> Result<Account, Error>;
+    async fn change_role(&self, id: &str, role: Role, actor_id: &str) -> Result<Account, Error>;
+    async fn verify_email(&self, id: &str, email: &str) -> Result<Account, Error>;
+    async fn update_totp(&self, id: &str, totp_secret: &str, totp_enabled: bool, recovery_code_hashes: Vec<String>) -> Result<Account, Error>;
+    async fn consume_recovery_code(&self, id: &str, recovery_code_hash: &str) -> Result<bool, Error>;
+    async fn update_status(&self, id: &str, status: Status, suspension_expiration_date: u64) -> Result<Account, Error>;
     async fn delete_account(&self, id: &str) -> Result<String, Error>;
+    async fn get_deletion(&self, id: &str) -> Result<Option<Deletion>, Error>;
+    async fn request_deletion(&self, id: &str) -> Result<Deletion, Error>;
+    async fn complete_erasure(&self, id: &str, service: &str) -> Result<Deletion, Error>;
+    async fn publish_deleted_account(&self, id: &str) -> Result<(), Error>;
 }
 
 pub struct AccountRepositoryImpl {
     collection: Collection<Account>,
+    role_change_collection: Collection<RoleChange>,
+    deletion_collection: Collection<Deletion>,
+    message_queue: MessageQueue,
 }
 
 impl AccountRepositoryImpl {
-    pub fn new(collection: Collection<Account>) -> Box<dyn AccountRepository + Send + Sync> {
-        Box::new(AccountRepositoryImpl { collection })
+    pub fn new(
+        collection: Collection<Account>,
+        role_change_collection: Collection<RoleChange>,
+        deletion_collection: Collection<Deletion>,
+        message_queue: MessageQueue,
+    ) -> Box<dyn AccountRepository + Send + Sync> {
+        Box::new(AccountRepositoryImpl { collection, role_change_collection, deletion_collection, message_queue })
+    }
+
+    fn email_collation() -> Collation {
+        Collation::builder().locale(String::from("en")).strength(CollationStrength::Secondary).build()
+    }
+
+    fn map_duplicate_email(error: mongodb::error::Error) -> Error {
+        match error.kind.as_ref() {
+            ErrorKind::Write(WriteFailure::WriteError(WriteError { code: DUPLICATE_KEY_CODE, .. })) => DomainError::already_exists("account with this email already exists"),
+            _ => Box::new(error)
+        }
+    }
+
+    fn select_duplicates(mut accounts: Vec<Account>) -> Vec<Account> {
+        // the verified and then the oldest account keeps the email
+        accounts.sort_by_key(|account| (!account.email_verified, account.created_at));
+        accounts.into_iter().skip(1).collect()
+    }
+
+    fn duplicate_email(id: &str) -> String {
+        format!("{}@{}", id, DUPLICATE_EMAIL_DOMAIN)
     }
 }
 
 #[async_trait::async_trait]
 impl AccountRepository for AccountRepositoryImpl {
+    async fn resolve_duplicate_emails(&self) -> Result<Vec<String>, Error> {
+        let pipeline = vec![
+            doc! { "$group": { "_id": { "$toLower": { "$trim": { "input": "$email" } } }, "ids": { "$push": "$_id" }, "count": { "$sum": 1 } } },
+            doc! { "$match": { "count": { "$gt": 1 } } },
+        ];
+        let groups: Vec<Document> = self.collection.aggregate(pipeline, None).await?.try_collect().await?;
+        let mut renamed_ids: Vec<String> = vec![];
+        for group in groups {
+            let ids = group.get_array("ids")?.clone();
+            let accounts: Vec<Account> = self.collection.find(doc! { "_id": { "$in": ids } }, None).await?.try_collect().await?;
+            for account in AccountRepositoryImpl::select_duplicates(accounts) {
+                let document = doc! {
+                    "email": AccountRepositoryImpl::duplicate_email(&account.id),
+                    "email_verified": false,
+                    "updated_at": Bson::Int64(Account::timestamp_now() as i64)
+                };
+                self.collection.update_one(doc! { "_id": &account.id }, doc! { "$set": document }, None).await?;
+                renamed_ids.push(account.id);
+            }
+        }
+        Ok(renamed_ids)
+    }
+
+    async fn create_indexes(&self) -> Result<(), Error> {
+        let options = IndexOptions::builder()
+            .name(String::from("email_unique"))
+            .unique(true)
+            .collation(AccountRepositoryImpl::email_collation())
+            .build();
+        let index = IndexModel::builder().keys(doc! { "email": 1 }).options(options).build();
+        self.collection.create_index(index, None).await?;
+        Ok(())
+    }
+
     async fn get_account_by_id(&self, id: &str) -> Result<Account, Error> {
         if let Some(account) = self.collection.find_one(doc! { "_id": id }, None).await? {
             return Ok(account);
         }
-        Err(make_error!("unable to get account by id"))
+        Err(DomainError::not_found("account not found"))
     }
 
-
     async fn get_account_by_email(&self, email: &str) -> Result<Account, Error> {
-        if let Some(account) = self.collection.find_one(doc! { "email": email }, None).await? {
+        let options = FindOneOptions::builder().collation(AccountRepositoryImpl::email_collation()).build();
+        if let Some(account) = self.collection.find_one(doc! { "email": Account::normalize_email(email) }, options).await? {
             return Ok(account);
         }
-        Err(make_error!("unable to get account by email and password"))
+        Err(DomainError::not_found("account not found"))
     }
 
     async fn create_account(&self, email: &str, password_hash: &str, password_salt: &str, role: Role) -> Result<String, Error> {
         let id = ObjectId::new().to_hex();
-        let account = Account::new(&id, email, password_hash, password_salt, role);
-        let result = self.collection.insert_one(account, None).await?;
+        let account = Account::new(&id, &Account::normalize_email(email), password_hash, password_salt, role);
+        let result = self.collection.insert_one(account, None).await.map_err(AccountRepositoryImpl::map_duplicate_email)?;
         if let Some(id) = result.inserted_id.as_str() {
             return Ok(String::from(id));
         }
@@ -74,38 +156,170 @@ impl AccountRepository for AccountRepositoryImpl {
         email: Option<String>,
         password_hash: Option<String>,
         password_salt: Option<String>,
-        role: Option<Role>,
         premium_expiration_date: Option<u64>,
     ) -> Result<Account, Error> {
         let timestamp = bson!(Account::timestamp_now() as i64);
         let mut document = doc! { "updated_at": timestamp };
         if let Some(email) = email {
-            document.insert("email", email);
+            document.insert("email", Account::normalize_email(&email));
+            document.insert("email_verified", false);
         }
         if !password_hash.is_none() && !password_salt.is_none() {
             document.insert("password_hash", password_hash.unwrap());
             document.insert("password_salt", password_salt.unwrap());
         }
-        if let Some(role) = role {
-            document.insert("role", match role {
-                Role::User => 0,
-                Role::Moderator => 1
-            });
-        }
         if let Some(premium_expiration_date) = premium_expiration_date {
             document.insert("premium_expiration_date", Bson::Int64(premium_expiration_date as i64));
         }
-        let result = self.collection.update_one(doc! { "_id": id }, document, None).await?;
+        let result = self.collection.update_one(doc! { "_id": id }, doc! { "$set": document }, None).await.map_err(AccountRepositoryImpl::map_duplicate_email)?;
         if result.modified_count > 0 {
             return self.get_account_by_id(id).await;
         }
-        Err(make_error!("unable to update account"))
+        Err(DomainError::not_found("account not found"))
+    }
+
+    async fn change_role(&self, id: &str, role: Role, actor_id: &str) -> Result<Account, Error> {
+        let account = self.get_account_by_id(id).await?;
+        if account.role == role {
+            return Ok(account);
+        }
+        let document = doc! {
+            "role": match role {
+                Role::User => "User",
+                Role::Moderator => "Moderator"
+            },
+            "updated_at": Bson::Int64(Account::timestamp_now() as i64)
+        };
+        let result = self.collection.update_one(doc! { "_id": id }, doc! { "$set": document }, None).await?;
+        if result.matched_count > 0 {
+            let role_change = RoleChange::new(&ObjectId::new().to_hex(), id, actor_id, account.role, role);
+            self.role_change_collection.insert_one(role_change, None).await?;
+            return self.get_account_by_id(id).await;
+        }
+        Err(DomainError::not_found("account not found"))
+    }
+
+    async fn verify_email(&self, id: &str, email: &str) -> Result<Account, Error> {
+        let document = doc! {
+            "email_verified": true,
+            "updated_at": Bson::Int64(Account::timestamp_now() as i64)
+        };
+        let options = UpdateOptions::builder().collation(AccountRepositoryImpl::email_collation()).build();
+        let result = self.collection.update_one(doc! { "_id": id, "email": Account::normalize_email(email) }, doc! { "$set": document }, options).await?;
+        if result.matched_count > 0 {
+            return self.get_account_by_id(id).await;
+        }
+        Err(DomainError::failed_precondition("email does not match the account"))
+    }
+
+    async fn update_totp(&self, id: &str, totp_secret: &str, totp_enabled: bool, recovery_code_hashes: Vec<String>) -> Result<Account, Error> {
+        let document = doc! {
+            "totp_secret": totp_secret,
+            "totp_enabled": totp_enabled,
+            "recovery_code_hashes": recovery_code_hashes,
+            "updated_at": Bson::Int64(Account::timestamp_now() as i64)
+        };
+        let result = self.collection.update_one(doc! { "_id": id }, doc! { "$set": document }, None).await?;
+        if result.matched_count > 0 {
+            return self.get_account_by_id(id).await;
+        }
+        Err(DomainError::not_found("account not found"))
+    }
+
+    async fn consume_recovery_code(&self, id: &str, recovery_code_hash: &str) -> Result<bool, Error> {
+        let result = self.collection.update_one(
+            doc! { "_id": id, "recovery_code_hashes": recovery_code_hash },
+            doc! { "$pull": { "recovery_code_hashes": recovery_code_hash } },
+            None,
+        ).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    async fn update_status(&self, id: &str, status: Status, suspension_expiration_date: u64) -> Result<Account, Error> {
+        let document = doc! {
+            "status": match status {
+                Status::Active => "Active",
+                Status::Suspended => "Suspended",
+                Status::Banned => "Banned"
+            },
+            "suspension_expiration_date": Bson::Int64(suspension_expiration_date as i64),
+            "updated_at": Bson::Int64(Account::timestamp_now() as i64)
+        };
+        let result = self.collection.update_one(doc! { "_id": id }, doc! { "$set": document }, None).await?;
+        if result.matched_count > 0 {
+            return self.get_account_by_id(id).await;
+        }
+        Err(DomainError::not_found("account not found"))
     }
 
     async fn delete_account(&self, id: &str) -> Result<String, Error> {
         if self.collection.delete_one(doc! { "_id": id }, None).await?.deleted_count > 0 {
             return Ok(String::from(id));
         }
-        Err(make_error!("unable to delete account"))
+        Err(DomainError::not_found("account not found"))
+    }
+
+    async fn get_deletion(&self, id: &str) -> Result<Option<Deletion>, Error> {
+        Ok(self.deletion_collection.find_one(doc! { "_id": id }, None).await?)
+    }
+
+    async fn request_deletion(&self, id: &str) -> Result<Deletion, Error> {
+        let deletion = Deletion::new(id);
+        self.deletion_collection.insert_one(&deletion, None).await?;
+        Ok(deletion)
+    }
+
+    async fn complete_erasure(&self, id: &str, service: &str) -> Result<Deletion, Error> {
+        let completed_at = Account::timestamp_now();
+        let document = doc! { format!("erasures.{}", service): Bson::Int64(completed_at as i64) };
+        if self.deletion_collection.update_one(doc! { "_id": id }, doc! { "$set": document }, None).await?.matched_count > 0 {
+            if let Some(deletion) = self.get_deletion(id).await? {
+                if deletion.completed_at.is_none() && deletion.is_erased() {
+                    let document = doc! { "completed_at": Bson::Int64(completed_at as i64) };
+                    self.deletion_collection.update_one(doc! { "_id": id }, doc! { "$set": document }, None).await?;
+                    return Ok(Deletion { completed_at: Some(completed_at), ..deletion });
+                }
+                return Ok(deletion);
+            }
+        }
+        Err(DomainError::not_found("deletion was not requested"))
+    }
+
+    async fn publish_deleted_account(&self, id: &str) -> Result<(), Error> {
+        self.message_queue.publish(EXCHANGE_NAME, DELETED_ROUTING_KEY, id.as_bytes()).await.map_err(DomainError::unavailable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::from_document;
+
+    use super::*;
+
+    fn account(id: &str, email_verified: bool, created_at: u64) -> Account {
+        Account { email_verified, created_at, ..Account::new(id, "user@example.com", "", "", Role::User) }
+    }
+
+    #[test]
+    fn map_duplicate_email() {
+        let write_error: WriteError = from_document(doc! { "code": DUPLICATE_KEY_CODE, "errmsg": "duplicate key" }).unwrap();
+        let error = AccountRepositoryImpl::map_duplicate_email(ErrorKind::Write(WriteFailure::WriteError(write_error)).into());
+        assert!(matches!(error.downcast_ref::<DomainError>(), Some(DomainError::AlreadyExists(_))));
+    }
+
+    #[test]
+    fn keep_other_errors() {
+        let write_error: WriteError = from_document(doc! { "code": 2, "errmsg": "bad value" }).unwrap();
+        let error = AccountRepositoryImpl::map_duplicate_email(ErrorKind::Write(WriteFailure::WriteError(write_error)).into());
+        assert!(error.downcast_ref::<DomainError>().is_none());
+        assert!(error.downcast_ref::<mongodb::error::Error>().is_some());
+    }
+
+    #[test]
+    fn select_duplicates() {
+        let accounts = vec![account("newer", false, 2), account("verified", true, 3), account("older", false, 1)];
+        let duplicates: Vec<String> = AccountRepositoryImpl::select_duplicates(accounts).into_iter().map(|account| account.id).collect();
+        assert_eq!(duplicates, vec!["older", "newer"]);
+        assert_eq!(AccountRepositoryImpl::duplicate_email("older"), "older@duplicate.invalid");
     }
 }
\ No newline at end of file
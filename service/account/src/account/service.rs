@@ -1,18 +1,28 @@
+use std::sync::Arc;
+
 use tonic::{Request, Response, Status};
 
 use crate::account::entity::Role;
 use crate::account::interactor::AccountInteractor;
-use crate::account::pb::{CreateAccountRequest, CreateAccountResponse, DeleteAccountRequest, DeleteAccountResponse, GetAccountByCredentialsRequest, GetAccountByCredentialsResponse, GetAccountByIdRequest, GetAccountByIdResponse, Role as RoleMessage, UpdateAccountRequest, UpdateAccountResponse};
+use crate::account::pb::{BanAccountRequest, BanAccountResponse, ChangeRoleRequest, ChangeRoleResponse, ConsumeRecoveryCodeRequest, ConsumeRecoveryCodeResponse, CreateAccountRequest, CreateAccountResponse, DeleteAccountRequest, DeleteAccountResponse, DownloadExportRequest, DownloadExportResponse, Entitlement as EntitlementMessage, Export as ExportMessage, ExportMyDataRequest, ExportMyDataResponse, ExtendPremiumRequest, ExtendPremiumResponse, GetAccountByCredentialsRequest, GetAccountByCredentialsResponse, GetAccountByEmailRequest, GetAccountByEmailResponse, GetAccountByIdRequest, GetAccountByIdResponse, GetDeletionStatusRequest, GetDeletionStatusResponse, GetEntitlementsRequest, GetEntitlementsResponse, GetExportRequest, GetExportResponse, GrantPremiumRequest, GrantPremiumResponse, ReinstateAccountRequest, ReinstateAccountResponse, RevokePremiumRequest, RevokePremiumResponse, Role as RoleMessage, SuspendAccountRequest, SuspendAccountResponse, UpdateAccountRequest, UpdateAccountResponse, UpdateTotpRequest, UpdateTotpResponse, VerifyEmailRequest, VerifyEmailResponse};
 use crate::account::pb::account_service_server::AccountService;
 
 pub struct AccountServiceImpl {
-    interactor: Box<dyn AccountInteractor + Send + Sync>,
+    interactor: Arc<Box<dyn AccountInteractor + Send + Sync>>,
 }
 
 impl AccountServiceImpl {
-    pub fn new(interactor: Box<dyn AccountInteractor + Send + Sync>) -> impl AccountService {
+    pub fn new(interactor: Arc<Box<dyn AccountInteractor + Send + Sync>>) -> impl AccountService {
         AccountServiceImpl { interactor }
     }
+
+    pub fn spawn_export(interactor: Arc<Box<dyn AccountInteractor + Send + Sync>>, export_id: String) {
+        tokio::spawn(async move {
+            if let Err(error) = interactor.generate_export(&export_id).await {
+                log::error!("Unable to generate export '{}': {}", export_id, error);
+            }
+        });
+    }
 }
 
 #[tonic::async_trait]
@@ -29,7 +39,23 @@ impl AccountService for AccountServiceImpl {
                     GetAccountByIdResponse { account: Some(account.into()) }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn get_account_by_email(&self, request: Request<GetAccountByEmailRequest>) -> Result<Response<GetAccountByEmailResponse>, Status> {
+        let GetAccountByEmailRequest { email } = request.into_inner();
+        if email.is_empty() {
+            return status::Status::invalid_arguments(vec!["email"]);
+        }
+
+        match self.interactor.get_account_by_email(&email).await {
+            Ok(account) => Ok(
+                Response::new(
+                    GetAccountByEmailResponse { account: Some(account.into()) }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -45,45 +71,223 @@ impl AccountService for AccountServiceImpl {
                     GetAccountByCredentialsResponse { account: Some(account.into()) }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn create_account(&self, request: Request<CreateAccountRequest>) -> Result<Response<CreateAccountResponse>, Status> {
-        let CreateAccountRequest { email, password, role } = request.into_inner();
+        let CreateAccountRequest { email, password } = request.into_inner();
         if email.is_empty() || password.is_empty() {
             return status::Status::invalid_arguments(vec!["email", "password"]);
         }
 
-        match self.interactor.create_account(&email, &password, match RoleMessage::from_i32(role).unwrap() {
-            RoleMessage::User => Role::User,
-            RoleMessage::Moderator => Role::Moderator
-        }).await {
+        match self.interactor.create_account(&email, &password).await {
             Ok(id) => Ok(
                 Response::new(
                     CreateAccountResponse { id }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn update_account(&self, request: Request<UpdateAccountRequest>) -> Result<Response<UpdateAccountResponse>, Status> {
-        let UpdateAccountRequest { id, email, password, role } = request.into_inner();
+        let UpdateAccountRequest { id, email, password } = request.into_inner();
         if id.is_empty() {
             return status::Status::invalid_arguments(vec!["id"]);
         }
 
-        match self.interactor.update_account(&id, email, password, role.map(|r| match RoleMessage::from_i32(r).unwrap() {
-            RoleMessage::User => Role::User,
-            RoleMessage::Moderator => Role::Moderator
-        })).await {
+        match self.interactor.update_account(&id, email, password).await {
             Ok(account) => Ok(
                 Response::new(
                     UpdateAccountResponse { account: Some(account.into()) }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn change_role(&self, request: Request<ChangeRoleRequest>) -> Result<Response<ChangeRoleResponse>, Status> {
+        let ChangeRoleRequest { id, role, actor_id } = request.into_inner();
+        if id.is_empty() || actor_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id", "actor_id"]);
+        }
+        let role = match RoleMessage::try_from(role) {
+            Ok(RoleMessage::User) => Role::User,
+            Ok(RoleMessage::Moderator) => Role::Moderator,
+            Ok(RoleMessage::Unspecified) | Err(_) => return status::Status::invalid_arguments(vec!["role"])
+        };
+
+        match self.interactor.change_role(&id, role, &actor_id).await {
+            Ok(account) => Ok(
+                Response::new(
+                    ChangeRoleResponse { account: Some(account.into()) }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn verify_email(&self, request: Request<VerifyEmailRequest>) -> Result<Response<VerifyEmailResponse>, Status> {
+        let VerifyEmailRequest { id, email } = request.into_inner();
+        if id.is_empty() || email.is_empty() {
+            return status::Status::invalid_arguments(vec!["id", "email"]);
+        }
+
+        match self.interactor.verify_email(&id, &email).await {
+            Ok(account) => Ok(
+                Response::new(
+                    VerifyEmailResponse { account: Some(account.into()) }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn update_totp(&self, request: Request<UpdateTotpRequest>) -> Result<Response<UpdateTotpResponse>, Status> {
+        let UpdateTotpRequest { id, totp_secret, totp_enabled, recovery_code_hashes } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        match self.interactor.update_totp(&id, &totp_secret, totp_enabled, recovery_code_hashes).await {
+            Ok(account) => Ok(
+                Response::new(
+                    UpdateTotpResponse { account: Some(account.into()) }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn consume_recovery_code(&self, request: Request<ConsumeRecoveryCodeRequest>) -> Result<Response<ConsumeRecoveryCodeResponse>, Status> {
+        let ConsumeRecoveryCodeRequest { id, recovery_code_hash } = request.into_inner();
+        if id.is_empty() || recovery_code_hash.is_empty() {
+            return status::Status::invalid_arguments(vec!["id", "recovery_code_hash"]);
+        }
+
+        match self.interactor.consume_recovery_code(&id, &recovery_code_hash).await {
+            Ok(is_consumed) => Ok(
+                Response::new(
+                    ConsumeRecoveryCodeResponse { is_consumed }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn grant_premium(&self, request: Request<GrantPremiumRequest>) -> Result<Response<GrantPremiumResponse>, Status> {
+        let GrantPremiumRequest { id, premium_expiration_date } = request.into_inner();
+        if id.is_empty() || premium_expiration_date == 0 {
+            return status::Status::invalid_arguments(vec!["id", "premium_expiration_date"]);
+        }
+
+        match self.interactor.grant_premium(&id, premium_expiration_date).await {
+            Ok(account) => Ok(
+                Response::new(
+                    GrantPremiumResponse { account: Some(account.into()) }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn extend_premium(&self, request: Request<ExtendPremiumRequest>) -> Result<Response<ExtendPremiumResponse>, Status> {
+        let ExtendPremiumRequest { id, duration } = request.into_inner();
+        if id.is_empty() || duration == 0 {
+            return status::Status::invalid_arguments(vec!["id", "duration"]);
+        }
+
+        match self.interactor.extend_premium(&id, duration).await {
+            Ok(account) => Ok(
+                Response::new(
+                    ExtendPremiumResponse { account: Some(account.into()) }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn revoke_premium(&self, request: Request<RevokePremiumRequest>) -> Result<Response<RevokePremiumResponse>, Status> {
+        let RevokePremiumRequest { id } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        match self.interactor.revoke_premium(&id).await {
+            Ok(account) => Ok(
+                Response::new(
+                    RevokePremiumResponse { account: Some(account.into()) }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn get_entitlements(&self, request: Request<GetEntitlementsRequest>) -> Result<Response<GetEntitlementsResponse>, Status> {
+        let GetEntitlementsRequest { id } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        match self.interactor.get_entitlements(&id).await {
+            Ok((entitlements, premium_expiration_date)) => Ok(
+                Response::new(
+                    GetEntitlementsResponse {
+                        entitlements: entitlements.into_iter().map(|entitlement| i32::from(EntitlementMessage::from(entitlement))).collect(),
+                        premium_expiration_date,
+                    }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn suspend_account(&self, request: Request<SuspendAccountRequest>) -> Result<Response<SuspendAccountResponse>, Status> {
+        let SuspendAccountRequest { id, suspension_expiration_date } = request.into_inner();
+        if id.is_empty() || suspension_expiration_date == 0 {
+            return status::Status::invalid_arguments(vec!["id", "suspension_expiration_date"]);
+        }
+
+        match self.interactor.suspend_account(&id, suspension_expiration_date).await {
+            Ok(account) => Ok(
+                Response::new(
+                    SuspendAccountResponse { account: Some(account.into()) }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn ban_account(&self, request: Request<BanAccountRequest>) -> Result<Response<BanAccountResponse>, Status> {
+        let BanAccountRequest { id } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        match self.interactor.ban_account(&id).await {
+            Ok(account) => Ok(
+                Response::new(
+                    BanAccountResponse { account: Some(account.into()) }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn reinstate_account(&self, request: Request<ReinstateAccountRequest>) -> Result<Response<ReinstateAccountResponse>, Status> {
+        let ReinstateAccountRequest { id } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        match self.interactor.reinstate_account(&id).await {
+            Ok(account) => Ok(
+                Response::new(
+                    ReinstateAccountResponse { account: Some(account.into()) }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -99,7 +303,80 @@ impl AccountService for AccountServiceImpl {
                     DeleteAccountResponse { id }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn get_deletion_status(&self, request: Request<GetDeletionStatusRequest>) -> Result<Response<GetDeletionStatusResponse>, Status> {
+        let GetDeletionStatusRequest { id } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        match self.interactor.get_deletion_status(&id).await {
+            Ok(Some(deletion)) => Ok(
+                Response::new(
+                    GetDeletionStatusResponse { deletion: Some(deletion.into()) }
+                )
+            ),
+            Ok(None) => status::Status::not_found("Deletion was not requested"),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn export_my_data(&self, request: Request<ExportMyDataRequest>) -> Result<Response<ExportMyDataResponse>, Status> {
+        let ExportMyDataRequest { id } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        match self.interactor.export_my_data(&id).await {
+            Ok(export) => {
+                Self::spawn_export(Arc::clone(&self.interactor), export.id.clone());
+                Ok(
+                    Response::new(
+                        ExportMyDataResponse { export: Some(export.into()) }
+                    )
+                )
+            }
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn get_export(&self, request: Request<GetExportRequest>) -> Result<Response<GetExportResponse>, Status> {
+        let GetExportRequest { id, export_id } = request.into_inner();
+        if id.is_empty() || export_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id", "export_id"]);
+        }
+
+        match self.interactor.get_export(&id, &export_id).await {
+            Ok((export, download_token)) => Ok(
+                Response::new(
+                    GetExportResponse {
+                        export: Some(ExportMessage {
+                            download_token: download_token.unwrap_or_default(),
+                            ..export.into()
+                        })
+                    }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn download_export(&self, request: Request<DownloadExportRequest>) -> Result<Response<DownloadExportResponse>, Status> {
+        let DownloadExportRequest { id, token } = request.into_inner();
+        if id.is_empty() || token.is_empty() {
+            return status::Status::invalid_arguments(vec!["id", "token"]);
+        }
+
+        match self.interactor.download_export(&id, &token).await {
+            Ok(archive) => Ok(
+                Response::new(
+                    DownloadExportResponse { archive }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
         }
     }
 }
\ No newline at end of file
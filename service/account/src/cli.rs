@@ -0,0 +1,21 @@
+use error::make_error;
+
+use crate::account::entity::Role;
+use crate::account::interactor::AccountInteractor;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+const PROVISION_MODERATOR_COMMAND: &str = "provision-moderator";
+const ACTOR_ID: &str = "cli";
+
+pub async fn run(interactor: &(dyn AccountInteractor + Send + Sync), args: &[String]) -> Result<(), Error> {
+    match args {
+        [command, email] if command == PROVISION_MODERATOR_COMMAND => {
+            let account = interactor.get_account_by_email(email).await?;
+            interactor.change_role(&account.id, Role::Moderator, ACTOR_ID).await?;
+            println!("Account '{}' is now a moderator", account.id);
+            Ok(())
+        }
+        _ => Err(make_error!("usage: account provision-moderator <email>"))
+    }
+}
\ No newline at end of file
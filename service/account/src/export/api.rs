@@ -0,0 +1,54 @@
+use tonic::Request;
+use tonic::transport::Channel;
+
+use error::DomainError;
+
+use crate::export::conversation::conversation_service_client::ConversationServiceClient;
+use crate::export::matchmaking::matchmaking_service_client::MatchmakingServiceClient;
+use crate::export::profile::profile_service_client::ProfileServiceClient;
+use crate::export::safety::safety_service_client::SafetyServiceClient;
+use crate::export::support::support_service_client::SupportServiceClient;
+use crate::export::{conversation, matchmaking, profile, safety, support};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+#[tonic::async_trait]
+pub trait ExportApi {
+    async fn export_data(&self, service: &str, id: &str) -> Result<Vec<u8>, Error>;
+}
+
+pub struct ExportApiImpl {
+    profile_client: ProfileServiceClient<Channel>,
+    matchmaking_client: MatchmakingServiceClient<Channel>,
+    conversation_client: ConversationServiceClient<Channel>,
+    safety_client: SafetyServiceClient<Channel>,
+    support_client: SupportServiceClient<Channel>,
+}
+
+impl ExportApiImpl {
+    pub fn new(
+        profile_client: ProfileServiceClient<Channel>,
+        matchmaking_client: MatchmakingServiceClient<Channel>,
+        conversation_client: ConversationServiceClient<Channel>,
+        safety_client: SafetyServiceClient<Channel>,
+        support_client: SupportServiceClient<Channel>,
+    ) -> Box<dyn ExportApi + Send + Sync> {
+        Box::new(ExportApiImpl { profile_client, matchmaking_client, conversation_client, safety_client, support_client })
+    }
+}
+
+#[tonic::async_trait]
+impl ExportApi for ExportApiImpl {
+    async fn export_data(&self, service: &str, id: &str) -> Result<Vec<u8>, Error> {
+        let id = String::from(id);
+        let data = match service {
+            "profile" => self.profile_client.clone().export_data(Request::new(profile::ExportDataRequest { id })).await?.into_inner().data,
+            "matchmaking" => self.matchmaking_client.clone().export_data(Request::new(matchmaking::ExportDataRequest { id })).await?.into_inner().data,
+            "conversation" => self.conversation_client.clone().export_data(Request::new(conversation::ExportDataRequest { id })).await?.into_inner().data,
+            "safety" => self.safety_client.clone().export_data(Request::new(safety::ExportDataRequest { id })).await?.into_inner().data,
+            "support" => self.support_client.clone().export_data(Request::new(support::ExportDataRequest { id })).await?.into_inner().data,
+            _ => return Err(DomainError::invalid_argument("unknown exporting service"))
+        };
+        Ok(data)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+pub const EXPORTING_SERVICES: [&str; 5] = ["profile", "matchmaking", "conversation", "safety", "support"];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ExportStatus {
+    Pending,
+    Ready,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Export {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub account_id: String,
+    pub status: ExportStatus,
+    pub archive_id: Option<String>,
+    pub requested_at: u64,
+    pub completed_at: Option<u64>,
+}
+
+impl Export {
+    pub fn timestamp_now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64
+    }
+
+    pub fn new(id: &str, account_id: &str) -> Self {
+        Self {
+            id: String::from(id),
+            account_id: String::from(account_id),
+            status: ExportStatus::Pending,
+            archive_id: None,
+            requested_at: Export::timestamp_now(),
+            completed_at: None,
+        }
+    }
+}
\ No newline at end of file
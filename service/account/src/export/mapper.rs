@@ -0,0 +1,18 @@
+use crate::account::pb::{Export as ExportMessage, ExportStatus as ExportStatusMessage};
+use crate::export::entity::{Export, ExportStatus};
+
+impl From<Export> for ExportMessage {
+    fn from(value: Export) -> Self {
+        Self {
+            id: value.id,
+            status: i32::from(match value.status {
+                ExportStatus::Pending => ExportStatusMessage::Pending,
+                ExportStatus::Ready => ExportStatusMessage::Ready,
+                ExportStatus::Failed => ExportStatusMessage::Failed
+            }),
+            requested_at: value.requested_at,
+            completed_at: value.completed_at.unwrap_or(0),
+            download_token: String::new(),
+        }
+    }
+}
\ No newline at end of file
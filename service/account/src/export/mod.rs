@@ -0,0 +1,28 @@
+pub mod api;
+pub mod entity;
+pub mod mapper;
+pub mod repository;
+
+pub mod criteria {
+    tonic::include_proto!("criteria");
+}
+
+pub mod profile {
+    tonic::include_proto!("profile");
+}
+
+pub mod matchmaking {
+    tonic::include_proto!("matchmaking");
+}
+
+pub mod conversation {
+    tonic::include_proto!("conversation");
+}
+
+pub mod safety {
+    tonic::include_proto!("safety");
+}
+
+pub mod support {
+    tonic::include_proto!("support");
+}
\ No newline at end of file
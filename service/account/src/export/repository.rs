@@ -0,0 +1,111 @@
+use futures::io::Cursor;
+use futures::TryStreamExt;
+use mongodb::bson::{Bson, doc};
+use mongodb::bson::oid::ObjectId;
+use mongodb::Collection;
+use mongodb::gridfs::GridFsBucket;
+
+use error::DomainError;
+
+use crate::export::api::ExportApi;
+use crate::export::entity::{Export, EXPORTING_SERVICES};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+#[async_trait::async_trait]
+pub trait ExportRepository {
+    async fn get_export(&self, id: &str) -> Result<Option<Export>, Error>;
+    async fn get_pending_exports(&self) -> Result<Vec<Export>, Error>;
+    async fn create_export(&self, account_id: &str) -> Result<Export, Error>;
+    async fn complete_export(&self, id: &str, archive: Vec<u8>) -> Result<(), Error>;
+    async fn fail_export(&self, id: &str) -> Result<(), Error>;
+    async fn delete_exports(&self, account_id: &str) -> Result<(), Error>;
+    async fn get_archive(&self, archive_id: &str) -> Result<Vec<u8>, Error>;
+    async fn collect_data(&self, account_id: &str) -> Result<Vec<(String, Vec<u8>)>, Error>;
+}
+
+pub struct ExportRepositoryImpl {
+    collection: Collection<Export>,
+    bucket: GridFsBucket,
+    api: Box<dyn ExportApi + Send + Sync>,
+}
+
+impl ExportRepositoryImpl {
+    pub fn new(collection: Collection<Export>, bucket: GridFsBucket, api: Box<dyn ExportApi + Send + Sync>) -> Box<dyn ExportRepository + Send + Sync> {
+        Box::new(ExportRepositoryImpl { collection, bucket, api })
+    }
+
+    async fn delete_archive(&self, archive_id: &str) -> Result<(), Error> {
+        let mut files = self.bucket.find(doc! { "_id": archive_id }, None).await?;
+        while let Some(file) = files.try_next().await? {
+            self.bucket.delete(file.id).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ExportRepository for ExportRepositoryImpl {
+    async fn get_export(&self, id: &str) -> Result<Option<Export>, Error> {
+        Ok(self.collection.find_one(doc! { "_id": id }, None).await?)
+    }
+
+    async fn get_pending_exports(&self) -> Result<Vec<Export>, Error> {
+        Ok(self.collection.find(doc! { "status": "Pending" }, None).await?.try_collect().await?)
+    }
+
+    async fn create_export(&self, account_id: &str) -> Result<Export, Error> {
+        let export = Export::new(&ObjectId::new().to_hex(), account_id);
+        self.collection.insert_one(&export, None).await?;
+        Ok(export)
+    }
+
+    async fn complete_export(&self, id: &str, archive: Vec<u8>) -> Result<(), Error> {
+        // the archive is named after the export, so an upload left behind by an interrupted generation is replaced
+        self.delete_archive(id).await?;
+        self.bucket.upload_from_futures_0_3_reader_with_id(Bson::String(String::from(id)), format!("{}.zip", id), Cursor::new(archive), None).await?;
+        let document = doc! {
+            "status": "Ready",
+            "archive_id": id,
+            "completed_at": Bson::Int64(Export::timestamp_now() as i64)
+        };
+        if self.collection.update_one(doc! { "_id": id }, doc! { "$set": document }, None).await?.matched_count > 0 {
+            return Ok(());
+        }
+        Err(DomainError::not_found("export not found"))
+    }
+
+    async fn fail_export(&self, id: &str) -> Result<(), Error> {
+        let document = doc! {
+            "status": "Failed",
+            "completed_at": Bson::Int64(Export::timestamp_now() as i64)
+        };
+        if self.collection.update_one(doc! { "_id": id }, doc! { "$set": document }, None).await?.matched_count > 0 {
+            return Ok(());
+        }
+        Err(DomainError::not_found("export not found"))
+    }
+
+    async fn delete_exports(&self, account_id: &str) -> Result<(), Error> {
+        let exports: Vec<Export> = self.collection.find(doc! { "account_id": account_id }, None).await?.try_collect().await?;
+        for export in exports {
+            self.delete_archive(&export.id).await?;
+        }
+        self.collection.delete_many(doc! { "account_id": account_id }, None).await?;
+        Ok(())
+    }
+
+    async fn get_archive(&self, archive_id: &str) -> Result<Vec<u8>, Error> {
+        let mut archive = Vec::new();
+        self.bucket.download_to_futures_0_3_writer(Bson::String(String::from(archive_id)), &mut archive).await?;
+        Ok(archive)
+    }
+
+    async fn collect_data(&self, account_id: &str) -> Result<Vec<(String, Vec<u8>)>, Error> {
+        let mut data = vec![];
+        for service in EXPORTING_SERVICES {
+            data.push((format!("{}.json", service), self.api.export_data(service, account_id).await?));
+        }
+        Ok(data)
+    }
+}
\ No newline at end of file
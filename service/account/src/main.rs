@@ -1,26 +1,124 @@
 use std::error::Error;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
-use tonic::transport::Server;
+use mongodb::options::GridFsBucketOptions;
+use tonic::transport::{Channel, Server};
+
+use crate::export::conversation::conversation_service_client::ConversationServiceClient;
+use crate::export::matchmaking::matchmaking_service_client::MatchmakingServiceClient;
+use crate::export::profile::profile_service_client::ProfileServiceClient;
+use crate::export::safety::safety_service_client::SafetyServiceClient;
+use crate::export::support::support_service_client::SupportServiceClient;
+use crate::token::pb::token_service_client::TokenServiceClient;
 
 mod account;
+mod cli;
+mod export;
 mod password;
+mod token;
 
 const SERVICE_NAME: &str = "account";
+const TOKEN_SERVICE_NAME: &str = "token";
+const PROFILE_SERVICE_NAME: &str = "profile";
+const MATCHMAKING_SERVICE_NAME: &str = "matchmaking";
+const CONVERSATION_SERVICE_NAME: &str = "conversation";
+const SAFETY_SERVICE_NAME: &str = "safety";
+const SUPPORT_SERVICE_NAME: &str = "support";
 const ACCOUNTS_COLLECTION: &str = "accounts";
+const ROLE_CHANGES_COLLECTION: &str = "role_changes";
+const DELETIONS_COLLECTION: &str = "deletions";
+const EXPORTS_COLLECTION: &str = "exports";
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
+    env_logger::init();
+
     let cfg = configuration::Config::default(SERVICE_NAME)?;
+    let token_cfg = configuration::Config::default(TOKEN_SERVICE_NAME)?;
+    let profile_cfg = configuration::Config::default(PROFILE_SERVICE_NAME)?;
+    let matchmaking_cfg = configuration::Config::default(MATCHMAKING_SERVICE_NAME)?;
+    let conversation_cfg = configuration::Config::default(CONVERSATION_SERVICE_NAME)?;
+    let safety_cfg = configuration::Config::default(SAFETY_SERVICE_NAME)?;
+    let support_cfg = configuration::Config::default(SUPPORT_SERVICE_NAME)?;
 
     let mongodb = mongodb::Client::with_uri_str(format!("mongodb://{}:{}", cfg.mongo_hostname.unwrap(), cfg.mongo_port.unwrap())).await?;
     let database = mongodb.database(SERVICE_NAME);
     let collection = database.collection::<account::entity::Account>(ACCOUNTS_COLLECTION);
+    let role_change_collection = database.collection::<account::entity::RoleChange>(ROLE_CHANGES_COLLECTION);
+    let deletion_collection = database.collection::<account::entity::Deletion>(DELETIONS_COLLECTION);
+    let export_collection = database.collection::<export::entity::Export>(EXPORTS_COLLECTION);
+    let export_bucket = database.gridfs_bucket(GridFsBucketOptions::builder().bucket_name(String::from(EXPORTS_COLLECTION)).build());
+
+    let amqp_hostname = cfg.amqp_hostname.unwrap();
+    let amqp_port = cfg.amqp_port.unwrap();
+
+    let message_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+
+    let create_channel_url: fn(&str, &str) -> &'static str = |hostname, port| Box::leak(format!("https://{}:{}", hostname, port).into_boxed_str());
+
+    let token_channel_url = create_channel_url(&token_cfg.service_hostname.unwrap(), &token_cfg.service_port.unwrap());
+    let token_channel = Channel::from_static(token_channel_url).connect_lazy();
+    let token_client = TokenServiceClient::new(token_channel);
+    let token_api = token::api::TokenApiImpl::new(token_client);
+    let token_repository = token::repository::TokenRepositoryImpl::new(token_api);
+
+    let profile_channel_url = create_channel_url(&profile_cfg.service_hostname.unwrap(), &profile_cfg.service_port.unwrap());
+    let profile_channel = Channel::from_static(profile_channel_url).connect_lazy();
+    let profile_client = ProfileServiceClient::new(profile_channel);
+
+    let matchmaking_channel_url = create_channel_url(&matchmaking_cfg.service_hostname.unwrap(), &matchmaking_cfg.service_port.unwrap());
+    let matchmaking_channel = Channel::from_static(matchmaking_channel_url).connect_lazy();
+    let matchmaking_client = MatchmakingServiceClient::new(matchmaking_channel);
+
+    let conversation_channel_url = create_channel_url(&conversation_cfg.service_hostname.unwrap(), &conversation_cfg.service_port.unwrap());
+    let conversation_channel = Channel::from_static(conversation_channel_url).connect_lazy();
+    let conversation_client = ConversationServiceClient::new(conversation_channel);
+
+    let safety_channel_url = create_channel_url(&safety_cfg.service_hostname.unwrap(), &safety_cfg.service_port.unwrap());
+    let safety_channel = Channel::from_static(safety_channel_url).connect_lazy();
+    let safety_client = SafetyServiceClient::new(safety_channel);
+
+    let support_channel_url = create_channel_url(&support_cfg.service_hostname.unwrap(), &support_cfg.service_port.unwrap());
+    let support_channel = Channel::from_static(support_channel_url).connect_lazy();
+    let support_client = SupportServiceClient::new(support_channel);
+
+    let export_api = export::api::ExportApiImpl::new(profile_client, matchmaking_client, conversation_client, safety_client, support_client);
+    let export_repository = export::repository::ExportRepositoryImpl::new(export_collection, export_bucket, export_api);
 
     let hasher = password::hasher::DefaultHasher::new();
-    let repository = account::repository::AccountRepositoryImpl::new(collection);
-    let interactor = account::interactor::AccountInteractorImpl::new(hasher, repository);
-    let service = account::service::AccountServiceImpl::new(interactor);
+    let repository = account::repository::AccountRepositoryImpl::new(collection, role_change_collection, deletion_collection, message_queue);
+    let renamed_ids = repository.resolve_duplicate_emails().await.map_err(|error| error as Box<dyn Error>)?;
+    if !renamed_ids.is_empty() {
+        println!("Renamed the emails of {} accounts sharing an email: {}", renamed_ids.len(), renamed_ids.join(", "));
+    }
+    if let Err(error) = repository.create_indexes().await {
+        log::error!("Unable to create the unique email index: {}", error);
+    }
+    let interactor = Arc::new(account::interactor::AccountInteractorImpl::new(hasher, repository, token_repository, export_repository));
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        return cli::run(interactor.as_ref().as_ref(), &args).await.map_err(|error| error as Box<dyn Error>);
+    }
+
+    let service = account::service::AccountServiceImpl::new(Arc::clone(&interactor));
+
+    for export in interactor.get_pending_exports().await.map_err(|error| error as Box<dyn Error>)? {
+        account::service::AccountServiceImpl::spawn_export(Arc::clone(&interactor), export.id);
+    }
+
+    let suspended_users_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut suspended_users_handler = account::amqp::MessageQueueHandler::new(Arc::clone(&interactor), suspended_users_queue);
+    suspended_users_handler.consume_suspended_users().await;
+
+    let banned_users_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut banned_users_handler = account::amqp::MessageQueueHandler::new(Arc::clone(&interactor), banned_users_queue);
+    banned_users_handler.consume_banned_users().await;
+
+    let erased_accounts_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut erased_accounts_handler = account::amqp::MessageQueueHandler::new(Arc::clone(&interactor), erased_accounts_queue);
+    erased_accounts_handler.consume_erased_accounts().await;
 
     let server_addr = SocketAddr::new(cfg.service_hostname.unwrap().parse().unwrap(), cfg.service_port.unwrap().parse().unwrap());
 
@@ -0,0 +1,70 @@
+use tonic::{Request, Response, Status};
+use tonic::transport::Channel;
+
+use crate::token::pb::{GenerateTokenRequest, GenerateTokenResponse, RevokeTokensRequest, RevokeTokensResponse, ValidateTokenRequest, ValidateTokenResponse};
+use crate::token::pb::token_service_client::TokenServiceClient;
+
+#[tonic::async_trait]
+pub trait TokenApi {
+    async fn generate_token(&self, request: Request<GenerateTokenRequest>) -> Result<Response<GenerateTokenResponse>, Status>;
+    async fn validate_token(&self, request: Request<ValidateTokenRequest>) -> Result<Response<ValidateTokenResponse>, Status>;
+    async fn revoke_tokens(&self, request: Request<RevokeTokensRequest>) -> Result<Response<RevokeTokensResponse>, Status>;
+}
+
+pub struct TokenApiImpl {
+    client: TokenServiceClient<Channel>,
+}
+
+impl TokenApiImpl {
+    pub fn new(client: TokenServiceClient<Channel>) -> Box<dyn TokenApi + Send + Sync> {
+        Box::new(TokenApiImpl { client })
+    }
+}
+
+#[tonic::async_trait]
+impl TokenApi for TokenApiImpl {
+    async fn generate_token(&self, request: Request<GenerateTokenRequest>) -> Result<Response<GenerateTokenResponse>, Status> {
+        let GenerateTokenRequest { kind, payload, subject, ttl } = request.into_inner();
+        if payload.is_empty() || subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["payload", "subject"]);
+        }
+
+        self.client
+            .clone()
+            .generate_token(
+                Request::new(
+                    GenerateTokenRequest { kind, payload, subject, ttl }
+                )
+            ).await
+    }
+
+    async fn validate_token(&self, request: Request<ValidateTokenRequest>) -> Result<Response<ValidateTokenResponse>, Status> {
+        let ValidateTokenRequest { token, kind } = request.into_inner();
+        if token.is_empty() {
+            return status::Status::invalid_arguments(vec!["token"]);
+        }
+
+        self.client
+            .clone()
+            .validate_token(
+                Request::new(
+                    ValidateTokenRequest { token, kind }
+                )
+            ).await
+    }
+
+    async fn revoke_tokens(&self, request: Request<RevokeTokensRequest>) -> Result<Response<RevokeTokensResponse>, Status> {
+        let RevokeTokensRequest { subject } = request.into_inner();
+        if subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["subject"]);
+        }
+
+        self.client
+            .clone()
+            .revoke_tokens(
+                Request::new(
+                    RevokeTokensRequest { subject }
+                )
+            ).await
+    }
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+pub mod repository;
+pub mod api;
+
+pub mod pb {
+    tonic::include_proto!("token");
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use tonic::Request;
+
+use crate::token::api::TokenApi;
+use crate::token::pb::{GenerateTokenRequest, GenerateTokenResponse, RevokeTokensRequest, RevokeTokensResponse, TokenKind, ValidateTokenRequest, ValidateTokenResponse};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+#[async_trait::async_trait]
+pub trait TokenRepository {
+    async fn generate_data_export_token(&self, subject: &str, export_id: &str) -> Result<String, Error>;
+    async fn validate_data_export_token(&self, token: &str) -> Result<String, Error>;
+    async fn revoke_tokens(&self, subject: &str) -> Result<(), Error>;
+}
+
+pub struct TokenRepositoryImpl {
+    api: Box<dyn TokenApi + Send + Sync>,
+}
+
+impl TokenRepositoryImpl {
+    pub fn new(api: Box<dyn TokenApi + Send + Sync>) -> Box<dyn TokenRepository + Send + Sync> {
+        Box::new(TokenRepositoryImpl { api })
+    }
+}
+
+#[async_trait::async_trait]
+impl TokenRepository for TokenRepositoryImpl {
+    async fn generate_data_export_token(&self, subject: &str, export_id: &str) -> Result<String, Error> {
+        let GenerateTokenResponse { token } = self.api.generate_token(
+            Request::new(
+                GenerateTokenRequest {
+                    kind: i32::from(TokenKind::DataExport),
+                    payload: String::from(export_id),
+                    subject: String::from(subject),
+                    ttl: 0,
+                }
+            )
+        ).await?.into_inner();
+        Ok(token)
+    }
+
+    async fn validate_data_export_token(&self, token: &str) -> Result<String, Error> {
+        let ValidateTokenResponse { payload } = self.api.validate_token(
+            Request::new(
+                ValidateTokenRequest { token: String::from(token), kind: i32::from(TokenKind::DataExport) }
+            )
+        ).await?.into_inner();
+        Ok(payload)
+    }
+
+    async fn revoke_tokens(&self, subject: &str) -> Result<(), Error> {
+        let RevokeTokensResponse {} = self.api.revoke_tokens(
+            Request::new(
+                RevokeTokensRequest { subject: String::from(subject) }
+            )
+        ).await?.into_inner();
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -1,16 +1,21 @@
 use tonic::{Request, Response, Status};
 use tonic::transport::Channel;
 
-use crate::account::pb::{CreateAccountRequest, CreateAccountResponse, DeleteAccountRequest, DeleteAccountResponse, GetAccountByCredentialsRequest, GetAccountByCredentialsResponse, GetAccountByIdRequest, GetAccountByIdResponse, UpdateAccountRequest, UpdateAccountResponse};
+use crate::account::pb::{ConsumeRecoveryCodeRequest, ConsumeRecoveryCodeResponse, CreateAccountRequest, CreateAccountResponse, DeleteAccountRequest, DeleteAccountResponse, GetAccountByCredentialsRequest, GetAccountByCredentialsResponse, GetAccountByEmailRequest, GetAccountByEmailResponse, GetAccountByIdRequest, GetAccountByIdResponse, GetEntitlementsRequest, GetEntitlementsResponse, UpdateAccountRequest, UpdateAccountResponse, UpdateTotpRequest, UpdateTotpResponse, VerifyEmailRequest, VerifyEmailResponse};
 use crate::account::pb::account_service_client::AccountServiceClient;
 
 #[tonic::async_trait]
 pub trait AccountApi {
     async fn get_account_by_id(&self, request: Request<GetAccountByIdRequest>) -> Result<Response<GetAccountByIdResponse>, Status>;
+    async fn get_account_by_email(&self, request: Request<GetAccountByEmailRequest>) -> Result<Response<GetAccountByEmailResponse>, Status>;
     async fn get_account_by_credentials(&self, request: Request<GetAccountByCredentialsRequest>) -> Result<Response<GetAccountByCredentialsResponse>, Status>;
     async fn create_account(&self, request: Request<CreateAccountRequest>) -> Result<Response<CreateAccountResponse>, Status>;
     async fn update_account(&self, request: Request<UpdateAccountRequest>) -> Result<Response<UpdateAccountResponse>, Status>;
+    async fn verify_email(&self, request: Request<VerifyEmailRequest>) -> Result<Response<VerifyEmailResponse>, Status>;
+    async fn update_totp(&self, request: Request<UpdateTotpRequest>) -> Result<Response<UpdateTotpResponse>, Status>;
+    async fn consume_recovery_code(&self, request: Request<ConsumeRecoveryCodeRequest>) -> Result<Response<ConsumeRecoveryCodeResponse>, Status>;
     async fn delete_account(&self, request: Request<DeleteAccountRequest>) -> Result<Response<DeleteAccountResponse>, Status>;
+    async fn get_entitlements(&self, request: Request<GetEntitlementsRequest>) -> Result<Response<GetEntitlementsResponse>, Status>;
 }
 
 pub struct AccountApiImpl {
@@ -40,6 +45,21 @@ impl AccountApi for AccountApiImpl {
             ).await
     }
 
+    async fn get_account_by_email(&self, request: Request<GetAccountByEmailRequest>) -> Result<Response<GetAccountByEmailResponse>, Status> {
+        let GetAccountByEmailRequest { email } = request.into_inner();
+        if email.is_empty() {
+            return status::Status::invalid_arguments(vec!["email"]);
+        }
+
+        self.client
+            .clone()
+            .get_account_by_email(
+                Request::new(
+                    GetAccountByEmailRequest { email }
+                )
+            ).await
+    }
+
     async fn get_account_by_credentials(&self, request: Request<GetAccountByCredentialsRequest>) -> Result<Response<GetAccountByCredentialsResponse>, Status> {
         let GetAccountByCredentialsRequest { email, password } = request.into_inner();
         if email.is_empty() || password.is_empty() {
@@ -59,7 +79,7 @@ impl AccountApi for AccountApiImpl {
     }
 
     async fn create_account(&self, request: Request<CreateAccountRequest>) -> Result<Response<CreateAccountResponse>, Status> {
-        let CreateAccountRequest { email, password, role } = request.into_inner();
+        let CreateAccountRequest { email, password } = request.into_inner();
         if email.is_empty() || password.is_empty() {
             return status::Status::invalid_arguments(vec!["email", "password"]);
         }
@@ -71,14 +91,13 @@ impl AccountApi for AccountApiImpl {
                     CreateAccountRequest {
                         email,
                         password,
-                        role,
                     }
                 )
             ).await
     }
 
     async fn update_account(&self, request: Request<UpdateAccountRequest>) -> Result<Response<UpdateAccountResponse>, Status> {
-        let UpdateAccountRequest { id, email, password, role } = request.into_inner();
+        let UpdateAccountRequest { id, email, password } = request.into_inner();
         if id.is_empty() {
             return status::Status::invalid_arguments(vec!["id"]);
         }
@@ -91,12 +110,61 @@ impl AccountApi for AccountApiImpl {
                         id,
                         email,
                         password,
-                        role,
                     }
                 )
             ).await
     }
 
+    async fn verify_email(&self, request: Request<VerifyEmailRequest>) -> Result<Response<VerifyEmailResponse>, Status> {
+        let VerifyEmailRequest { id, email } = request.into_inner();
+        if id.is_empty() || email.is_empty() {
+            return status::Status::invalid_arguments(vec!["id", "email"]);
+        }
+
+        self.client
+            .clone()
+            .verify_email(
+                Request::new(
+                    VerifyEmailRequest { id, email }
+                )
+            ).await
+    }
+
+    async fn update_totp(&self, request: Request<UpdateTotpRequest>) -> Result<Response<UpdateTotpResponse>, Status> {
+        let UpdateTotpRequest { id, totp_secret, totp_enabled, recovery_code_hashes } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        self.client
+            .clone()
+            .update_totp(
+                Request::new(
+                    UpdateTotpRequest {
+                        id,
+                        totp_secret,
+                        totp_enabled,
+                        recovery_code_hashes,
+                    }
+                )
+            ).await
+    }
+
+    async fn consume_recovery_code(&self, request: Request<ConsumeRecoveryCodeRequest>) -> Result<Response<ConsumeRecoveryCodeResponse>, Status> {
+        let ConsumeRecoveryCodeRequest { id, recovery_code_hash } = request.into_inner();
+        if id.is_empty() || recovery_code_hash.is_empty() {
+            return status::Status::invalid_arguments(vec!["id", "recovery_code_hash"]);
+        }
+
+        self.client
+            .clone()
+            .consume_recovery_code(
+                Request::new(
+                    ConsumeRecoveryCodeRequest { id, recovery_code_hash }
+                )
+            ).await
+    }
+
     async fn delete_account(&self, request: Request<DeleteAccountRequest>) -> Result<Response<DeleteAccountResponse>, Status> {
         let DeleteAccountRequest { id } = request.into_inner();
         if id.is_empty() {
@@ -109,4 +177,18 @@ impl AccountApi for AccountApiImpl {
             )
         ).await
     }
+    async fn get_entitlements(&self, request: Request<GetEntitlementsRequest>) -> Result<Response<GetEntitlementsResponse>, Status> {
+        let GetEntitlementsRequest { id } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        self.client
+            .clone()
+            .get_entitlements(
+                Request::new(
+                    GetEntitlementsRequest { id }
+                )
+            ).await
+    }
 }
\ No newline at end of file
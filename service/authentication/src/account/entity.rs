@@ -1,3 +1,5 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -6,6 +8,19 @@ pub enum Role {
     Moderator,
 }
 
+#[derive(Serialize, Deserialize)]
+pub enum Entitlement {
+    Backtrack,
+    SeeWhoLikedYou,
+    UnlimitedLikes,
+}
+
+pub enum Status {
+    Active,
+    Suspended,
+    Banned,
+}
+
 pub struct Account {
     pub id: String,
     pub email: String,
@@ -15,4 +30,16 @@ pub struct Account {
     pub premium_expiration_date: u64,
     pub created_at: u64,
     pub updated_at: u64,
+    pub status: Status,
+    pub suspension_expiration_date: u64,
+    pub email_verified: bool,
+    pub totp_secret: String,
+    pub totp_enabled: bool,
+    pub recovery_code_hashes: Vec<String>,
+}
+
+impl Account {
+    pub fn timestamp_now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64
+    }
 }
\ No newline at end of file
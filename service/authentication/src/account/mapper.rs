@@ -1,5 +1,5 @@
-use crate::account::entity::{Account, Role};
-use crate::account::pb::{Account as AccountMessage, Role as RoleMessage};
+use crate::account::entity::{Account, Entitlement, Role, Status};
+use crate::account::pb::{Account as AccountMessage, Entitlement as EntitlementMessage, Role as RoleMessage, Status as StatusMessage};
 
 impl From<AccountMessage> for Account {
     fn from(value: AccountMessage) -> Self {
@@ -8,13 +8,23 @@ impl From<AccountMessage> for Account {
             email: value.email,
             password_hash: value.password_hash,
             password_salt: value.password_salt,
-            role: match RoleMessage::from_i32(value.role).unwrap() {
-                RoleMessage::User => Role::User,
+            role: match RoleMessage::try_from(value.role).unwrap_or_default() {
+                RoleMessage::Unspecified | RoleMessage::User => Role::User,
                 RoleMessage::Moderator => Role::Moderator
             },
             premium_expiration_date: value.premium_expiration_date,
             created_at: value.created_at,
             updated_at: value.updated_at,
+            status: match StatusMessage::try_from(value.status).unwrap_or_default() {
+                StatusMessage::Active => Status::Active,
+                StatusMessage::Suspended => Status::Suspended,
+                StatusMessage::Banned => Status::Banned
+            },
+            suspension_expiration_date: value.suspension_expiration_date,
+            email_verified: value.email_verified,
+            totp_secret: value.totp_secret,
+            totp_enabled: value.totp_enabled,
+            recovery_code_hashes: value.recovery_code_hashes,
         }
     }
 }
@@ -33,6 +43,26 @@ impl From<Account> for AccountMessage {
             premium_expiration_date: value.premium_expiration_date,
             created_at: value.created_at,
             updated_at: value.updated_at,
+            status: i32::from(match value.status {
+                Status::Active => StatusMessage::Active,
+                Status::Suspended => StatusMessage::Suspended,
+                Status::Banned => StatusMessage::Banned
+            }),
+            suspension_expiration_date: value.suspension_expiration_date,
+            email_verified: value.email_verified,
+            totp_secret: value.totp_secret,
+            totp_enabled: value.totp_enabled,
+            recovery_code_hashes: value.recovery_code_hashes,
+        }
+    }
+}
+
+impl From<EntitlementMessage> for Entitlement {
+    fn from(value: EntitlementMessage) -> Self {
+        match value {
+            EntitlementMessage::Backtrack => Entitlement::Backtrack,
+            EntitlementMessage::SeeWhoLikedYou => Entitlement::SeeWhoLikedYou,
+            EntitlementMessage::UnlimitedLikes => Entitlement::UnlimitedLikes
         }
     }
 }
\ No newline at end of file
@@ -1,29 +1,32 @@
 use tonic::Request;
 
 use crate::account::api::AccountApi;
-use crate::account::entity::{Account, Role};
-use crate::account::pb::{CreateAccountRequest, CreateAccountResponse, DeleteAccountRequest, DeleteAccountResponse, GetAccountByCredentialsRequest, GetAccountByCredentialsResponse, GetAccountByIdRequest, GetAccountByIdResponse, Role as RoleMessage, UpdateAccountRequest, UpdateAccountResponse};
+use crate::account::entity::{Account, Entitlement};
+use crate::account::pb::{ConsumeRecoveryCodeRequest, ConsumeRecoveryCodeResponse, CreateAccountRequest, CreateAccountResponse, DeleteAccountRequest, DeleteAccountResponse, GetAccountByCredentialsRequest, GetAccountByCredentialsResponse, GetAccountByEmailRequest, GetAccountByEmailResponse, GetAccountByIdRequest, GetAccountByIdResponse, Entitlement as EntitlementMessage, GetEntitlementsRequest, GetEntitlementsResponse, UpdateAccountRequest, UpdateAccountResponse, UpdateTotpRequest, UpdateTotpResponse, VerifyEmailRequest, VerifyEmailResponse};
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
 #[async_trait::async_trait]
 pub trait AccountRepository {
     async fn get_account_by_id(&self, id: &str) -> Result<Option<Account>, Error>;
+    async fn get_account_by_email(&self, email: &str) -> Result<Option<Account>, Error>;
     async fn get_account_by_credentials(&self, email: &str, password: &str) -> Result<Option<Account>, Error>;
     async fn create_account(
         &self,
         email: &str,
         password: &str,
-        role: Role,
     ) -> Result<String, Error>;
     async fn update_account(
         &self,
         id: &str,
         email: Option<String>,
         password: Option<String>,
-        role: Option<Role>,
     ) -> Result<Option<Account>, Error>;
+    async fn verify_email(&self, id: &str, email: &str) -> Result<Option<Account>, Error>;
+    async fn update_totp(&self, id: &str, totp_secret: &str, totp_enabled: bool, recovery_code_hashes: Vec<String>) -> Result<Option<Account>, Error>;
+    async fn consume_recovery_code(&self, id: &str, recovery_code_hash: &str) -> Result<bool, Error>;
     async fn delete_account(&self, id: &str) -> Result<String, Error>;
+    async fn get_entitlements(&self, id: &str) -> Result<Vec<Entitlement>, Error>;
 }
 
 pub struct AccountRepositoryImpl {
@@ -47,6 +50,15 @@ impl AccountRepository for AccountRepositoryImpl {
         Ok(account.map(|a| a.into()))
     }
 
+    async fn get_account_by_email(&self, email: &str) -> Result<Option<Account>, Error> {
+        let GetAccountByEmailResponse { account } = self.api.get_account_by_email(
+            Request::new(
+                GetAccountByEmailRequest { email: String::from(email) }
+            )
+        ).await?.into_inner();
+        Ok(account.map(|a| a.into()))
+    }
+
     async fn get_account_by_credentials(&self, email: &str, password: &str) -> Result<Option<Account>, Error> {
         let GetAccountByCredentialsResponse { account } = self.api.get_account_by_credentials(
             Request::new(
@@ -59,39 +71,69 @@ impl AccountRepository for AccountRepositoryImpl {
         Ok(account.map(|a| a.into()))
     }
 
-    async fn create_account(&self, email: &str, password: &str, role: Role) -> Result<String, Error> {
+    async fn create_account(&self, email: &str, password: &str) -> Result<String, Error> {
         let CreateAccountResponse { id } = self.api.create_account(
             Request::new(
                 CreateAccountRequest {
                     email: String::from(email),
                     password: String::from(password),
-                    role: i32::from(match role {
-                        Role::User => RoleMessage::User,
-                        Role::Moderator => RoleMessage::Moderator
-                    }),
                 }
             )
         ).await?.into_inner();
         Ok(id)
     }
 
-    async fn update_account(&self, id: &str, email: Option<String>, password: Option<String>, role: Option<Role>) -> Result<Option<Account>, Error> {
+    async fn update_account(&self, id: &str, email: Option<String>, password: Option<String>) -> Result<Option<Account>, Error> {
         let UpdateAccountResponse { account } = self.api.update_account(
             Request::new(
                 UpdateAccountRequest {
                     id: String::from(id),
                     email,
                     password,
-                    role: role.map(|r| i32::from(match r {
-                        Role::User => RoleMessage::User,
-                        Role::Moderator => RoleMessage::Moderator
-                    })),
                 }
             )
         ).await?.into_inner();
         Ok(account.map(|a| a.into()))
     }
 
+    async fn verify_email(&self, id: &str, email: &str) -> Result<Option<Account>, Error> {
+        let VerifyEmailResponse { account } = self.api.verify_email(
+            Request::new(
+                VerifyEmailRequest {
+                    id: String::from(id),
+                    email: String::from(email),
+                }
+            )
+        ).await?.into_inner();
+        Ok(account.map(|a| a.into()))
+    }
+
+    async fn update_totp(&self, id: &str, totp_secret: &str, totp_enabled: bool, recovery_code_hashes: Vec<String>) -> Result<Option<Account>, Error> {
+        let UpdateTotpResponse { account } = self.api.update_totp(
+            Request::new(
+                UpdateTotpRequest {
+                    id: String::from(id),
+                    totp_secret: String::from(totp_secret),
+                    totp_enabled,
+                    recovery_code_hashes,
+                }
+            )
+        ).await?.into_inner();
+        Ok(account.map(|a| a.into()))
+    }
+
+    async fn consume_recovery_code(&self, id: &str, recovery_code_hash: &str) -> Result<bool, Error> {
+        let ConsumeRecoveryCodeResponse { is_consumed } = self.api.consume_recovery_code(
+            Request::new(
+                ConsumeRecoveryCodeRequest {
+                    id: String::from(id),
+                    recovery_code_hash: String::from(recovery_code_hash),
+                }
+            )
+        ).await?.into_inner();
+        Ok(is_consumed)
+    }
+
     async fn delete_account(&self, id: &str) -> Result<String, Error> {
         let DeleteAccountResponse { id } = self.api.delete_account(
             Request::new(
@@ -100,4 +142,13 @@ impl AccountRepository for AccountRepositoryImpl {
         ).await?.into_inner();
         Ok(id)
     }
+
+    async fn get_entitlements(&self, id: &str) -> Result<Vec<Entitlement>, Error> {
+        let GetEntitlementsResponse { entitlements, .. } = self.api.get_entitlements(
+            Request::new(
+                GetEntitlementsRequest { id: String::from(id) }
+            )
+        ).await?.into_inner();
+        Ok(entitlements.into_iter().filter_map(|entitlement| EntitlementMessage::try_from(entitlement).ok().map(|entitlement| entitlement.into())).collect())
+    }
 }
\ No newline at end of file
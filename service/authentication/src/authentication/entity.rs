@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::account::entity::Role;
+use crate::account::entity::{Entitlement, Role};
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
@@ -8,11 +8,19 @@ type Error = Box<dyn std::error::Error + Send + Sync>;
 pub struct TokenPayload {
     pub account_id: String,
     pub role: Role,
+    #[serde(default)]
+    pub entitlements: Vec<Entitlement>,
+    #[serde(default)]
+    pub email_verified: bool,
+    #[serde(default)]
+    pub totp_enabled: bool,
+    #[serde(default)]
+    pub session_id: String,
 }
 
 impl TokenPayload {
-    pub fn new(id: &str, role: Role) -> Self {
-        Self { account_id: String::from(id), role }
+    pub fn new(id: &str, role: Role, entitlements: Vec<Entitlement>, email_verified: bool, totp_enabled: bool, session_id: &str) -> Self {
+        Self { account_id: String::from(id), role, entitlements, email_verified, totp_enabled, session_id: String::from(session_id) }
     }
 
     pub fn from_str(s: &str) -> Result<TokenPayload, Error> {
@@ -36,4 +44,69 @@ impl TokenPair {
             refresh_token: String::from(refresh_token),
         }
     }
+}
+
+pub enum SignInResult {
+    Authenticated(TokenPair),
+    Challenged(String),
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Device {
+    pub name: String,
+    pub ip_address: String,
+    pub user_agent: String,
+}
+
+impl Device {
+    pub fn new(name: &str, ip_address: &str, user_agent: &str) -> Self {
+        Self {
+            name: String::from(name),
+            ip_address: String::from(ip_address),
+            user_agent: String::from(user_agent),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EmailVerification {
+    pub account_id: String,
+    pub email: String,
+}
+
+impl EmailVerification {
+    pub fn new(account_id: &str, email: &str) -> Self {
+        Self {
+            account_id: String::from(account_id),
+            email: String::from(email),
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<EmailVerification, Error> {
+        serde_json::from_str(s).map_err(|e| Box::new(e) as Error)
+    }
+
+    pub fn to_string(&self) -> Result<String, Error> {
+        serde_json::to_string(self).map_err(|e| Box::new(e) as Error)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SignInChallenge {
+    pub account_id: String,
+    pub device: Device,
+}
+
+impl SignInChallenge {
+    pub fn new(account_id: &str, device: Device) -> Self {
+        Self { account_id: String::from(account_id), device }
+    }
+
+    pub fn from_str(s: &str) -> Result<SignInChallenge, Error> {
+        serde_json::from_str(s).map_err(|e| Box::new(e) as Error)
+    }
+
+    pub fn to_string(&self) -> Result<String, Error> {
+        serde_json::to_string(self).map_err(|e| Box::new(e) as Error)
+    }
 }
\ No newline at end of file
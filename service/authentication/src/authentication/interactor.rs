@@ -1,21 +1,36 @@
-use error::make_error;
+use error::{DomainError, make_error};
 
-use crate::account::entity::{Account, Role};
+use crate::account::entity::{Account, Status};
 use crate::account::repository::AccountRepository;
-use crate::authentication::entity::{TokenPair, TokenPayload};
+use crate::authentication::entity::{Device, EmailVerification, SignInChallenge, SignInResult, TokenPair, TokenPayload};
+use crate::authentication::repository::AuthenticationRepository;
+use crate::email::sender::EmailSender;
+use crate::token::entity::Session;
 use crate::token::repository::TokenRepository;
+use crate::totp::authenticator::Authenticator;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
+const PASSWORD_RESET_REQUEST_LIMIT: u64 = 3;
+const EMAIL_VERIFICATION_REQUEST_LIMIT: u64 = 3;
+const SIGN_IN_CHALLENGE_ATTEMPT_LIMIT: u64 = 5;
+const SIGN_IN_BACKOFF_THRESHOLD: u64 = 3;
+const SIGN_IN_EMAIL_LOCKOUT_THRESHOLD: u64 = 10;
+const SIGN_IN_IP_LOCKOUT_THRESHOLD: u64 = 50;
+const SIGN_IN_LOCKOUT_SECONDS: u64 = 60 * 15;
+
 #[async_trait::async_trait]
 pub trait AuthenticationInteractor {
     async fn sign_up(
         &self,
         email: &str,
         password: &str,
-        role: Role,
+        device: Device,
     ) -> Result<TokenPair, Error>;
-    async fn sign_in(&self, email: &str, password: &str) -> Result<TokenPair, Error>;
+    async fn sign_in(&self, email: &str, password: &str, device: Device) -> Result<SignInResult, Error>;
+    async fn complete_sign_in(&self, challenge: &str, code: &str) -> Result<TokenPair, Error>;
+    async fn enroll_totp(&self, access_token: &str) -> Result<(String, String), Error>;
+    async fn confirm_totp(&self, access_token: &str, code: &str) -> Result<Vec<String>, Error>;
     async fn sign_out(&self, access_token: &str, refresh_token: &str) -> Result<bool, Error>;
     async fn change_password(
         &self,
@@ -23,24 +38,125 @@ pub trait AuthenticationInteractor {
         refresh_token: &str,
         new_password: &str,
     ) -> Result<TokenPair, Error>;
+    async fn change_email(
+        &self,
+        access_token: &str,
+        refresh_token: &str,
+        new_email: &str,
+    ) -> Result<TokenPair, Error>;
+    async fn verify_email(&self, code: &str) -> Result<(), Error>;
+    async fn resend_verification(&self, access_token: &str) -> Result<(), Error>;
+    async fn request_password_reset(&self, email: &str) -> Result<(), Error>;
+    async fn confirm_password_reset(&self, code: &str, new_password: &str) -> Result<(), Error>;
     async fn refresh_token(&self, refresh_token: &str) -> Result<TokenPair, Error>;
     async fn validate_token(&self, access_token: &str) -> Result<String, Error>;
+    async fn list_sessions(&self, access_token: &str) -> Result<Vec<Session>, Error>;
+    async fn revoke_session(&self, access_token: &str, session_id: &str) -> Result<(), Error>;
+    async fn revoke_all_sessions(&self, access_token: &str) -> Result<(), Error>;
 }
 
 pub struct AuthenticationInteractorImpl {
+    repository: Box<dyn AuthenticationRepository + Send + Sync>,
     account_repository: Box<dyn AccountRepository + Send + Sync>,
     token_repository: Box<dyn TokenRepository + Send + Sync>,
+    email_sender: Box<dyn EmailSender + Send + Sync>,
+    authenticator: Box<dyn Authenticator + Send + Sync>,
 }
 
 impl AuthenticationInteractorImpl {
-    async fn create_tokens(&self, account: Account) -> Result<TokenPair, Error> {
-        let payload = TokenPayload::new(&account.id, account.role);
+    fn check_status(account: &Account) -> Result<(), Error> {
+        match account.status {
+            Status::Suspended if account.suspension_expiration_date > Account::timestamp_now() => Err(DomainError::permission_denied("account is suspended")),
+            Status::Banned => Err(DomainError::permission_denied("account is banned")),
+            _ => Ok(())
+        }
+    }
+
+    fn sign_in_subjects(email: &str, device: &Device) -> Vec<(String, u64)> {
+        let mut subjects = vec![(format!("email:{}", email.trim().to_lowercase()), SIGN_IN_EMAIL_LOCKOUT_THRESHOLD)];
+        if !device.ip_address.is_empty() {
+            subjects.push((format!("ip:{}", device.ip_address), SIGN_IN_IP_LOCKOUT_THRESHOLD));
+        }
+        subjects
+    }
+
+    fn sign_in_backoff_seconds(failures: u64, lockout_threshold: u64) -> u64 {
+        if failures >= lockout_threshold {
+            return SIGN_IN_LOCKOUT_SECONDS;
+        }
+        if failures < SIGN_IN_BACKOFF_THRESHOLD {
+            return 0;
+        }
+        (1 << (failures - SIGN_IN_BACKOFF_THRESHOLD).min(16)).min(SIGN_IN_LOCKOUT_SECONDS)
+    }
+
+    fn is_invalid_credentials(error: &Error) -> bool {
+        matches!(error.downcast_ref::<tonic::Status>().map(tonic::Status::code), Some(tonic::Code::Unauthenticated | tonic::Code::NotFound))
+            || matches!(error.downcast_ref::<DomainError>(), Some(DomainError::Unauthenticated(_) | DomainError::NotFound(_)))
+    }
+
+    async fn check_sign_in_lockout(&self, subjects: &[(String, u64)]) -> Result<(), Error> {
+        let mut retry_after = 0;
+        for (subject, _) in subjects {
+            retry_after = retry_after.max(self.repository.get_sign_in_retry_after(subject).await?);
+        }
+        if retry_after > 0 {
+            return Err(DomainError::resource_exhausted_with_retry(&format!("too many sign in attempts, retry after {} seconds", retry_after), retry_after));
+        }
+        Ok(())
+    }
+
+    async fn record_sign_in_failure(&self, subjects: &[(String, u64)]) -> Result<(), Error> {
+        for (subject, lockout_threshold) in subjects {
+            let failures = self.repository.increment_sign_in_failures(subject).await?;
+            let seconds = Self::sign_in_backoff_seconds(failures, *lockout_threshold);
+            if seconds > 0 {
+                self.repository.lock_sign_in(subject, seconds).await?;
+            }
+            if failures == *lockout_threshold {
+                self.repository.publish_sign_in_lockout(subject, Account::timestamp_now() + seconds * 1000).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn create_session(&self, account: &Account, device: Device) -> Result<String, Error> {
+        Self::check_status(account)?;
+        self.token_repository.create_session(&account.id, &device.name, &device.ip_address, &device.user_agent).await
+    }
+
+    async fn create_tokens(&self, account: Account, session_id: &str) -> Result<TokenPair, Error> {
+        Self::check_status(&account)?;
+        let entitlements = self.account_repository.get_entitlements(&account.id).await?;
+        let payload = TokenPayload::new(&account.id, account.role, entitlements, account.email_verified, account.totp_enabled, session_id);
         let payload_str = payload.to_string()?;
-        let access_token = self.token_repository.generate_access_token(&payload_str).await?;
-        let refresh_token = self.token_repository.generate_refresh_token(&payload_str).await?;
+        let access_token = self.token_repository.generate_access_token(&account.id, &payload_str, Some(String::from(session_id))).await?;
+        let refresh_token = self.token_repository.generate_refresh_token(&account.id, &payload_str, Some(String::from(session_id))).await?;
         Ok(TokenPair::new(&access_token, &refresh_token))
     }
 
+    async fn verify_second_factor(&self, account: &Account, code: &str) -> Result<bool, Error> {
+        let code = code.trim();
+        if code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(self.authenticator.verify_code(&account.totp_secret, code)? && self.repository.use_totp_code(&account.id, code).await?);
+        }
+        self.account_repository.consume_recovery_code(&account.id, &self.authenticator.hash_recovery_code(code)).await
+    }
+
+    async fn send_verification(&self, account: &Account) -> Result<(), Error> {
+        let code = self.repository.create_email_verification_code(EmailVerification::new(&account.id, &account.email)).await?;
+        self.email_sender.send(
+            &account.email,
+            "Email verification",
+            &format!("Use this code to verify your email: {}\nThe code expires in 24 hours.", code),
+        ).await
+    }
+
+    async fn get_payload(&self, access_token: &str) -> Result<TokenPayload, Error> {
+        let payload_str = self.token_repository.validate_token(access_token).await?;
+        TokenPayload::from_str(&payload_str)
+    }
+
     async fn invalidate_tokens(&self, access_token: &str, refresh_token: &str) -> Result<(), Error> {
         let _ = self.token_repository.invalidate_token(access_token).await;
         let _ = self.token_repository.invalidate_token(refresh_token).await;
@@ -48,61 +164,254 @@ impl AuthenticationInteractorImpl {
     }
 
     pub fn new(
+        repository: Box<dyn AuthenticationRepository + Send + Sync>,
         account_repository: Box<dyn AccountRepository + Send + Sync>,
         token_repository: Box<dyn TokenRepository + Send + Sync>,
+        email_sender: Box<dyn EmailSender + Send + Sync>,
+        authenticator: Box<dyn Authenticator + Send + Sync>,
     ) -> Box<dyn AuthenticationInteractor + Send + Sync> {
-        Box::new(AuthenticationInteractorImpl { account_repository, token_repository })
+        Box::new(AuthenticationInteractorImpl { repository, account_repository, token_repository, email_sender, authenticator })
     }
 }
 
 #[async_trait::async_trait]
 impl AuthenticationInteractor for AuthenticationInteractorImpl {
-    async fn sign_up(&self, email: &str, password: &str, role: Role) -> Result<TokenPair, Error> {
-        let id = self.account_repository.create_account(email, password, role).await?;
+    async fn sign_up(&self, email: &str, password: &str, device: Device) -> Result<TokenPair, Error> {
+        let id = self.account_repository.create_account(email, password).await?;
         if let Some(account) = self.account_repository.get_account_by_id(&id).await? {
-            return self.create_tokens(account).await;
+            let _ = self.send_verification(&account).await;
+            let session_id = self.create_session(&account, device).await?;
+            return self.create_tokens(account, &session_id).await;
         }
         Err(make_error!("unable to sign up"))
     }
 
-    async fn sign_in(&self, email: &str, password: &str) -> Result<TokenPair, Error> {
-        if let Some(account) = self.account_repository.get_account_by_credentials(email, password).await? {
-            return self.create_tokens(account).await;
+    async fn sign_in(&self, email: &str, password: &str, device: Device) -> Result<SignInResult, Error> {
+        let subjects = Self::sign_in_subjects(email, &device);
+        self.check_sign_in_lockout(&subjects).await?;
+        let account = match self.account_repository.get_account_by_credentials(email, password).await {
+            Ok(account) => account,
+            Err(error) if Self::is_invalid_credentials(&error) => None,
+            Err(error) => return Err(error)
+        };
+        if let Some(account) = account {
+            for (subject, _) in &subjects {
+                self.repository.reset_sign_in_failures(subject).await?;
+            }
+            Self::check_status(&account)?;
+            if account.totp_enabled {
+                let challenge = self.repository.create_sign_in_challenge(SignInChallenge::new(&account.id, device)).await?;
+                return Ok(SignInResult::Challenged(challenge));
+            }
+            let session_id = self.create_session(&account, device).await?;
+            return self.create_tokens(account, &session_id).await.map(SignInResult::Authenticated);
+        }
+        self.record_sign_in_failure(&subjects).await?;
+        Err(DomainError::unauthenticated("invalid email or password"))
+    }
+
+    async fn complete_sign_in(&self, challenge: &str, code: &str) -> Result<TokenPair, Error> {
+        let SignInChallenge { account_id, device } = self.repository.get_sign_in_challenge(challenge).await?
+            .ok_or_else(|| DomainError::unauthenticated("invalid or expired sign in challenge"))?;
+        if let Some(account) = self.account_repository.get_account_by_id(&account_id).await? {
+            if !self.verify_second_factor(&account, code).await? {
+                if self.repository.increment_sign_in_challenge_attempts(challenge).await? >= SIGN_IN_CHALLENGE_ATTEMPT_LIMIT {
+                    self.repository.delete_sign_in_challenge(challenge).await?;
+                }
+                return Err(DomainError::unauthenticated("invalid two-factor authentication code"));
+            }
+            self.repository.delete_sign_in_challenge(challenge).await?;
+            let session_id = self.create_session(&account, device).await?;
+            return self.create_tokens(account, &session_id).await;
+        }
+        Err(DomainError::not_found("account not found"))
+    }
+
+    async fn enroll_totp(&self, access_token: &str) -> Result<(String, String), Error> {
+        let TokenPayload { account_id, .. } = self.get_payload(access_token).await?;
+        if let Some(account) = self.account_repository.get_account_by_id(&account_id).await? {
+            if account.totp_enabled {
+                return Err(DomainError::failed_precondition("two-factor authentication is already enabled"));
+            }
+            let secret = self.authenticator.generate_secret();
+            let uri = self.authenticator.get_uri(&secret, &account.email)?;
+            self.account_repository.update_totp(&account_id, &secret, false, vec![]).await?;
+            return Ok((secret, uri));
+        }
+        Err(DomainError::not_found("account not found"))
+    }
+
+    async fn confirm_totp(&self, access_token: &str, code: &str) -> Result<Vec<String>, Error> {
+        let TokenPayload { account_id, .. } = self.get_payload(access_token).await?;
+        if let Some(account) = self.account_repository.get_account_by_id(&account_id).await? {
+            if account.totp_enabled {
+                return Err(DomainError::failed_precondition("two-factor authentication is already enabled"));
+            }
+            if account.totp_secret.is_empty() {
+                return Err(DomainError::failed_precondition("two-factor authentication is not enrolled"));
+            }
+            let code = code.trim();
+            if !(self.authenticator.verify_code(&account.totp_secret, code)? && self.repository.use_totp_code(&account.id, code).await?) {
+                return Err(DomainError::unauthenticated("invalid two-factor authentication code"));
+            }
+            let recovery_codes = self.authenticator.generate_recovery_codes();
+            let recovery_code_hashes = recovery_codes.iter().map(|code| self.authenticator.hash_recovery_code(code)).collect();
+            self.account_repository.update_totp(&account_id, &account.totp_secret, true, recovery_code_hashes).await?;
+            return Ok(recovery_codes);
         }
-        Err(make_error!("unable to sign in"))
+        Err(DomainError::not_found("account not found"))
     }
 
     async fn sign_out(&self, access_token: &str, refresh_token: &str) -> Result<bool, Error> {
-        if let Ok(_) = self.token_repository.validate_token(access_token).await {
+        if let Ok(TokenPayload { account_id, session_id, .. }) = self.get_payload(access_token).await {
             let _ = self.invalidate_tokens(access_token, refresh_token).await;
+            if !session_id.is_empty() {
+                let _ = self.token_repository.revoke_session(&account_id, &session_id).await;
+            }
             return Ok(true);
         }
         Ok(false)
     }
 
     async fn change_password(&self, access_token: &str, refresh_token: &str, new_password: &str) -> Result<TokenPair, Error> {
-        let payload_str = self.token_repository.validate_token(access_token).await?;
-        if let Ok(TokenPayload { account_id, role }) = TokenPayload::from_str(&payload_str) {
-            if let Some(_) = self.account_repository.update_account(&account_id, None, Some(String::from(new_password)), None).await? {
+        if let Ok(TokenPayload { account_id, session_id, .. }) = self.get_payload(access_token).await {
+            if let Some(account) = self.account_repository.update_account(&account_id, None, Some(String::from(new_password))).await? {
                 let _ = self.invalidate_tokens(access_token, refresh_token).await;
-                let updated_payload = TokenPayload::new(&account_id, role);
-                let updated_payload_str = updated_payload.to_string()?;
-                let access_token = self.token_repository.generate_access_token(&updated_payload_str).await?;
-                let refresh_token = self.token_repository.generate_refresh_token(&updated_payload_str).await?;
-                return Ok(TokenPair::new(&access_token, &refresh_token));
+                let except_session_id = Some(session_id.clone()).filter(|session_id| !session_id.is_empty());
+                self.token_repository.revoke_all_sessions(&account_id, except_session_id.clone()).await?;
+                let session_id = match except_session_id {
+                    Some(session_id) => session_id,
+                    None => self.create_session(&account, Device::new("", "", "")).await?
+                };
+                return self.create_tokens(account, &session_id).await;
             }
         }
-        Err(make_error!("unable to change password"))
+        Err(DomainError::permission_denied("unable to change password"))
+    }
+
+    async fn change_email(&self, access_token: &str, refresh_token: &str, new_email: &str) -> Result<TokenPair, Error> {
+        if let Ok(TokenPayload { account_id, session_id, .. }) = self.get_payload(access_token).await {
+            if let Some(account) = self.account_repository.update_account(&account_id, Some(String::from(new_email)), None).await? {
+                let _ = self.invalidate_tokens(access_token, refresh_token).await;
+                let _ = self.send_verification(&account).await;
+                let session_id = if session_id.is_empty() {
+                    self.create_session(&account, Device::new("", "", "")).await?
+                } else {
+                    session_id
+                };
+                return self.create_tokens(account, &session_id).await;
+            }
+        }
+        Err(DomainError::permission_denied("unable to change email"))
+    }
+
+    async fn verify_email(&self, code: &str) -> Result<(), Error> {
+        if let Some(EmailVerification { account_id, email }) = self.repository.consume_email_verification_code(code).await? {
+            if let Ok(Some(_)) = self.account_repository.verify_email(&account_id, &email).await {
+                return Ok(());
+            }
+        }
+        Err(DomainError::invalid_argument("invalid or expired email verification code"))
+    }
+
+    async fn resend_verification(&self, access_token: &str) -> Result<(), Error> {
+        let TokenPayload { account_id, .. } = self.get_payload(access_token).await?;
+        if let Some(account) = self.account_repository.get_account_by_id(&account_id).await? {
+            if account.email_verified {
+                return Ok(());
+            }
+            if self.repository.increment_email_verification_requests(&account_id).await? > EMAIL_VERIFICATION_REQUEST_LIMIT {
+                return Err(DomainError::resource_exhausted("too many email verification requests"));
+            }
+            return self.send_verification(&account).await;
+        }
+        Err(DomainError::not_found("account not found"))
+    }
+
+    async fn request_password_reset(&self, email: &str) -> Result<(), Error> {
+        let email = email.trim().to_lowercase();
+        if self.repository.increment_password_reset_requests(&email).await? > PASSWORD_RESET_REQUEST_LIMIT {
+            return Err(DomainError::resource_exhausted("too many password reset requests"));
+        }
+        if let Ok(Some(account)) = self.account_repository.get_account_by_email(&email).await {
+            let code = self.repository.create_password_reset_code(&account.id).await?;
+            self.email_sender.send(
+                &account.email,
+                "Password reset",
+                &format!("Use this code to reset your password: {}\nThe code expires in 15 minutes.", code),
+            ).await?;
+        }
+        Ok(())
+    }
+
+    async fn confirm_password_reset(&self, code: &str, new_password: &str) -> Result<(), Error> {
+        match self.repository.consume_password_reset_code(code).await? {
+            Some(account_id) => {
+                self.account_repository.update_account(&account_id, None, Some(String::from(new_password))).await?;
+                self.token_repository.revoke_all_sessions(&account_id, None).await
+            }
+            None => Err(DomainError::invalid_argument("invalid or expired password reset code"))
+        }
     }
 
     async fn refresh_token(&self, refresh_token: &str) -> Result<TokenPair, Error> {
-        let payload = self.token_repository.validate_token(refresh_token).await?;
-        let access_token = self.token_repository.generate_access_token(&payload).await?;
-        let refresh_token = self.token_repository.generate_refresh_token(&payload).await?;
-        Ok(TokenPair::new(&access_token, &refresh_token))
+        let (account_id, _, family_id) = self.token_repository.rotate_refresh_token(refresh_token).await?;
+        if let Some(account) = self.account_repository.get_account_by_id(&account_id).await? {
+            return self.create_tokens(account, &family_id).await;
+        }
+        Err(DomainError::not_found("account not found"))
     }
 
     async fn validate_token(&self, access_token: &str) -> Result<String, Error> {
         self.token_repository.validate_token(access_token).await
     }
+    async fn list_sessions(&self, access_token: &str) -> Result<Vec<Session>, Error> {
+        let TokenPayload { account_id, session_id, .. } = self.get_payload(access_token).await?;
+        let sessions = self.token_repository.list_sessions(&account_id).await?;
+        Ok(sessions.into_iter().map(|session| Session { is_current: session.id == session_id, ..session }).collect())
+    }
+
+    async fn revoke_session(&self, access_token: &str, session_id: &str) -> Result<(), Error> {
+        let TokenPayload { account_id, .. } = self.get_payload(access_token).await?;
+        self.token_repository.revoke_session(&account_id, session_id).await
+    }
+
+    async fn revoke_all_sessions(&self, access_token: &str) -> Result<(), Error> {
+        let TokenPayload { account_id, .. } = self.get_payload(access_token).await?;
+        self.token_repository.revoke_all_sessions(&account_id, None).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_backoff_below_threshold() {
+        for failures in 0..SIGN_IN_BACKOFF_THRESHOLD {
+            assert_eq!(AuthenticationInteractorImpl::sign_in_backoff_seconds(failures, SIGN_IN_EMAIL_LOCKOUT_THRESHOLD), 0);
+        }
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let backoff: Vec<u64> = (SIGN_IN_BACKOFF_THRESHOLD..SIGN_IN_EMAIL_LOCKOUT_THRESHOLD)
+            .map(|failures| AuthenticationInteractorImpl::sign_in_backoff_seconds(failures, SIGN_IN_EMAIL_LOCKOUT_THRESHOLD))
+            .collect();
+        assert_eq!(backoff, vec![1, 2, 4, 8, 16, 32, 64]);
+    }
+
+    #[test]
+    fn lockout_at_threshold() {
+        assert_eq!(AuthenticationInteractorImpl::sign_in_backoff_seconds(SIGN_IN_EMAIL_LOCKOUT_THRESHOLD, SIGN_IN_EMAIL_LOCKOUT_THRESHOLD), SIGN_IN_LOCKOUT_SECONDS);
+        assert_eq!(AuthenticationInteractorImpl::sign_in_backoff_seconds(SIGN_IN_EMAIL_LOCKOUT_THRESHOLD + 1, SIGN_IN_EMAIL_LOCKOUT_THRESHOLD), SIGN_IN_LOCKOUT_SECONDS);
+    }
+
+    #[test]
+    fn backoff_never_exceeds_lockout() {
+        for failures in [SIGN_IN_IP_LOCKOUT_THRESHOLD - 1, 30, 19] {
+            assert!(AuthenticationInteractorImpl::sign_in_backoff_seconds(failures, SIGN_IN_IP_LOCKOUT_THRESHOLD) <= SIGN_IN_LOCKOUT_SECONDS);
+        }
+        assert_eq!(AuthenticationInteractorImpl::sign_in_backoff_seconds(SIGN_IN_IP_LOCKOUT_THRESHOLD - 1, SIGN_IN_IP_LOCKOUT_THRESHOLD), SIGN_IN_LOCKOUT_SECONDS);
+    }
 }
\ No newline at end of file
@@ -1,15 +1,24 @@
-use crate::account::entity::Role;
+use crate::account::entity::{Entitlement, Role};
 use crate::authentication::entity::TokenPayload;
-use crate::authentication::pb::{Role as RoleMessage, Token as TokenMessage};
+use crate::authentication::pb::{Entitlement as EntitlementMessage, Role as RoleMessage, Session as SessionMessage, Token as TokenMessage};
+use crate::token::entity::Session;
 
 impl From<TokenMessage> for TokenPayload {
     fn from(value: TokenMessage) -> Self {
         Self {
             account_id: value.account_id,
-            role: match RoleMessage::from_i32(value.role).unwrap() {
-                RoleMessage::User => Role::User,
+            role: match RoleMessage::try_from(value.role).unwrap_or_default() {
+                RoleMessage::Unspecified | RoleMessage::User => Role::User,
                 RoleMessage::Moderator => Role::Moderator
             },
+            entitlements: value.entitlements.into_iter().filter_map(|entitlement| EntitlementMessage::try_from(entitlement).ok()).map(|entitlement| match entitlement {
+                EntitlementMessage::Backtrack => Entitlement::Backtrack,
+                EntitlementMessage::SeeWhoLikedYou => Entitlement::SeeWhoLikedYou,
+                EntitlementMessage::UnlimitedLikes => Entitlement::UnlimitedLikes
+            }).collect(),
+            email_verified: value.email_verified,
+            totp_enabled: value.totp_enabled,
+            session_id: String::new(),
         }
     }
 }
@@ -22,6 +31,27 @@ impl From<TokenPayload> for TokenMessage {
                 Role::User => RoleMessage::User,
                 Role::Moderator => RoleMessage::Moderator
             }),
+            entitlements: value.entitlements.into_iter().map(|entitlement| i32::from(match entitlement {
+                Entitlement::Backtrack => EntitlementMessage::Backtrack,
+                Entitlement::SeeWhoLikedYou => EntitlementMessage::SeeWhoLikedYou,
+                Entitlement::UnlimitedLikes => EntitlementMessage::UnlimitedLikes
+            })).collect(),
+            email_verified: value.email_verified,
+            totp_enabled: value.totp_enabled,
+        }
+    }
+}
+
+impl From<Session> for SessionMessage {
+    fn from(value: Session) -> Self {
+        Self {
+            id: value.id,
+            device_name: value.device_name,
+            ip_address: value.ip_address,
+            user_agent: value.user_agent,
+            created_at: value.created_at,
+            last_seen_at: value.last_seen_at,
+            is_current: value.is_current,
         }
     }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 pub mod entity;
 pub mod mapper;
+pub mod repository;
 pub mod interactor;
 pub mod service;
 
@@ -0,0 +1,211 @@
+use redis::{Client as RedisClient, Commands, ExistenceCheck, SetExpiry, SetOptions};
+
+use amqp::MessageQueue;
+
+use crate::authentication::entity::{EmailVerification, SignInChallenge};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+const EXCHANGE_NAME: &str = "action";
+const SIGN_IN_LOCKED_ROUTING_KEY: &str = "sign_in.locked";
+
+const PASSWORD_RESET_CODE_EXPIRATION_SECONDS: u64 = 60 * 15;
+const PASSWORD_RESET_REQUESTS_EXPIRATION_SECONDS: i64 = 60 * 60;
+const EMAIL_VERIFICATION_CODE_EXPIRATION_SECONDS: u64 = 60 * 60 * 24;
+const EMAIL_VERIFICATION_REQUESTS_EXPIRATION_SECONDS: i64 = 60 * 60;
+const SIGN_IN_CHALLENGE_EXPIRATION_SECONDS: u64 = 60 * 5;
+const TOTP_CODE_EXPIRATION_SECONDS: usize = 90;
+const SIGN_IN_FAILURES_EXPIRATION_SECONDS: i64 = 60 * 60;
+
+#[async_trait::async_trait]
+pub trait AuthenticationRepository {
+    async fn increment_password_reset_requests(&self, email: &str) -> Result<u64, Error>;
+    async fn create_password_reset_code(&self, account_id: &str) -> Result<String, Error>;
+    async fn consume_password_reset_code(&self, code: &str) -> Result<Option<String>, Error>;
+    async fn increment_email_verification_requests(&self, account_id: &str) -> Result<u64, Error>;
+    async fn create_email_verification_code(&self, verification: EmailVerification) -> Result<String, Error>;
+    async fn consume_email_verification_code(&self, code: &str) -> Result<Option<EmailVerification>, Error>;
+    async fn create_sign_in_challenge(&self, challenge: SignInChallenge) -> Result<String, Error>;
+    async fn get_sign_in_challenge(&self, challenge: &str) -> Result<Option<SignInChallenge>, Error>;
+    async fn increment_sign_in_challenge_attempts(&self, challenge: &str) -> Result<u64, Error>;
+    async fn delete_sign_in_challenge(&self, challenge: &str) -> Result<(), Error>;
+    async fn use_totp_code(&self, account_id: &str, code: &str) -> Result<bool, Error>;
+    async fn get_sign_in_retry_after(&self, subject: &str) -> Result<u64, Error>;
+    async fn increment_sign_in_failures(&self, subject: &str) -> Result<u64, Error>;
+    async fn lock_sign_in(&self, subject: &str, seconds: u64) -> Result<(), Error>;
+    async fn reset_sign_in_failures(&self, subject: &str) -> Result<(), Error>;
+    async fn publish_sign_in_lockout(&self, subject: &str, locked_until: u64) -> Result<(), Error>;
+}
+
+pub struct AuthenticationRepositoryImpl {
+    redis_client: RedisClient,
+    message_queue: MessageQueue,
+}
+
+impl AuthenticationRepositoryImpl {
+    fn password_reset_requests_key(email: &str) -> String {
+        format!("password_reset_requests:{}", email.trim().to_lowercase())
+    }
+
+    fn password_reset_code_key(code: &str) -> String {
+        format!("password_reset:{}", code)
+    }
+
+    fn email_verification_requests_key(account_id: &str) -> String {
+        format!("email_verification_requests:{}", account_id)
+    }
+
+    fn email_verification_code_key(code: &str) -> String {
+        format!("email_verification:{}", code)
+    }
+
+    fn sign_in_challenge_key(challenge: &str) -> String {
+        format!("sign_in_challenge:{}", challenge)
+    }
+
+    fn sign_in_challenge_attempts_key(challenge: &str) -> String {
+        format!("sign_in_challenge_attempts:{}", challenge)
+    }
+
+    fn totp_code_key(account_id: &str, code: &str) -> String {
+        format!("totp_code:{}:{}", account_id, code)
+    }
+
+    fn sign_in_failures_key(subject: &str) -> String {
+        format!("sign_in_failures:{}", subject)
+    }
+
+    fn sign_in_lockout_key(subject: &str) -> String {
+        format!("sign_in_lockout:{}", subject)
+    }
+
+    pub fn new(redis_client: RedisClient, message_queue: MessageQueue) -> Box<dyn AuthenticationRepository + Send + Sync> {
+        Box::new(AuthenticationRepositoryImpl { redis_client, message_queue })
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthenticationRepository for AuthenticationRepositoryImpl {
+    async fn increment_password_reset_requests(&self, email: &str) -> Result<u64, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let key = Self::password_reset_requests_key(email);
+        let count: u64 = redis.incr(&key, 1)?;
+        if count == 1 {
+            let _: () = redis.expire(&key, PASSWORD_RESET_REQUESTS_EXPIRATION_SECONDS)?;
+        }
+        Ok(count)
+    }
+
+    async fn create_password_reset_code(&self, account_id: &str) -> Result<String, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let code = uuid::Uuid::new_v4().simple().to_string();
+        let _: () = redis.set_ex(Self::password_reset_code_key(&code), account_id, PASSWORD_RESET_CODE_EXPIRATION_SECONDS)?;
+        Ok(code)
+    }
+
+    async fn consume_password_reset_code(&self, code: &str) -> Result<Option<String>, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        Ok(redis.get_del(Self::password_reset_code_key(code))?)
+    }
+    async fn increment_email_verification_requests(&self, account_id: &str) -> Result<u64, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let key = Self::email_verification_requests_key(account_id);
+        let count: u64 = redis.incr(&key, 1)?;
+        if count == 1 {
+            let _: () = redis.expire(&key, EMAIL_VERIFICATION_REQUESTS_EXPIRATION_SECONDS)?;
+        }
+        Ok(count)
+    }
+
+    async fn create_email_verification_code(&self, verification: EmailVerification) -> Result<String, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let code = uuid::Uuid::new_v4().simple().to_string();
+        let _: () = redis.set_ex(Self::email_verification_code_key(&code), verification.to_string()?, EMAIL_VERIFICATION_CODE_EXPIRATION_SECONDS)?;
+        Ok(code)
+    }
+
+    async fn consume_email_verification_code(&self, code: &str) -> Result<Option<EmailVerification>, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let verification: Option<String> = redis.get_del(Self::email_verification_code_key(code))?;
+        verification.map(|verification| EmailVerification::from_str(&verification)).transpose()
+    }
+    async fn create_sign_in_challenge(&self, challenge: SignInChallenge) -> Result<String, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        let _: () = redis.set_ex(Self::sign_in_challenge_key(&id), challenge.to_string()?, SIGN_IN_CHALLENGE_EXPIRATION_SECONDS)?;
+        Ok(id)
+    }
+
+    async fn get_sign_in_challenge(&self, challenge: &str) -> Result<Option<SignInChallenge>, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let value: Option<String> = redis.get(Self::sign_in_challenge_key(challenge))?;
+        value.map(|value| SignInChallenge::from_str(&value)).transpose()
+    }
+
+    async fn increment_sign_in_challenge_attempts(&self, challenge: &str) -> Result<u64, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let key = Self::sign_in_challenge_attempts_key(challenge);
+        let count: u64 = redis.incr(&key, 1)?;
+        if count == 1 {
+            let _: () = redis.expire(&key, SIGN_IN_CHALLENGE_EXPIRATION_SECONDS as i64)?;
+        }
+        Ok(count)
+    }
+
+    async fn delete_sign_in_challenge(&self, challenge: &str) -> Result<(), Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let _: () = redis.del(&[Self::sign_in_challenge_key(challenge), Self::sign_in_challenge_attempts_key(challenge)])?;
+        Ok(())
+    }
+
+    async fn use_totp_code(&self, account_id: &str, code: &str) -> Result<bool, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(TOTP_CODE_EXPIRATION_SECONDS));
+        let result: Option<String> = redis.set_options(Self::totp_code_key(account_id, code), 1, options)?;
+        Ok(result.is_some())
+    }
+    async fn get_sign_in_retry_after(&self, subject: &str) -> Result<u64, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let ttl: i64 = redis.ttl(Self::sign_in_lockout_key(subject))?;
+        Ok(ttl.max(0) as u64)
+    }
+
+    async fn increment_sign_in_failures(&self, subject: &str) -> Result<u64, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let key = Self::sign_in_failures_key(subject);
+        let count: u64 = redis.incr(&key, 1)?;
+        let _: () = redis.expire(&key, SIGN_IN_FAILURES_EXPIRATION_SECONDS)?;
+        Ok(count)
+    }
+
+    async fn lock_sign_in(&self, subject: &str, seconds: u64) -> Result<(), Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let _: () = redis.set_ex(Self::sign_in_lockout_key(subject), 1, seconds)?;
+        Ok(())
+    }
+
+    async fn reset_sign_in_failures(&self, subject: &str) -> Result<(), Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let _: () = redis.del(&[Self::sign_in_failures_key(subject), Self::sign_in_lockout_key(subject)])?;
+        Ok(())
+    }
+
+    async fn publish_sign_in_lockout(&self, subject: &str, locked_until: u64) -> Result<(), Error> {
+        let message = [String::from(subject), locked_until.to_string()].join(",");
+        let _ = self.message_queue.publish(EXCHANGE_NAME, SIGN_IN_LOCKED_ROUTING_KEY, message.as_bytes()).await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn password_reset_requests_key() {
+        assert_eq!(AuthenticationRepositoryImpl::password_reset_requests_key(" User@Example.COM "), "password_reset_requests:user@example.com");
+        assert_eq!(AuthenticationRepositoryImpl::password_reset_requests_key("user@example.com"), AuthenticationRepositoryImpl::password_reset_requests_key("USER@example.com"));
+    }
+}
\ No newline at end of file
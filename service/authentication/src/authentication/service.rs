@@ -1,9 +1,10 @@
 use tonic::{Request, Response, Status};
 
-use crate::account::entity::Role;
+use error::DomainError;
+
 use crate::authentication;
-use crate::authentication::entity::TokenPair;
-use crate::authentication::pb::{ChangePasswordRequest, ChangePasswordResponse, RefreshTokenRequest, RefreshTokenResponse, Role as RoleMessage, SignInRequest, SignInResponse, SignOutRequest, SignOutResponse, SignUpRequest, SignUpResponse, ValidateTokenRequest, ValidateTokenResponse};
+use crate::authentication::entity::{Device, SignInResult, TokenPair};
+use crate::authentication::pb::{ChangeEmailRequest, ChangeEmailResponse, ChangePasswordRequest, ChangePasswordResponse, CompleteSignInRequest, CompleteSignInResponse, ConfirmPasswordResetRequest, ConfirmPasswordResetResponse, ConfirmTotpRequest, ConfirmTotpResponse, EnrollTotpRequest, EnrollTotpResponse, ListSessionsRequest, ListSessionsResponse, RefreshTokenRequest, RefreshTokenResponse, RequestPasswordResetRequest, RequestPasswordResetResponse, ResendVerificationRequest, ResendVerificationResponse, RevokeAllSessionsRequest, RevokeAllSessionsResponse, RevokeSessionRequest, RevokeSessionResponse, SignInRequest, SignInResponse, SignOutRequest, SignOutResponse, SignUpRequest, SignUpResponse, ValidateTokenRequest, ValidateTokenResponse, VerifyEmailRequest, VerifyEmailResponse};
 use crate::authentication::pb::authentication_service_server::AuthenticationService;
 
 pub struct AuthenticationServiceImpl {
@@ -14,21 +15,29 @@ impl AuthenticationServiceImpl {
     pub fn new(interactor: Box<dyn authentication::interactor::AuthenticationInteractor + Send + Sync>) -> impl AuthenticationService {
         AuthenticationServiceImpl { interactor }
     }
+
+    fn get_device<T>(request: &Request<T>, device_name: &str) -> Device {
+        let metadata_value = |key: &str| request.metadata().get(key).and_then(|value| value.to_str().ok()).map(String::from);
+        let ip_address = metadata_value("x-client-ip")
+            .or_else(|| request.remote_addr().map(|addr| addr.ip().to_string()))
+            .unwrap_or_default();
+        let user_agent = metadata_value("x-client-user-agent")
+            .or_else(|| metadata_value("user-agent"))
+            .unwrap_or_default();
+        Device::new(device_name, &ip_address, &user_agent)
+    }
 }
 
 #[tonic::async_trait]
 impl AuthenticationService for AuthenticationServiceImpl {
     async fn sign_up(&self, request: Request<SignUpRequest>) -> Result<Response<SignUpResponse>, Status> {
-        let SignUpRequest { email, password, role } = request.into_inner();
+        let device = Self::get_device(&request, &request.get_ref().device_name);
+        let SignUpRequest { email, password, .. } = request.into_inner();
         if email.is_empty() || password.is_empty() {
             return status::Status::invalid_arguments(vec!["email", "password"]);
         }
 
-        let role = match RoleMessage::from_i32(role).unwrap() {
-            RoleMessage::User => Role::User,
-            RoleMessage::Moderator => Role::Moderator
-        };
-        match self.interactor.sign_up(&email, &password, role).await {
+        match self.interactor.sign_up(&email, &password, device).await {
             Ok(TokenPair { access_token, refresh_token }) => Ok(
                 Response::new(
                     SignUpResponse {
@@ -37,26 +46,88 @@ impl AuthenticationService for AuthenticationServiceImpl {
                     }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn sign_in(&self, request: Request<SignInRequest>) -> Result<Response<SignInResponse>, Status> {
-        let SignInRequest { email, password } = request.into_inner();
+        let device = Self::get_device(&request, &request.get_ref().device_name);
+        let SignInRequest { email, password, .. } = request.into_inner();
         if email.is_empty() || password.is_empty() {
             return status::Status::invalid_arguments(vec!["email", "password"]);
         }
 
-        match self.interactor.sign_in(&email, &password).await {
-            Ok(TokenPair { access_token, refresh_token }) => Ok(
+        match self.interactor.sign_in(&email, &password, device).await {
+            Ok(SignInResult::Authenticated(TokenPair { access_token, refresh_token })) => Ok(
                 Response::new(
                     SignInResponse {
                         access_token,
                         refresh_token,
+                        challenge: String::new(),
+                    }
+                )
+            ),
+            Ok(SignInResult::Challenged(challenge)) => Ok(
+                Response::new(
+                    SignInResponse {
+                        access_token: String::new(),
+                        refresh_token: String::new(),
+                        challenge,
+                    }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn complete_sign_in(&self, request: Request<CompleteSignInRequest>) -> Result<Response<CompleteSignInResponse>, Status> {
+        let CompleteSignInRequest { challenge, code } = request.into_inner();
+        if challenge.is_empty() || code.is_empty() {
+            return status::Status::invalid_arguments(vec!["challenge", "code"]);
+        }
+
+        match self.interactor.complete_sign_in(&challenge, &code).await {
+            Ok(TokenPair { access_token, refresh_token }) => Ok(
+                Response::new(
+                    CompleteSignInResponse {
+                        access_token,
+                        refresh_token,
                     }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn enroll_totp(&self, request: Request<EnrollTotpRequest>) -> Result<Response<EnrollTotpResponse>, Status> {
+        let EnrollTotpRequest { access_token } = request.into_inner();
+        if access_token.is_empty() {
+            return status::Status::invalid_arguments(vec!["access_token"]);
+        }
+
+        match self.interactor.enroll_totp(&access_token).await {
+            Ok((secret, uri)) => Ok(
+                Response::new(
+                    EnrollTotpResponse { secret, uri }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn confirm_totp(&self, request: Request<ConfirmTotpRequest>) -> Result<Response<ConfirmTotpResponse>, Status> {
+        let ConfirmTotpRequest { access_token, code } = request.into_inner();
+        if access_token.is_empty() || code.is_empty() {
+            return status::Status::invalid_arguments(vec!["access_token", "code"]);
+        }
+
+        match self.interactor.confirm_totp(&access_token, &code).await {
+            Ok(recovery_codes) => Ok(
+                Response::new(
+                    ConfirmTotpResponse { recovery_codes }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -72,7 +143,7 @@ impl AuthenticationService for AuthenticationServiceImpl {
                     SignOutResponse { is_success }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -91,7 +162,90 @@ impl AuthenticationService for AuthenticationServiceImpl {
                     }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn change_email(&self, request: Request<ChangeEmailRequest>) -> Result<Response<ChangeEmailResponse>, Status> {
+        let ChangeEmailRequest { access_token, refresh_token, new_email } = request.into_inner();
+        if access_token.is_empty() || refresh_token.is_empty() || new_email.is_empty() {
+            return status::Status::invalid_arguments(vec!["access_token", "refresh_token", "new_email"]);
+        }
+
+        match self.interactor.change_email(&access_token, &refresh_token, &new_email).await {
+            Ok(TokenPair { access_token, refresh_token }) => Ok(
+                Response::new(
+                    ChangeEmailResponse {
+                        access_token,
+                        refresh_token,
+                    }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn verify_email(&self, request: Request<VerifyEmailRequest>) -> Result<Response<VerifyEmailResponse>, Status> {
+        let VerifyEmailRequest { code } = request.into_inner();
+        if code.is_empty() {
+            return status::Status::invalid_arguments(vec!["code"]);
+        }
+
+        match self.interactor.verify_email(&code).await {
+            Ok(_) => Ok(
+                Response::new(
+                    VerifyEmailResponse {}
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn resend_verification(&self, request: Request<ResendVerificationRequest>) -> Result<Response<ResendVerificationResponse>, Status> {
+        let ResendVerificationRequest { access_token } = request.into_inner();
+        if access_token.is_empty() {
+            return status::Status::invalid_arguments(vec!["access_token"]);
+        }
+
+        match self.interactor.resend_verification(&access_token).await {
+            Ok(_) => Ok(
+                Response::new(
+                    ResendVerificationResponse {}
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn request_password_reset(&self, request: Request<RequestPasswordResetRequest>) -> Result<Response<RequestPasswordResetResponse>, Status> {
+        let RequestPasswordResetRequest { email } = request.into_inner();
+        if email.is_empty() {
+            return status::Status::invalid_arguments(vec!["email"]);
+        }
+
+        match self.interactor.request_password_reset(&email).await {
+            Ok(_) => Ok(
+                Response::new(
+                    RequestPasswordResetResponse {}
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn confirm_password_reset(&self, request: Request<ConfirmPasswordResetRequest>) -> Result<Response<ConfirmPasswordResetResponse>, Status> {
+        let ConfirmPasswordResetRequest { code, new_password } = request.into_inner();
+        if code.is_empty() || new_password.is_empty() {
+            return status::Status::invalid_arguments(vec!["code", "new_password"]);
+        }
+
+        match self.interactor.confirm_password_reset(&code, &new_password).await {
+            Ok(_) => Ok(
+                Response::new(
+                    ConfirmPasswordResetResponse {}
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -101,21 +255,17 @@ impl AuthenticationService for AuthenticationServiceImpl {
             return status::Status::invalid_arguments(vec!["refresh_token"]);
         }
 
-        match self.interactor.validate_token(&refresh_token).await {
-            Ok(_) => {
-                match self.interactor.refresh_token(&refresh_token).await {
-                    Ok(TokenPair { access_token, refresh_token }) => Ok(
-                        Response::new(
-                            RefreshTokenResponse {
-                                access_token,
-                                refresh_token,
-                            }
-                        )
-                    ),
-                    _ => status::Status::unauthenticated("invalid token")
-                }
-            }
-            Err(error) => status::Status::internal(error)
+        match self.interactor.refresh_token(&refresh_token).await {
+            Ok(TokenPair { access_token, refresh_token }) => Ok(
+                Response::new(
+                    RefreshTokenResponse {
+                        access_token,
+                        refresh_token,
+                    }
+                )
+            ),
+            Err(error) if error.is::<DomainError>() => status::Status::from_error(error),
+            _ => status::Status::unauthenticated("invalid token")
         }
     }
 
@@ -131,7 +281,56 @@ impl AuthenticationService for AuthenticationServiceImpl {
                     ValidateTokenResponse { payload }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+    async fn list_sessions(&self, request: Request<ListSessionsRequest>) -> Result<Response<ListSessionsResponse>, Status> {
+        let ListSessionsRequest { access_token } = request.into_inner();
+        if access_token.is_empty() {
+            return status::Status::invalid_arguments(vec!["access_token"]);
+        }
+
+        match self.interactor.list_sessions(&access_token).await {
+            Ok(sessions) => Ok(
+                Response::new(
+                    ListSessionsResponse {
+                        sessions: sessions.into_iter().map(|session| session.into()).collect()
+                    }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn revoke_session(&self, request: Request<RevokeSessionRequest>) -> Result<Response<RevokeSessionResponse>, Status> {
+        let RevokeSessionRequest { access_token, session_id } = request.into_inner();
+        if access_token.is_empty() || session_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["access_token", "session_id"]);
+        }
+
+        match self.interactor.revoke_session(&access_token, &session_id).await {
+            Ok(_) => Ok(
+                Response::new(
+                    RevokeSessionResponse {}
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn revoke_all_sessions(&self, request: Request<RevokeAllSessionsRequest>) -> Result<Response<RevokeAllSessionsResponse>, Status> {
+        let RevokeAllSessionsRequest { access_token } = request.into_inner();
+        if access_token.is_empty() {
+            return status::Status::invalid_arguments(vec!["access_token"]);
+        }
+
+        match self.interactor.revoke_all_sessions(&access_token).await {
+            Ok(_) => Ok(
+                Response::new(
+                    RevokeAllSessionsResponse {}
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
         }
     }
 }
\ No newline at end of file
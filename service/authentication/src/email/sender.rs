@@ -0,0 +1,60 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use lettre::transport::smtp::authentication::Credentials;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+#[async_trait::async_trait]
+pub trait EmailSender {
+    async fn send(&self, recipient: &str, subject: &str, body: &str) -> Result<(), Error>;
+}
+
+pub struct SmtpEmailSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    sender: String,
+}
+
+impl SmtpEmailSender {
+    pub fn new(hostname: &str, port: u16, username: &str, password: &str, sender: &str) -> Result<Box<dyn EmailSender + Send + Sync>, Error> {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::relay(hostname)?
+            .port(port)
+            .credentials(Credentials::new(String::from(username), String::from(password)))
+            .build();
+        Ok(Box::new(SmtpEmailSender { transport, sender: String::from(sender) }))
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailSender for SmtpEmailSender {
+    async fn send(&self, recipient: &str, subject: &str, body: &str) -> Result<(), Error> {
+        let message = Message::builder()
+            .from(self.sender.parse()?)
+            .to(recipient.parse()?)
+            .subject(subject)
+            .body(String::from(body))?;
+        self.transport.send(message).await?;
+        Ok(())
+    }
+}
+
+pub struct FileEmailSender {
+    path: String,
+}
+
+impl FileEmailSender {
+    pub fn new(path: &str) -> Box<dyn EmailSender + Send + Sync> {
+        Box::new(FileEmailSender { path: String::from(path) })
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailSender for FileEmailSender {
+    async fn send(&self, recipient: &str, subject: &str, body: &str) -> Result<(), Error> {
+        println!("Email to '{}': {}", recipient, subject);
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "To: {}\nSubject: {}\n\n{}\n", recipient, subject, body)?;
+        Ok(())
+    }
+}
\ No newline at end of file
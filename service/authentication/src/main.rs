@@ -8,11 +8,14 @@ use crate::token::pb::token_service_client::TokenServiceClient;
 
 mod account;
 mod token;
+mod email;
+mod totp;
 mod authentication;
 
 const SERVICE_NAME: &str = "authentication";
 const ACCOUNT_SERVICE_NAME: &str = "account";
 const TOKEN_SERVICE_NAME: &str = "token";
+const EMAIL_FILE_PATH: &str = "email.log";
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
@@ -34,7 +37,22 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let token_api = token::api::TokenApiImpl::new(token_client);
     let token_repository = token::repository::TokenRepositoryImpl::new(token_api);
 
-    let interactor = authentication::interactor::AuthenticationInteractorImpl::new(account_repository, token_repository);
+    let redis_client = redis::Client::open(format!("redis://{}:{}", cfg.redis_hostname.unwrap(), cfg.redis_port.unwrap()))?;
+    let message_queue = amqp::MessageQueue::connect(&cfg.amqp_hostname.unwrap(), &cfg.amqp_port.unwrap()).await?;
+    let repository = authentication::repository::AuthenticationRepositoryImpl::new(redis_client, message_queue);
+
+    let email_sender = match cfg.smtp_hostname {
+        Some(smtp_hostname) => email::sender::SmtpEmailSender::new(
+            &smtp_hostname,
+            cfg.smtp_port.unwrap().parse()?,
+            &cfg.smtp_username.unwrap(),
+            &cfg.smtp_password.unwrap(),
+            &cfg.smtp_sender.unwrap(),
+        ).map_err(|error| error as Box<dyn Error>)?,
+        None => email::sender::FileEmailSender::new(EMAIL_FILE_PATH)
+    };
+
+    let interactor = authentication::interactor::AuthenticationInteractorImpl::new(repository, account_repository, token_repository, email_sender, totp::authenticator::DefaultAuthenticator::new());
     let service = authentication::service::AuthenticationServiceImpl::new(interactor);
 
     let server_addr = SocketAddr::new(cfg.service_hostname.unwrap().parse().unwrap(), cfg.service_port.unwrap().parse().unwrap());
@@ -1,7 +1,7 @@
 use tonic::{Request, Response, Status};
 use tonic::transport::Channel;
 
-use crate::token::pb::{GenerateAccessTokenRequest, GenerateAccessTokenResponse, GenerateRefreshTokenRequest, GenerateRefreshTokenResponse, InvalidateTokenRequest, InvalidateTokenResponse, ValidateTokenRequest, ValidateTokenResponse};
+use crate::token::pb::{CreateSessionRequest, CreateSessionResponse, GenerateAccessTokenRequest, GenerateAccessTokenResponse, GenerateRefreshTokenRequest, GenerateRefreshTokenResponse, InvalidateTokenRequest, InvalidateTokenResponse, ListSessionsRequest, ListSessionsResponse, RevokeAllSessionsRequest, RevokeAllSessionsResponse, RevokeSessionRequest, RevokeSessionResponse, RotateRefreshTokenRequest, RotateRefreshTokenResponse, ValidateTokenRequest, ValidateTokenResponse};
 use crate::token::pb::token_service_client::TokenServiceClient;
 
 #[tonic::async_trait]
@@ -10,6 +10,11 @@ pub trait TokenApi {
     async fn generate_refresh_token(&self, request: Request<GenerateRefreshTokenRequest>) -> Result<Response<GenerateRefreshTokenResponse>, Status>;
     async fn validate_token(&self, request: Request<ValidateTokenRequest>) -> Result<Response<ValidateTokenResponse>, Status>;
     async fn invalidate_token(&self, request: Request<InvalidateTokenRequest>) -> Result<Response<InvalidateTokenResponse>, Status>;
+    async fn rotate_refresh_token(&self, request: Request<RotateRefreshTokenRequest>) -> Result<Response<RotateRefreshTokenResponse>, Status>;
+    async fn create_session(&self, request: Request<CreateSessionRequest>) -> Result<Response<CreateSessionResponse>, Status>;
+    async fn list_sessions(&self, request: Request<ListSessionsRequest>) -> Result<Response<ListSessionsResponse>, Status>;
+    async fn revoke_session(&self, request: Request<RevokeSessionRequest>) -> Result<Response<RevokeSessionResponse>, Status>;
+    async fn revoke_all_sessions(&self, request: Request<RevokeAllSessionsRequest>) -> Result<Response<RevokeAllSessionsResponse>, Status>;
 }
 
 pub struct TokenApiImpl {
@@ -25,37 +30,37 @@ impl TokenApiImpl {
 #[tonic::async_trait]
 impl TokenApi for TokenApiImpl {
     async fn generate_access_token(&self, request: Request<GenerateAccessTokenRequest>) -> Result<Response<GenerateAccessTokenResponse>, Status> {
-        let GenerateAccessTokenRequest { payload } = request.into_inner();
-        if payload.is_empty() {
-            return status::Status::invalid_arguments(vec!["payload"]);
+        let GenerateAccessTokenRequest { payload, subject, family_id, ttl } = request.into_inner();
+        if payload.is_empty() || subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["payload", "subject"]);
         }
 
         self.client
             .clone()
             .generate_access_token(
                 Request::new(
-                    GenerateAccessTokenRequest { payload }
+                    GenerateAccessTokenRequest { payload, subject, family_id, ttl }
                 )
             ).await
     }
 
     async fn generate_refresh_token(&self, request: Request<GenerateRefreshTokenRequest>) -> Result<Response<GenerateRefreshTokenResponse>, Status> {
-        let GenerateRefreshTokenRequest { payload } = request.into_inner();
-        if payload.is_empty() {
-            return status::Status::invalid_arguments(vec!["payload"]);
+        let GenerateRefreshTokenRequest { payload, subject, family_id, ttl } = request.into_inner();
+        if payload.is_empty() || subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["payload", "subject"]);
         }
 
         self.client
             .clone()
             .generate_refresh_token(
                 Request::new(
-                    GenerateRefreshTokenRequest { payload }
+                    GenerateRefreshTokenRequest { payload, subject, family_id, ttl }
                 )
             ).await
     }
 
     async fn validate_token(&self, request: Request<ValidateTokenRequest>) -> Result<Response<ValidateTokenResponse>, Status> {
-        let ValidateTokenRequest { token } = request.into_inner();
+        let ValidateTokenRequest { token, kind } = request.into_inner();
         if token.is_empty() {
             return status::Status::invalid_arguments(vec!["token"]);
         }
@@ -64,7 +69,7 @@ impl TokenApi for TokenApiImpl {
             .clone()
             .validate_token(
                 Request::new(
-                    ValidateTokenRequest { token }
+                    ValidateTokenRequest { token, kind }
                 )
             ).await
     }
@@ -83,4 +88,82 @@ impl TokenApi for TokenApiImpl {
                 )
             ).await
     }
+    async fn rotate_refresh_token(&self, request: Request<RotateRefreshTokenRequest>) -> Result<Response<RotateRefreshTokenResponse>, Status> {
+        let RotateRefreshTokenRequest { token } = request.into_inner();
+        if token.is_empty() {
+            return status::Status::invalid_arguments(vec!["token"]);
+        }
+
+        self.client
+            .clone()
+            .rotate_refresh_token(
+                Request::new(
+                    RotateRefreshTokenRequest { token }
+                )
+            ).await
+    }
+    async fn create_session(&self, request: Request<CreateSessionRequest>) -> Result<Response<CreateSessionResponse>, Status> {
+        let CreateSessionRequest { subject, device_name, ip_address, user_agent } = request.into_inner();
+        if subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["subject"]);
+        }
+
+        self.client
+            .clone()
+            .create_session(
+                Request::new(
+                    CreateSessionRequest {
+                        subject,
+                        device_name,
+                        ip_address,
+                        user_agent,
+                    }
+                )
+            ).await
+    }
+
+    async fn list_sessions(&self, request: Request<ListSessionsRequest>) -> Result<Response<ListSessionsResponse>, Status> {
+        let ListSessionsRequest { subject } = request.into_inner();
+        if subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["subject"]);
+        }
+
+        self.client
+            .clone()
+            .list_sessions(
+                Request::new(
+                    ListSessionsRequest { subject }
+                )
+            ).await
+    }
+
+    async fn revoke_session(&self, request: Request<RevokeSessionRequest>) -> Result<Response<RevokeSessionResponse>, Status> {
+        let RevokeSessionRequest { subject, session_id } = request.into_inner();
+        if subject.is_empty() || session_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["subject", "session_id"]);
+        }
+
+        self.client
+            .clone()
+            .revoke_session(
+                Request::new(
+                    RevokeSessionRequest { subject, session_id }
+                )
+            ).await
+    }
+
+    async fn revoke_all_sessions(&self, request: Request<RevokeAllSessionsRequest>) -> Result<Response<RevokeAllSessionsResponse>, Status> {
+        let RevokeAllSessionsRequest { subject, except_session_id } = request.into_inner();
+        if subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["subject"]);
+        }
+
+        self.client
+            .clone()
+            .revoke_all_sessions(
+                Request::new(
+                    RevokeAllSessionsRequest { subject, except_session_id }
+                )
+            ).await
+    }
 }
\ No newline at end of file
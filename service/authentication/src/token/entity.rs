@@ -0,0 +1,9 @@
+pub struct Session {
+    pub id: String,
+    pub device_name: String,
+    pub ip_address: String,
+    pub user_agent: String,
+    pub created_at: u64,
+    pub last_seen_at: u64,
+    pub is_current: bool,
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+use crate::token::entity::Session;
+use crate::token::pb::Session as SessionMessage;
+
+impl From<SessionMessage> for Session {
+    fn from(value: SessionMessage) -> Self {
+        Self {
+            id: value.id,
+            device_name: value.device_name,
+            ip_address: value.ip_address,
+            user_agent: value.user_agent,
+            created_at: value.created_at,
+            last_seen_at: value.last_seen_at,
+            is_current: false,
+        }
+    }
+}
\ No newline at end of file
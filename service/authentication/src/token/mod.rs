@@ -1,6 +1,8 @@
+pub mod entity;
+pub mod mapper;
 pub mod repository;
 pub mod api;
 
 pub mod pb {
     tonic::include_proto!("token");
-}
\ No newline at end of file
+}
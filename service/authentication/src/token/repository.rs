@@ -1,16 +1,22 @@
 use tonic::Request;
 
 use crate::token::api::TokenApi;
-use crate::token::pb::{GenerateAccessTokenRequest, GenerateAccessTokenResponse, GenerateRefreshTokenRequest, GenerateRefreshTokenResponse, InvalidateTokenRequest, InvalidateTokenResponse, ValidateTokenRequest, ValidateTokenResponse};
+use crate::token::entity::Session;
+use crate::token::pb::{CreateSessionRequest, CreateSessionResponse, GenerateAccessTokenRequest, GenerateAccessTokenResponse, GenerateRefreshTokenRequest, GenerateRefreshTokenResponse, InvalidateTokenRequest, InvalidateTokenResponse, ListSessionsRequest, ListSessionsResponse, RevokeAllSessionsRequest, RevokeAllSessionsResponse, RevokeSessionRequest, RevokeSessionResponse, RotateRefreshTokenRequest, RotateRefreshTokenResponse, TokenKind, ValidateTokenRequest, ValidateTokenResponse};
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
 #[async_trait::async_trait]
 pub trait TokenRepository {
-    async fn generate_access_token(&self, payload: &str) -> Result<String, Error>;
-    async fn generate_refresh_token(&self, payload: &str) -> Result<String, Error>;
+    async fn generate_access_token(&self, subject: &str, payload: &str, family_id: Option<String>) -> Result<String, Error>;
+    async fn generate_refresh_token(&self, subject: &str, payload: &str, family_id: Option<String>) -> Result<String, Error>;
     async fn validate_token(&self, token: &str) -> Result<String, Error>;
     async fn invalidate_token(&self, token: &str) -> Result<(), Error>;
+    async fn rotate_refresh_token(&self, token: &str) -> Result<(String, String, String), Error>;
+    async fn create_session(&self, subject: &str, device_name: &str, ip_address: &str, user_agent: &str) -> Result<String, Error>;
+    async fn list_sessions(&self, subject: &str) -> Result<Vec<Session>, Error>;
+    async fn revoke_session(&self, subject: &str, session_id: &str) -> Result<(), Error>;
+    async fn revoke_all_sessions(&self, subject: &str, except_session_id: Option<String>) -> Result<(), Error>;
 }
 
 pub struct TokenRepositoryImpl {
@@ -25,19 +31,29 @@ impl TokenRepositoryImpl {
 
 #[async_trait::async_trait]
 impl TokenRepository for TokenRepositoryImpl {
-    async fn generate_access_token(&self, payload: &str) -> Result<String, Error> {
+    async fn generate_access_token(&self, subject: &str, payload: &str, family_id: Option<String>) -> Result<String, Error> {
         let GenerateAccessTokenResponse { token } = self.api.generate_access_token(
             Request::new(
-                GenerateAccessTokenRequest { payload: String::from(payload) }
+                GenerateAccessTokenRequest {
+                    payload: String::from(payload),
+                    subject: String::from(subject),
+                    family_id: family_id.unwrap_or_default(),
+                    ttl: 0,
+                }
             )
         ).await?.into_inner();
         Ok(token)
     }
 
-    async fn generate_refresh_token(&self, payload: &str) -> Result<String, Error> {
+    async fn generate_refresh_token(&self, subject: &str, payload: &str, family_id: Option<String>) -> Result<String, Error> {
         let GenerateRefreshTokenResponse { token } = self.api.generate_refresh_token(
             Request::new(
-                GenerateRefreshTokenRequest { payload: String::from(payload) }
+                GenerateRefreshTokenRequest {
+                    payload: String::from(payload),
+                    subject: String::from(subject),
+                    family_id: family_id.unwrap_or_default(),
+                    ttl: 0,
+                }
             )
         ).await?.into_inner();
         Ok(token)
@@ -46,7 +62,7 @@ impl TokenRepository for TokenRepositoryImpl {
     async fn validate_token(&self, token: &str) -> Result<String, Error> {
         let ValidateTokenResponse { payload } = self.api.validate_token(
             Request::new(
-                ValidateTokenRequest { token: String::from(token) }
+                ValidateTokenRequest { token: String::from(token), kind: TokenKind::Access as i32 }
             )
         ).await?.into_inner();
         Ok(payload)
@@ -60,4 +76,58 @@ impl TokenRepository for TokenRepositoryImpl {
         ).await?.into_inner();
         Ok(())
     }
+    async fn rotate_refresh_token(&self, token: &str) -> Result<(String, String, String), Error> {
+        let RotateRefreshTokenResponse { subject, payload, family_id } = self.api.rotate_refresh_token(
+            Request::new(
+                RotateRefreshTokenRequest { token: String::from(token) }
+            )
+        ).await?.into_inner();
+        Ok((subject, payload, family_id))
+    }
+    async fn create_session(&self, subject: &str, device_name: &str, ip_address: &str, user_agent: &str) -> Result<String, Error> {
+        let CreateSessionResponse { session_id } = self.api.create_session(
+            Request::new(
+                CreateSessionRequest {
+                    subject: String::from(subject),
+                    device_name: String::from(device_name),
+                    ip_address: String::from(ip_address),
+                    user_agent: String::from(user_agent),
+                }
+            )
+        ).await?.into_inner();
+        Ok(session_id)
+    }
+
+    async fn list_sessions(&self, subject: &str) -> Result<Vec<Session>, Error> {
+        let ListSessionsResponse { sessions } = self.api.list_sessions(
+            Request::new(
+                ListSessionsRequest { subject: String::from(subject) }
+            )
+        ).await?.into_inner();
+        Ok(sessions.into_iter().map(|session| session.into()).collect())
+    }
+
+    async fn revoke_session(&self, subject: &str, session_id: &str) -> Result<(), Error> {
+        let RevokeSessionResponse {} = self.api.revoke_session(
+            Request::new(
+                RevokeSessionRequest {
+                    subject: String::from(subject),
+                    session_id: String::from(session_id),
+                }
+            )
+        ).await?.into_inner();
+        Ok(())
+    }
+
+    async fn revoke_all_sessions(&self, subject: &str, except_session_id: Option<String>) -> Result<(), Error> {
+        let RevokeAllSessionsResponse {} = self.api.revoke_all_sessions(
+            Request::new(
+                RevokeAllSessionsRequest {
+                    subject: String::from(subject),
+                    except_session_id,
+                }
+            )
+        ).await?.into_inner();
+        Ok(())
+    }
 }
\ No newline at end of file
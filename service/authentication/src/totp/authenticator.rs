@@ -0,0 +1,103 @@
+use sha2::{Digest, Sha256};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+const ISSUER: &str = "dddating";
+const DIGITS: usize = 6;
+const SKEW: u8 = 1;
+const STEP: u64 = 30;
+const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_LENGTH: usize = 10;
+
+pub trait Authenticator {
+    fn generate_secret(&self) -> String;
+    fn get_uri(&self, secret: &str, account_name: &str) -> Result<String, Error>;
+    fn verify_code(&self, secret: &str, code: &str) -> Result<bool, Error>;
+    fn generate_recovery_codes(&self) -> Vec<String>;
+    fn hash_recovery_code(&self, code: &str) -> String;
+}
+
+pub struct DefaultAuthenticator {}
+
+impl DefaultAuthenticator {
+    fn create_totp(secret: &str, account_name: &str) -> Result<TOTP, Error> {
+        let secret = Secret::Encoded(String::from(secret)).to_bytes()?;
+        Ok(TOTP::new(Algorithm::SHA1, DIGITS, SKEW, STEP, secret, Some(String::from(ISSUER)), account_name.replace(':', ""))?)
+    }
+
+    pub fn new() -> Box<dyn Authenticator + Send + Sync> {
+        Box::new(DefaultAuthenticator {})
+    }
+}
+
+impl Authenticator for DefaultAuthenticator {
+    fn generate_secret(&self) -> String {
+        Secret::generate_secret().to_encoded().to_string()
+    }
+
+    fn get_uri(&self, secret: &str, account_name: &str) -> Result<String, Error> {
+        Ok(Self::create_totp(secret, account_name)?.get_url())
+    }
+
+    fn verify_code(&self, secret: &str, code: &str) -> Result<bool, Error> {
+        Ok(Self::create_totp(secret, "")?.check_current(code)?)
+    }
+
+    fn generate_recovery_codes(&self) -> Vec<String> {
+        (0..RECOVERY_CODE_COUNT)
+            .map(|_| uuid::Uuid::new_v4().simple().to_string()[..RECOVERY_CODE_LENGTH].to_string())
+            .collect()
+    }
+
+    fn hash_recovery_code(&self, code: &str) -> String {
+        format!("{:x}", Sha256::digest(code.trim().to_lowercase().as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_current_code() {
+        let authenticator = DefaultAuthenticator::new();
+        let secret = authenticator.generate_secret();
+        let code = DefaultAuthenticator::create_totp(&secret, "").unwrap().generate_current().unwrap();
+        assert_eq!(code.len(), DIGITS);
+        assert!(authenticator.verify_code(&secret, &code).unwrap());
+    }
+
+    #[test]
+    fn reject_malformed_code() {
+        let authenticator = DefaultAuthenticator::new();
+        let secret = authenticator.generate_secret();
+        assert!(!authenticator.verify_code(&secret, "abcdef").unwrap());
+        assert!(!authenticator.verify_code(&secret, "12345").unwrap());
+    }
+
+    #[test]
+    fn get_uri() {
+        let authenticator = DefaultAuthenticator::new();
+        let uri = authenticator.get_uri(&authenticator.generate_secret(), "user@example.com").unwrap();
+        assert!(uri.starts_with("otpauth://totp/dddating:user%40example.com?"));
+    }
+
+    #[test]
+    fn generate_recovery_codes() {
+        let authenticator = DefaultAuthenticator::new();
+        let codes = authenticator.generate_recovery_codes();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+        assert!(codes.iter().all(|code| code.len() == RECOVERY_CODE_LENGTH));
+        let hashes: std::collections::HashSet<String> = codes.iter().map(|code| authenticator.hash_recovery_code(code)).collect();
+        assert_eq!(hashes.len(), RECOVERY_CODE_COUNT);
+    }
+
+    #[test]
+    fn hash_recovery_code() {
+        let authenticator = DefaultAuthenticator::new();
+        let hash = authenticator.hash_recovery_code("a1b2c3d4e5");
+        assert_eq!(authenticator.hash_recovery_code(" A1B2C3D4E5 "), hash);
+        assert_ne!(authenticator.hash_recovery_code("a1b2c3d4e6"), hash);
+    }
+}
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod authenticator;
\ No newline at end of file
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
@@ -13,6 +14,10 @@ pub struct Chat {
     pub typing_member_ids: Vec<String>,
     pub created_at: u64,
     pub updated_at: u64,
+    #[serde(default)]
+    pub unread_counts: HashMap<String, u64>,
+    #[serde(default)]
+    pub blocked_member_ids: Vec<String>,
 }
 
 impl Chat {
@@ -23,11 +28,13 @@ impl Chat {
     pub fn new(id: &str, member_ids: Vec<String>) -> Self {
         Self {
             id: String::from(id),
+            unread_counts: member_ids.iter().map(|member_id| (member_id.clone(), 0)).collect(),
             member_ids,
             last_message: None,
             typing_member_ids: vec![],
             created_at: Self::timestamp_now(),
             updated_at: Self::timestamp_now(),
+            blocked_member_ids: vec![],
         }
     }
 }
\ No newline at end of file
@@ -10,6 +10,8 @@ impl From<ChatMessage> for Chat {
             typing_member_ids: value.typing_member_ids,
             created_at: value.created_at,
             updated_at: value.updated_at,
+            unread_counts: value.unread_counts,
+            blocked_member_ids: vec![],
         }
     }
 }
@@ -23,6 +25,8 @@ impl From<Chat> for ChatMessage {
             typing_member_ids: value.typing_member_ids,
             created_at: value.created_at,
             updated_at: value.updated_at,
+            unread_counts: value.unread_counts,
+            is_closed: !value.blocked_member_ids.is_empty(),
         }
     }
 }
\ No newline at end of file
@@ -4,7 +4,7 @@ use mongodb::bson::{bson, doc};
 use mongodb::bson::oid::ObjectId;
 use mongodb::options::FindOptions;
 
-use error::make_error;
+use error::{DomainError, make_error};
 
 use crate::chat::entity::Chat;
 use crate::message::entity::Message;
@@ -28,6 +28,11 @@ pub trait ChatRepository {
         typing_member_ids: Vec<String>,
     ) -> Result<Chat, Error>;
     async fn delete_chat(&self, chat_id: &str) -> Result<String, Error>;
+    async fn set_unread_count(&self, chat_id: &str, member_id: &str, count: u64) -> Result<(), Error>;
+    async fn set_blocked(&self, from_id: &str, to_id: &str, is_blocked: bool) -> Result<(), Error>;
+    async fn get_chat_ids(&self, member_id: &str) -> Result<Vec<String>, Error>;
+    async fn get_member_chats(&self, member_id: &str) -> Result<Vec<Chat>, Error>;
+    async fn delete_chats(&self, chat_ids: &[String]) -> Result<(), Error>;
 }
 
 pub struct ChatRepositoryImpl {
@@ -44,14 +49,11 @@ impl ChatRepositoryImpl {
 impl ChatRepository for ChatRepositoryImpl {
     async fn get_chats(&self, member_id: &str, skip: u64, limit: u64) -> Result<Vec<Chat>, Error> {
         let filter = doc! {
-            "member_ids": {
-                "$elemMatch": {
-                    "member_id": member_id
-                }
-            }
+            "member_ids": member_id,
+            "blocked_member_ids.0": { "$exists": false }
         };
         let mut chats: Vec<Chat> = vec![];
-        let options = FindOptions::builder().skip(skip).limit(limit.try_into().ok()).build();
+        let options = FindOptions::builder().sort(doc! { "updated_at": -1 }).skip(skip).limit(limit.try_into().ok()).build();
         let mut cursor = self.collection.find(filter, options).await?;
         while let Some(chat) = cursor.try_next().await? {
             chats.push(chat)
@@ -60,10 +62,7 @@ impl ChatRepository for ChatRepositoryImpl {
     }
 
     async fn get_chat_by_id(&self, chat_id: &str) -> Result<Option<Chat>, Error> {
-        if let Ok(chat) = self.collection.find_one(doc! { "_id": chat_id }, None).await {
-            return Ok(chat);
-        }
-        Err(make_error!("unable to get chat by id"))
+        Ok(self.collection.find_one(doc! { "_id": chat_id }, None).await?)
     }
 
     async fn create_chat(&self, member_ids: Vec<String>) -> Result<Option<Chat>, Error> {
@@ -99,13 +98,60 @@ impl ChatRepository for ChatRepositoryImpl {
                 return Ok(chat);
             }
         }
-        Err(make_error!("unable to update chat"))
+        Err(DomainError::not_found("chat not found"))
     }
 
     async fn delete_chat(&self, chat_id: &str) -> Result<String, Error> {
         if self.collection.delete_one(doc! { "_id": chat_id }, None).await?.deleted_count > 0 {
             return Ok(String::from(chat_id));
         }
-        Err(make_error!("unable to delete chat"))
+        Err(DomainError::not_found("chat not found"))
+    }
+
+    async fn set_unread_count(&self, chat_id: &str, member_id: &str, count: u64) -> Result<(), Error> {
+        let document = doc! { format!("unread_counts.{}", member_id): count as i64 };
+        if self.collection.update_one(doc! { "_id": chat_id }, doc! { "$set": document }, None).await?.matched_count > 0 {
+            return Ok(());
+        }
+        Err(DomainError::not_found("chat not found"))
+    }
+
+    async fn set_blocked(&self, from_id: &str, to_id: &str, is_blocked: bool) -> Result<(), Error> {
+        let filter = doc! {
+            "member_ids": {
+                "$all": [from_id, to_id]
+            }
+        };
+        let update = if is_blocked {
+            doc! { "$addToSet": { "blocked_member_ids": from_id } }
+        } else {
+            doc! { "$pull": { "blocked_member_ids": from_id } }
+        };
+        self.collection.update_many(filter, update, None).await?;
+        Ok(())
+    }
+
+    async fn get_chat_ids(&self, member_id: &str) -> Result<Vec<String>, Error> {
+        let mut chat_ids: Vec<String> = vec![];
+        let mut cursor = self.collection.find(doc! { "member_ids": member_id }, None).await?;
+        while let Some(chat) = cursor.try_next().await? {
+            chat_ids.push(chat.id)
+        }
+        Ok(chat_ids)
+    }
+
+    async fn get_member_chats(&self, member_id: &str) -> Result<Vec<Chat>, Error> {
+        let mut chats: Vec<Chat> = vec![];
+        let options = FindOptions::builder().sort(doc! { "created_at": 1 }).build();
+        let mut cursor = self.collection.find(doc! { "member_ids": member_id }, options).await?;
+        while let Some(chat) = cursor.try_next().await? {
+            chats.push(chat)
+        }
+        Ok(chats)
+    }
+
+    async fn delete_chats(&self, chat_ids: &[String]) -> Result<(), Error> {
+        self.collection.delete_many(doc! { "_id": { "$in": chat_ids } }, None).await?;
+        Ok(())
     }
 }
\ No newline at end of file
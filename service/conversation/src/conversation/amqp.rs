@@ -5,11 +5,22 @@ use tokio::spawn;
 
 use amqp::MessageQueue;
 
+use crate::conversation::event::EventBus;
 use crate::conversation::interactor::ConversationInteractor;
+use crate::conversation::pb::{ChatEvent, MemberBlocked};
+use crate::conversation::pb::chat_event::Event;
 
 const EXCHANGE_NAME: &str = "action";
 const QUEUE_NAME: &str = "matchmaking";
 const ROUTING_KEY: &str = "match";
+const BLOCKED_QUEUE_NAME: &str = "conversation.user.blocked";
+const BLOCKED_ROUTING_KEY: &str = "user.blocked";
+const UNBLOCKED_QUEUE_NAME: &str = "conversation.user.unblocked";
+const UNBLOCKED_ROUTING_KEY: &str = "user.unblocked";
+const DELETED_QUEUE_NAME: &str = "conversation.account.deleted";
+const DELETED_ROUTING_KEY: &str = "account.deleted";
+const ERASED_ROUTING_KEY: &str = "account.erased";
+const SERVICE_NAME: &str = "conversation";
 
 pub struct MessageQueueHandler {
     interactor: Arc<Box<dyn ConversationInteractor + Send + Sync>>,
@@ -43,4 +54,75 @@ impl MessageQueueHandler {
 
         let _ = self.message_queue.start_consuming(QUEUE_NAME, Box::new(handle_message)).await;
     }
+
+    pub async fn consume_blocked_users(&mut self, event_bus: Arc<EventBus>) {
+        let _ = self.message_queue.bind_queue(EXCHANGE_NAME, "direct", BLOCKED_QUEUE_NAME, BLOCKED_ROUTING_KEY).await;
+
+        let interactor = Arc::clone(&self.interactor);
+        let handle_message = move |msg: &[u8]| {
+            let interactor = Arc::clone(&interactor);
+            let event_bus = Arc::clone(&event_bus);
+            if let Ok(value) = from_utf8(msg) {
+                let user_ids: Vec<String> = value.split(",").map(String::from).collect();
+                if user_ids.len() == 2 {
+                    spawn(async move {
+                        if interactor.block_member(&user_ids[0], &user_ids[1]).await.is_ok() {
+                            event_bus.publish(ChatEvent {
+                                chat_id: String::new(),
+                                member_ids: user_ids.clone(),
+                                event: Some(Event::MemberBlocked(MemberBlocked {
+                                    member_id: user_ids[0].clone(),
+                                    blocked_member_id: user_ids[1].clone(),
+                                })),
+                            }).await;
+                        }
+                    });
+                }
+            }
+            Ok(())
+        };
+
+        let _ = self.message_queue.start_consuming(BLOCKED_QUEUE_NAME, Box::new(handle_message)).await;
+    }
+
+    pub async fn consume_unblocked_users(&mut self) {
+        let _ = self.message_queue.bind_queue(EXCHANGE_NAME, "direct", UNBLOCKED_QUEUE_NAME, UNBLOCKED_ROUTING_KEY).await;
+
+        let interactor = Arc::clone(&self.interactor);
+        let handle_message = move |msg: &[u8]| {
+            let interactor = Arc::clone(&interactor);
+            if let Ok(value) = from_utf8(msg) {
+                let user_ids: Vec<String> = value.split(",").map(String::from).collect();
+                if user_ids.len() == 2 {
+                    spawn(async move {
+                        let _ = interactor.unblock_member(&user_ids[0], &user_ids[1]).await;
+                    });
+                }
+            }
+            Ok(())
+        };
+
+        let _ = self.message_queue.start_consuming(UNBLOCKED_QUEUE_NAME, Box::new(handle_message)).await;
+    }
+
+    pub async fn consume_deleted_accounts(&mut self) {
+        let _ = self.message_queue.bind_queue(EXCHANGE_NAME, "direct", DELETED_QUEUE_NAME, DELETED_ROUTING_KEY).await;
+
+        let interactor = Arc::clone(&self.interactor);
+        let message_queue = self.message_queue.clone();
+        let handle_message = move |msg: Vec<u8>| {
+            let interactor = Arc::clone(&interactor);
+            let message_queue = message_queue.clone();
+            async move {
+                if let Ok(id) = String::from_utf8(msg) {
+                    interactor.erase_account(&id).await?;
+                    let message = [id.as_str(), SERVICE_NAME].join(",");
+                    message_queue.publish(EXCHANGE_NAME, ERASED_ROUTING_KEY, message.as_bytes()).await?;
+                }
+                Ok(())
+            }
+        };
+
+        let _ = self.message_queue.start_consuming_async(DELETED_QUEUE_NAME, handle_message).await;
+    }
 }
\ No newline at end of file
@@ -0,0 +1,48 @@
+use prost::Message;
+use tokio::sync::broadcast::{channel, Receiver, Sender};
+
+use amqp::MessageQueue;
+
+use crate::conversation::pb::ChatEvent;
+
+const EXCHANGE_NAME: &str = "conversation";
+const EXCHANGE_TYPE: &str = "fanout";
+const ROUTING_KEY: &str = "";
+const CHANNEL_CAPACITY: usize = 1024;
+
+pub struct EventBus {
+    message_queue: MessageQueue,
+    sender: Sender<ChatEvent>,
+}
+
+impl EventBus {
+    pub fn new(message_queue: MessageQueue) -> Self {
+        let (sender, _) = channel(CHANNEL_CAPACITY);
+        Self {
+            message_queue,
+            sender,
+        }
+    }
+
+    pub async fn consume_events(&mut self) {
+        if let Ok(queue_name) = self.message_queue.bind_exclusive_queue(EXCHANGE_NAME, EXCHANGE_TYPE, ROUTING_KEY).await {
+            let sender = self.sender.clone();
+            let handle_message = move |msg: &[u8]| {
+                if let Ok(event) = ChatEvent::decode(msg) {
+                    let _ = sender.send(event);
+                }
+                Ok(())
+            };
+
+            let _ = self.message_queue.start_consuming(&queue_name, Box::new(handle_message)).await;
+        }
+    }
+
+    pub async fn publish(&self, event: ChatEvent) {
+        let _ = self.message_queue.publish(EXCHANGE_NAME, ROUTING_KEY, &event.encode_to_vec()).await;
+    }
+
+    pub fn subscribe(&self) -> Receiver<ChatEvent> {
+        self.sender.subscribe()
+    }
+}
\ No newline at end of file
@@ -1,3 +1,7 @@
+use serde_json::json;
+
+use error::DomainError;
+
 use crate::chat::entity::Chat;
 use crate::chat::repository::ChatRepository;
 use crate::message::entity::Message;
@@ -13,22 +17,23 @@ pub trait ConversationInteractor {
         skip: u64,
         limit: u64,
     ) -> Result<Vec<Chat>, Error>;
-    async fn get_chat_by_id(&self, chat_id: &str) -> Result<Option<Chat>, Error>;
+    async fn get_chat_by_id(&self, chat_id: &str, member_id: &str) -> Result<Chat, Error>;
     async fn create_chat(&self, member_ids: Vec<String>) -> Result<Option<Chat>, Error>;
     async fn update_chat(
         &self,
         chat_id: &str,
-        last_message: Option<Message>,
+        member_id: &str,
         typing_member_ids: Vec<String>,
     ) -> Result<Chat, Error>;
-    async fn delete_chat(&self, chat_id: &str) -> Result<String, Error>;
+    async fn delete_chat(&self, chat_id: &str, member_id: &str) -> Result<String, Error>;
     async fn get_messages(
         &self,
         chat_id: &str,
+        member_id: &str,
         skip: u64,
         limit: u64,
     ) -> Result<Vec<Message>, Error>;
-    async fn get_message_by_id(&self, message_id: &str) -> Result<Option<Message>, Error>;
+    async fn get_message_by_id(&self, message_id: &str, member_id: &str) -> Result<Message, Error>;
     async fn send_message(
         &self,
         chat_id: &str,
@@ -36,7 +41,24 @@ pub trait ConversationInteractor {
         text: Option<String>,
         images: Vec<Vec<u8>>,
     ) -> Result<Message, Error>;
-    async fn delete_message(&self, message_id: &str) -> Result<String, Error>;
+    async fn edit_message(&self, message_id: &str, member_id: &str, text: &str) -> Result<Message, Error>;
+    async fn delete_message(&self, message_id: &str, member_id: &str) -> Result<String, Error>;
+    async fn mark_delivered(
+        &self,
+        chat_id: &str,
+        member_id: &str,
+        up_to_message_id: &str,
+    ) -> Result<Vec<String>, Error>;
+    async fn mark_read(
+        &self,
+        chat_id: &str,
+        member_id: &str,
+        up_to_message_id: &str,
+    ) -> Result<Vec<String>, Error>;
+    async fn block_member(&self, from_id: &str, to_id: &str) -> Result<(), Error>;
+    async fn unblock_member(&self, from_id: &str, to_id: &str) -> Result<(), Error>;
+    async fn erase_account(&self, account_id: &str) -> Result<(), Error>;
+    async fn export_data(&self, account_id: &str) -> Result<Vec<u8>, Error>;
 }
 
 pub struct ConversationInteractorImpl {
@@ -51,6 +73,30 @@ impl ConversationInteractorImpl {
     ) -> Box<dyn ConversationInteractor + Send + Sync> {
         Box::new(ConversationInteractorImpl { chat_repository, message_repository })
     }
+
+    async fn get_member_chat(&self, chat_id: &str, member_id: &str) -> Result<Chat, Error> {
+        match self.chat_repository.get_chat_by_id(chat_id).await? {
+            Some(chat) if chat.member_ids.iter().any(|id| id == member_id) => Ok(chat),
+            Some(_) => Err(DomainError::permission_denied("member does not belong to the chat")),
+            None => Err(DomainError::not_found("chat not found"))
+        }
+    }
+
+    async fn get_open_chat(&self, chat_id: &str, member_id: &str) -> Result<Chat, Error> {
+        let chat = self.get_member_chat(chat_id, member_id).await?;
+        if !chat.blocked_member_ids.is_empty() {
+            return Err(DomainError::failed_precondition("chat is closed"));
+        }
+        Ok(chat)
+    }
+
+    async fn get_sender_message(&self, message_id: &str, member_id: &str) -> Result<Message, Error> {
+        match self.message_repository.get_message_by_id(message_id).await? {
+            Some(message) if message.sender_id == member_id => Ok(message),
+            Some(_) => Err(DomainError::permission_denied("member is not the sender of the message")),
+            None => Err(DomainError::not_found("message not found"))
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -59,35 +105,87 @@ impl ConversationInteractor for ConversationInteractorImpl {
         self.chat_repository.get_chats(member_id, skip, limit).await
     }
 
-    async fn get_chat_by_id(&self, chat_id: &str) -> Result<Option<Chat>, Error> {
-        self.chat_repository.get_chat_by_id(chat_id).await
+    async fn get_chat_by_id(&self, chat_id: &str, member_id: &str) -> Result<Chat, Error> {
+        self.get_member_chat(chat_id, member_id).await
     }
 
     async fn create_chat(&self, member_ids: Vec<String>) -> Result<Option<Chat>, Error> {
         self.chat_repository.create_chat(member_ids).await
     }
 
-    async fn update_chat(&self, chat_id: &str, last_message: Option<Message>, typing_member_ids: Vec<String>) -> Result<Chat, Error> {
-        self.chat_repository.update_chat(chat_id, last_message, typing_member_ids).await
+    async fn update_chat(&self, chat_id: &str, member_id: &str, typing_member_ids: Vec<String>) -> Result<Chat, Error> {
+        let chat = self.get_open_chat(chat_id, member_id).await?;
+        let typing_member_ids = typing_member_ids.into_iter().filter(|id| chat.member_ids.contains(id)).collect();
+        self.chat_repository.update_chat(chat_id, None, typing_member_ids).await
     }
 
-    async fn delete_chat(&self, chat_id: &str) -> Result<String, Error> {
+    async fn delete_chat(&self, chat_id: &str, member_id: &str) -> Result<String, Error> {
+        self.get_member_chat(chat_id, member_id).await?;
         self.chat_repository.delete_chat(chat_id).await
     }
 
-    async fn get_messages(&self, chat_id: &str, skip: u64, limit: u64) -> Result<Vec<Message>, Error> {
+    async fn get_messages(&self, chat_id: &str, member_id: &str, skip: u64, limit: u64) -> Result<Vec<Message>, Error> {
+        self.get_member_chat(chat_id, member_id).await?;
         self.message_repository.get_messages(chat_id, skip, limit).await
     }
 
-    async fn get_message_by_id(&self, message_id: &str) -> Result<Option<Message>, Error> {
-        self.message_repository.get_message_by_id(message_id).await
+    async fn get_message_by_id(&self, message_id: &str, member_id: &str) -> Result<Message, Error> {
+        match self.message_repository.get_message_by_id(message_id).await? {
+            Some(message) => {
+                self.get_member_chat(&message.chat_id, member_id).await?;
+                Ok(message)
+            }
+            None => Err(DomainError::not_found("message not found"))
+        }
     }
 
     async fn send_message(&self, chat_id: &str, member_id: &str, text: Option<String>, images: Vec<Vec<u8>>) -> Result<Message, Error> {
+        self.get_open_chat(chat_id, member_id).await?;
         self.message_repository.create_message(chat_id, member_id, text, images).await
     }
 
-    async fn delete_message(&self, message_id: &str) -> Result<String, Error> {
+    async fn edit_message(&self, message_id: &str, member_id: &str, text: &str) -> Result<Message, Error> {
+        let message = self.get_sender_message(message_id, member_id).await?;
+        self.get_open_chat(&message.chat_id, member_id).await?;
+        self.message_repository.edit_message(message_id, text).await
+    }
+
+    async fn delete_message(&self, message_id: &str, member_id: &str) -> Result<String, Error> {
+        self.get_sender_message(message_id, member_id).await?;
         self.message_repository.delete_message(message_id).await
     }
+
+    async fn mark_delivered(&self, chat_id: &str, member_id: &str, up_to_message_id: &str) -> Result<Vec<String>, Error> {
+        self.get_member_chat(chat_id, member_id).await?;
+        self.message_repository.mark_delivered(chat_id, member_id, up_to_message_id).await
+    }
+
+    async fn mark_read(&self, chat_id: &str, member_id: &str, up_to_message_id: &str) -> Result<Vec<String>, Error> {
+        self.get_member_chat(chat_id, member_id).await?;
+        let message_ids = self.message_repository.mark_read(chat_id, member_id, up_to_message_id).await?;
+        let count = self.message_repository.count_unread(chat_id, member_id).await?;
+        self.chat_repository.set_unread_count(chat_id, member_id, count).await?;
+        Ok(message_ids)
+    }
+
+    async fn block_member(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
+        self.chat_repository.set_blocked(from_id, to_id, true).await
+    }
+
+    async fn unblock_member(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
+        self.chat_repository.set_blocked(from_id, to_id, false).await
+    }
+
+    async fn erase_account(&self, account_id: &str) -> Result<(), Error> {
+        let chat_ids = self.chat_repository.get_chat_ids(account_id).await?;
+        self.message_repository.delete_chat_messages(&chat_ids).await?;
+        self.chat_repository.delete_chats(&chat_ids).await
+    }
+
+    async fn export_data(&self, account_id: &str) -> Result<Vec<u8>, Error> {
+        let chats = self.chat_repository.get_member_chats(account_id).await?;
+        let chat_ids: Vec<String> = chats.iter().map(|chat| chat.id.clone()).collect();
+        let messages = self.message_repository.get_chat_messages(&chat_ids).await?;
+        Ok(serde_json::to_vec(&json!({ "chats": chats, "messages": messages }))?)
+    }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 pub mod interactor;
 pub mod amqp;
+pub mod event;
 pub mod service;
 
 pub mod pb {
@@ -1,22 +1,71 @@
+use std::pin::Pin;
 use std::sync::Arc;
+
+use futures::stream::unfold;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_stream::Stream;
 use tonic::{Request, Response, Status};
 
+use crate::conversation::event::EventBus;
 use crate::conversation::interactor::ConversationInteractor;
-use crate::conversation::pb::{DeleteChatRequest, DeleteChatResponse, DeleteMessageRequest, DeleteMessageResponse, GetChatByIdRequest, GetChatByIdResponse, GetChatsRequest, GetChatsResponse, GetMessageByIdRequest, GetMessageByIdResponse, GetMessagesRequest, GetMessagesResponse, SendMessageRequest, SendMessageResponse, UpdateChatRequest, UpdateChatResponse};
+use crate::conversation::pb::{ChatEvent, DeleteChatRequest, DeleteChatResponse, DeleteMessageRequest, DeleteMessageResponse, EditMessageRequest, EditMessageResponse, ExportDataRequest, ExportDataResponse, GetChatByIdRequest, GetChatByIdResponse, GetChatsRequest, GetChatsResponse, GetMessageByIdRequest, GetMessageByIdResponse, GetMessagesRequest, GetMessagesResponse, MarkDeliveredRequest, MarkDeliveredResponse, MarkReadRequest, MarkReadResponse, MessageDeleted, MessageEdited, MessagesStatusChanged, MessageSent, SendMessageRequest, SendMessageResponse, SubscribeChatRequest, SubscribeInboxRequest, TypingChanged, UpdateChatRequest, UpdateChatResponse};
+use crate::conversation::pb::Message as MessageMessage;
+use crate::conversation::pb::chat_event::Event;
 use crate::conversation::pb::conversation_service_server::ConversationService;
 
+type EventStream = Pin<Box<dyn Stream<Item=Result<ChatEvent, Status>> + Send>>;
+
 pub struct ConversationServiceImpl {
     interactor: Arc<Box<dyn ConversationInteractor + Send + Sync>>,
+    event_bus: Arc<EventBus>,
 }
 
 impl ConversationServiceImpl {
-    pub fn new(interactor: Arc<Box<dyn ConversationInteractor + Send + Sync>>) -> impl ConversationService {
-        ConversationServiceImpl { interactor }
+    pub fn new(interactor: Arc<Box<dyn ConversationInteractor + Send + Sync>>, event_bus: Arc<EventBus>) -> impl ConversationService {
+        ConversationServiceImpl { interactor, event_bus }
+    }
+
+    fn subscribe(&self, filter: impl Fn(&ChatEvent) -> bool + Send + 'static, is_closing: impl Fn(&ChatEvent) -> bool + Send + 'static) -> EventStream {
+        let state = Some((self.event_bus.subscribe(), filter, is_closing));
+        let stream = unfold(state, |state| async move {
+            let (mut receiver, filter, is_closing) = state?;
+            loop {
+                match receiver.recv().await {
+                    Ok(event) if is_closing(&event) => return None,
+                    Ok(ChatEvent { event: Some(Event::MemberBlocked(_)), .. }) => continue,
+                    Ok(event) if filter(&event) => return Some((Ok(event), Some((receiver, filter, is_closing)))),
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => return Some((Err(Status::data_loss(format!("subscription missed {} events", skipped))), None)),
+                    Err(RecvError::Closed) => return None
+                }
+            }
+        });
+        Box::pin(stream)
+    }
+
+    async fn publish_status_changed(&self, chat_id: &str, member_id: &str, message_ids: &[String], is_read: bool) {
+        if message_ids.is_empty() {
+            return;
+        }
+        if let Ok(chat) = self.interactor.get_chat_by_id(chat_id, member_id).await {
+            self.event_bus.publish(ChatEvent {
+                chat_id: chat.id,
+                member_ids: chat.member_ids,
+                event: Some(Event::MessagesStatusChanged(MessagesStatusChanged {
+                    message_ids: message_ids.to_vec(),
+                    is_delivered: true,
+                    is_read,
+                })),
+            }).await;
+        }
     }
 }
 
 #[tonic::async_trait]
 impl ConversationService for ConversationServiceImpl {
+    type SubscribeChatStream = EventStream;
+    type SubscribeInboxStream = EventStream;
+
     async fn get_chats(&self, request: Request<GetChatsRequest>) -> Result<Response<GetChatsResponse>, Status> {
         let GetChatsRequest { member_id, skip, limit } = request.into_inner();
         if member_id.is_empty() {
@@ -31,69 +80,76 @@ impl ConversationService for ConversationServiceImpl {
                     }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn get_chat_by_id(&self, request: Request<GetChatByIdRequest>) -> Result<Response<GetChatByIdResponse>, Status> {
-        let GetChatByIdRequest { chat_id } = request.into_inner();
-        if chat_id.is_empty() {
-            return status::Status::invalid_arguments(vec!["chat_id"]);
+        let GetChatByIdRequest { chat_id, member_id } = request.into_inner();
+        if chat_id.is_empty() || member_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["chat_id", "member_id"]);
         }
 
-        match self.interactor.get_chat_by_id(&chat_id).await {
+        match self.interactor.get_chat_by_id(&chat_id, &member_id).await {
             Ok(chat) => Ok(
                 Response::new(
                     GetChatByIdResponse {
-                        chat: chat.map(|chat| chat.into())
+                        chat: Some(chat.into())
                     }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn update_chat(&self, request: Request<UpdateChatRequest>) -> Result<Response<UpdateChatResponse>, Status> {
-        let UpdateChatRequest { chat_id, typing_member_ids, .. } = request.into_inner();
-        if chat_id.is_empty() {
-            return status::Status::invalid_arguments(vec!["chat_id"]);
+        let UpdateChatRequest { chat_id, typing_member_ids, member_id, .. } = request.into_inner();
+        if chat_id.is_empty() || member_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["chat_id", "member_id"]);
         }
 
-        match self.interactor.update_chat(&chat_id, None, typing_member_ids).await {
-            Ok(chat) => Ok(
-                Response::new(
-                    UpdateChatResponse {
-                        chat: Some(chat.into())
-                    }
+        match self.interactor.update_chat(&chat_id, &member_id, typing_member_ids).await {
+            Ok(chat) => {
+                self.event_bus.publish(ChatEvent {
+                    chat_id: chat.id.clone(),
+                    member_ids: chat.member_ids.clone(),
+                    event: Some(Event::TypingChanged(TypingChanged { typing_member_ids: chat.typing_member_ids.clone() })),
+                }).await;
+                Ok(
+                    Response::new(
+                        UpdateChatResponse {
+                            chat: Some(chat.into())
+                        }
+                    )
                 )
-            ),
-            Err(error) => status::Status::internal(error)
+            }
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn delete_chat(&self, request: Request<DeleteChatRequest>) -> Result<Response<DeleteChatResponse>, Status> {
-        let DeleteChatRequest { chat_id } = request.into_inner();
-        if chat_id.is_empty() {
-            return status::Status::invalid_arguments(vec!["chat_id"]);
+        let DeleteChatRequest { chat_id, member_id } = request.into_inner();
+        if chat_id.is_empty() || member_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["chat_id", "member_id"]);
         }
 
-        match self.interactor.delete_chat(&chat_id).await {
+        match self.interactor.delete_chat(&chat_id, &member_id).await {
             Ok(chat_id) => Ok(
                 Response::new(
                     DeleteChatResponse { chat_id }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn get_messages(&self, request: Request<GetMessagesRequest>) -> Result<Response<GetMessagesResponse>, Status> {
-        let GetMessagesRequest { chat_id, skip, limit } = request.into_inner();
-        if chat_id.is_empty() {
-            return status::Status::invalid_arguments(vec!["chat_id"]);
+        let GetMessagesRequest { chat_id, skip, limit, member_id } = request.into_inner();
+        if chat_id.is_empty() || member_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["chat_id", "member_id"]);
         }
 
-        match self.interactor.get_messages(&chat_id, skip, limit).await {
+        match self.interactor.get_messages(&chat_id, &member_id, skip, limit).await {
             Ok(messages) => Ok(
                 Response::new(
                     GetMessagesResponse {
@@ -101,25 +157,25 @@ impl ConversationService for ConversationServiceImpl {
                     }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn get_message_by_id(&self, request: Request<GetMessageByIdRequest>) -> Result<Response<GetMessageByIdResponse>, Status> {
-        let GetMessageByIdRequest { message_id } = request.into_inner();
-        if message_id.is_empty() {
-            return status::Status::invalid_arguments(vec!["message_id"]);
+        let GetMessageByIdRequest { message_id, member_id } = request.into_inner();
+        if message_id.is_empty() || member_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["message_id", "member_id"]);
         }
 
-        match self.interactor.get_message_by_id(&message_id).await {
+        match self.interactor.get_message_by_id(&message_id, &member_id).await {
             Ok(message) => Ok(
                 Response::new(
                     GetMessageByIdResponse {
-                        message: message.map(|message| message.into())
+                        message: Some(message.into())
                     }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -133,30 +189,158 @@ impl ConversationService for ConversationServiceImpl {
         }
 
         match self.interactor.send_message(&chat_id, &member_id, text, images).await {
-            Ok(message) => Ok(
-                Response::new(
-                    SendMessageResponse {
-                        message: Some(message.into())
-                    }
+            Ok(message) => {
+                let message: MessageMessage = message.into();
+                if let Ok(chat) = self.interactor.get_chat_by_id(&chat_id, &member_id).await {
+                    self.event_bus.publish(ChatEvent {
+                        chat_id: chat.id,
+                        member_ids: chat.member_ids,
+                        event: Some(Event::MessageSent(MessageSent { message: Some(message.clone()) })),
+                    }).await;
+                }
+                Ok(
+                    Response::new(
+                        SendMessageResponse {
+                            message: Some(message)
+                        }
+                    )
                 )
-            ),
-            Err(error) => status::Status::internal(error)
+            }
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn edit_message(&self, request: Request<EditMessageRequest>) -> Result<Response<EditMessageResponse>, Status> {
+        let EditMessageRequest { message_id, text, member_id } = request.into_inner();
+        if message_id.is_empty() || text.is_empty() || member_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["message_id", "text", "member_id"]);
+        }
+
+        match self.interactor.edit_message(&message_id, &member_id, &text).await {
+            Ok(message) => {
+                let message: MessageMessage = message.into();
+                if let Ok(chat) = self.interactor.get_chat_by_id(&message.chat_id, &member_id).await {
+                    self.event_bus.publish(ChatEvent {
+                        chat_id: chat.id,
+                        member_ids: chat.member_ids,
+                        event: Some(Event::MessageEdited(MessageEdited { message: Some(message.clone()) })),
+                    }).await;
+                }
+                Ok(
+                    Response::new(
+                        EditMessageResponse {
+                            message: Some(message)
+                        }
+                    )
+                )
+            }
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn delete_message(&self, request: Request<DeleteMessageRequest>) -> Result<Response<DeleteMessageResponse>, Status> {
-        let DeleteMessageRequest { message_id } = request.into_inner();
-        if message_id.is_empty() {
-            return status::Status::invalid_arguments(vec!["message_id"]);
+        let DeleteMessageRequest { message_id, member_id } = request.into_inner();
+        if message_id.is_empty() || member_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["message_id", "member_id"]);
         }
 
-        match self.interactor.delete_message(&message_id).await {
-            Ok(message_id) => Ok(
-                Response::new(
-                    DeleteMessageResponse { message_id }
+        let chat = match self.interactor.get_message_by_id(&message_id, &member_id).await {
+            Ok(message) => self.interactor.get_chat_by_id(&message.chat_id, &member_id).await.ok(),
+            Err(_) => None
+        };
+
+        match self.interactor.delete_message(&message_id, &member_id).await {
+            Ok(message_id) => {
+                if let Some(chat) = chat {
+                    self.event_bus.publish(ChatEvent {
+                        chat_id: chat.id,
+                        member_ids: chat.member_ids,
+                        event: Some(Event::MessageDeleted(MessageDeleted { message_id: message_id.clone() })),
+                    }).await;
+                }
+                Ok(
+                    Response::new(
+                        DeleteMessageResponse { message_id }
+                    )
                 )
-            ),
-            Err(error) => status::Status::internal(error)
+            }
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn mark_delivered(&self, request: Request<MarkDeliveredRequest>) -> Result<Response<MarkDeliveredResponse>, Status> {
+        let MarkDeliveredRequest { chat_id, member_id, up_to_message_id } = request.into_inner();
+        if chat_id.is_empty() || member_id.is_empty() || up_to_message_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["chat_id", "member_id", "up_to_message_id"]);
+        }
+
+        match self.interactor.mark_delivered(&chat_id, &member_id, &up_to_message_id).await {
+            Ok(message_ids) => {
+                self.publish_status_changed(&chat_id, &member_id, &message_ids, false).await;
+                Ok(
+                    Response::new(
+                        MarkDeliveredResponse { message_ids }
+                    )
+                )
+            }
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn mark_read(&self, request: Request<MarkReadRequest>) -> Result<Response<MarkReadResponse>, Status> {
+        let MarkReadRequest { chat_id, member_id, up_to_message_id } = request.into_inner();
+        if chat_id.is_empty() || member_id.is_empty() || up_to_message_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["chat_id", "member_id", "up_to_message_id"]);
+        }
+
+        match self.interactor.mark_read(&chat_id, &member_id, &up_to_message_id).await {
+            Ok(message_ids) => {
+                self.publish_status_changed(&chat_id, &member_id, &message_ids, true).await;
+                Ok(
+                    Response::new(
+                        MarkReadResponse { message_ids }
+                    )
+                )
+            }
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn subscribe_chat(&self, request: Request<SubscribeChatRequest>) -> Result<Response<Self::SubscribeChatStream>, Status> {
+        let SubscribeChatRequest { chat_id, member_id } = request.into_inner();
+        if chat_id.is_empty() || member_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["chat_id", "member_id"]);
+        }
+
+        match self.interactor.get_chat_by_id(&chat_id, &member_id).await {
+            Ok(chat) => {
+                let is_closing = move |event: &ChatEvent| {
+                    matches!(event.event, Some(Event::MemberBlocked(_))) && event.member_ids.iter().all(|id| chat.member_ids.contains(id))
+                };
+                Ok(Response::new(self.subscribe(move |event| event.chat_id == chat_id, is_closing)))
+            }
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn subscribe_inbox(&self, request: Request<SubscribeInboxRequest>) -> Result<Response<Self::SubscribeInboxStream>, Status> {
+        let SubscribeInboxRequest { member_id } = request.into_inner();
+        if member_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["member_id"]);
+        }
+
+        Ok(Response::new(self.subscribe(move |event| event.member_ids.contains(&member_id), |_| false)))
+    }
+
+    async fn export_data(&self, request: Request<ExportDataRequest>) -> Result<Response<ExportDataResponse>, Status> {
+        let ExportDataRequest { id } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        match self.interactor.export_data(&id).await {
+            Ok(data) => Ok(Response::new(ExportDataResponse { data })),
+            Err(error) => status::Status::from_error(error)
         }
     }
 }
\ No newline at end of file
@@ -23,18 +23,39 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let database = mongodb.database(SERVICE_NAME);
 
     let chats_collection = database.collection::<Chat>(CHATS_COLLECTION);
-    let chat_repository = chat::repository::ChatRepositoryImpl::new(chats_collection);
+    let chat_repository = chat::repository::ChatRepositoryImpl::new(chats_collection.clone());
 
     let messages_collection = database.collection::<Message>(MESSAGES_COLLECTION);
-    let message_repository = message::repository::MessageRepositoryImpl::new(messages_collection);
+    let message_repository = message::repository::MessageRepositoryImpl::new(mongodb.clone(), messages_collection, chats_collection);
 
     let interactor = Arc::new(conversation::interactor::ConversationInteractorImpl::new(chat_repository, message_repository));
-    let service = conversation::service::ConversationServiceImpl::new(Arc::clone(&interactor));
+    let amqp_hostname = cfg.amqp_hostname.unwrap();
+    let amqp_port = cfg.amqp_port.unwrap();
 
-    let message_queue = amqp::MessageQueue::connect(&cfg.amqp_hostname.unwrap(), &cfg.amqp_port.unwrap()).await?;
+    let event_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut event_bus = conversation::event::EventBus::new(event_queue);
+    event_bus.consume_events().await;
+
+    let event_bus = Arc::new(event_bus);
+
+    let service = conversation::service::ConversationServiceImpl::new(Arc::clone(&interactor), Arc::clone(&event_bus));
+
+    let message_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
     let mut message_queue_handler = conversation::amqp::MessageQueueHandler::new(Arc::clone(&interactor), message_queue);
     message_queue_handler.consume_new_chats().await;
 
+    let blocked_users_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut blocked_users_handler = conversation::amqp::MessageQueueHandler::new(Arc::clone(&interactor), blocked_users_queue);
+    blocked_users_handler.consume_blocked_users(Arc::clone(&event_bus)).await;
+
+    let unblocked_users_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut unblocked_users_handler = conversation::amqp::MessageQueueHandler::new(Arc::clone(&interactor), unblocked_users_queue);
+    unblocked_users_handler.consume_unblocked_users().await;
+
+    let deleted_accounts_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut deleted_accounts_handler = conversation::amqp::MessageQueueHandler::new(Arc::clone(&interactor), deleted_accounts_queue);
+    deleted_accounts_handler.consume_deleted_accounts().await;
+
     let server_addr = SocketAddr::new(cfg.service_hostname.unwrap().parse().unwrap(), cfg.service_port.unwrap().parse().unwrap());
 
     println!("Service '{}' started at address: {}", SERVICE_NAME, server_addr);
@@ -24,8 +24,8 @@ impl From<Message> for MessageMessage {
             sender_id: value.sender_id,
             text: if let Some(text) = value.text { text } else { String::new() },
             images: value.images,
-            is_delivered: false,
-            is_read: false,
+            is_delivered: value.is_delivered,
+            is_read: value.is_read,
             sent_at: value.sent_at,
         }
     }
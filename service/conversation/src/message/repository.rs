@@ -1,11 +1,12 @@
 use futures::TryStreamExt;
-use mongodb::bson::doc;
+use mongodb::{bson, Client, Collection};
+use mongodb::bson::{doc, Document};
 use mongodb::bson::oid::ObjectId;
-use mongodb::Collection;
-use mongodb::options::FindOptions;
+use mongodb::options::{FindOneOptions, FindOptions};
 
-use error::make_error;
+use error::DomainError;
 
+use crate::chat::entity::Chat;
 use crate::message::entity::Message;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
@@ -26,16 +27,34 @@ pub trait MessageRepository {
         text: Option<String>,
         images: Vec<Vec<u8>>,
     ) -> Result<Message, Error>;
+    async fn edit_message(&self, message_id: &str, text: &str) -> Result<Message, Error>;
     async fn delete_message(&self, message_id: &str) -> Result<String, Error>;
+    async fn mark_delivered(
+        &self,
+        chat_id: &str,
+        member_id: &str,
+        up_to_message_id: &str,
+    ) -> Result<Vec<String>, Error>;
+    async fn mark_read(
+        &self,
+        chat_id: &str,
+        member_id: &str,
+        up_to_message_id: &str,
+    ) -> Result<Vec<String>, Error>;
+    async fn count_unread(&self, chat_id: &str, member_id: &str) -> Result<u64, Error>;
+    async fn delete_chat_messages(&self, chat_ids: &[String]) -> Result<(), Error>;
+    async fn get_chat_messages(&self, chat_ids: &[String]) -> Result<Vec<Message>, Error>;
 }
 
 pub struct MessageRepositoryImpl {
+    client: Client,
     collection: Collection<Message>,
+    chats_collection: Collection<Chat>,
 }
 
 impl MessageRepositoryImpl {
-    pub fn new(collection: Collection<Message>) -> Box<dyn MessageRepository + Send + Sync> {
-        Box::new(MessageRepositoryImpl { collection })
+    pub fn new(client: Client, collection: Collection<Message>, chats_collection: Collection<Chat>) -> Box<dyn MessageRepository + Send + Sync> {
+        Box::new(MessageRepositoryImpl { client, collection, chats_collection })
     }
 }
 
@@ -53,28 +72,137 @@ impl MessageRepository for MessageRepositoryImpl {
     }
 
     async fn get_message_by_id(&self, message_id: &str) -> Result<Option<Message>, Error> {
-        if let Ok(message) = self.collection.find_one(doc! { "_id": message_id }, None).await {
-            return Ok(message);
-        }
-        Err(make_error!("unable to get message by id"))
+        Ok(self.collection.find_one(doc! { "_id": message_id }, None).await?)
     }
 
     async fn create_message(&self, chat_id: &str, member_id: &str, text: Option<String>, images: Vec<Vec<u8>>) -> Result<Message, Error> {
         let id = ObjectId::new().to_hex();
         let message = Message::new(&id, chat_id, member_id, text, images);
-        let result = self.collection.insert_one(message, None).await?;
-        if let Some(id) = result.inserted_id.as_str() {
-            if let Some(message) = self.get_message_by_id(id).await? {
-                return Ok(message);
+        let mut session = self.client.start_session(None).await?;
+        session.start_transaction(None).await?;
+        let chat = match self.chats_collection.find_one_with_session(doc! { "_id": chat_id }, None, &mut session).await? {
+            Some(chat) => chat,
+            None => return Err(DomainError::not_found("chat not found"))
+        };
+        self.collection.insert_one_with_session(&message, None, &mut session).await?;
+        let update = doc! {
+            "$set": {
+                "last_message": bson::to_bson(&message)?,
+                "updated_at": Chat::timestamp_now() as i64
+            },
+            "$inc": Self::unread_counts_update(&chat, member_id, 1)
+        };
+        self.chats_collection.update_one_with_session(doc! { "_id": chat_id }, update, None, &mut session).await?;
+        session.commit_transaction().await?;
+        Ok(message)
+    }
+
+    async fn edit_message(&self, message_id: &str, text: &str) -> Result<Message, Error> {
+        let mut session = self.client.start_session(None).await?;
+        session.start_transaction(None).await?;
+        let mut message = match self.collection.find_one_with_session(doc! { "_id": message_id }, None, &mut session).await? {
+            Some(message) => message,
+            None => return Err(DomainError::not_found("message not found"))
+        };
+        message.text = Some(String::from(text));
+        self.collection.update_one_with_session(doc! { "_id": message_id }, doc! { "$set": { "text": text } }, None, &mut session).await?;
+        let filter = doc! { "_id": &message.chat_id };
+        self.chats_collection.update_one_with_session(filter, doc! { "$set": { "updated_at": Chat::timestamp_now() as i64 } }, None, &mut session).await?;
+        let filter = doc! { "_id": &message.chat_id, "last_message._id": message_id };
+        self.chats_collection.update_one_with_session(filter, doc! { "$set": { "last_message": bson::to_bson(&message)? } }, None, &mut session).await?;
+        session.commit_transaction().await?;
+        Ok(message)
+    }
+
+    async fn delete_message(&self, message_id: &str) -> Result<String, Error> {
+        let mut session = self.client.start_session(None).await?;
+        session.start_transaction(None).await?;
+        let message = match self.collection.find_one_with_session(doc! { "_id": message_id }, None, &mut session).await? {
+            Some(message) => message,
+            None => return Err(DomainError::not_found("message not found"))
+        };
+        let chat = match self.chats_collection.find_one_with_session(doc! { "_id": &message.chat_id }, None, &mut session).await? {
+            Some(chat) => chat,
+            None => return Err(DomainError::not_found("chat not found"))
+        };
+        self.collection.delete_one_with_session(doc! { "_id": message_id }, None, &mut session).await?;
+        let options = FindOneOptions::builder().sort(doc! { "sent_at": -1 }).build();
+        let last_message = self.collection.find_one_with_session(doc! { "chat_id": &message.chat_id }, options, &mut session).await?;
+        let mut update = doc! {
+            "$set": {
+                "last_message": bson::to_bson(&last_message)?,
+                "updated_at": Chat::timestamp_now() as i64
             }
+        };
+        if !message.is_read {
+            update.insert("$inc", Self::unread_counts_update(&chat, &message.sender_id, -1));
         }
-        Err(make_error!("unable to create message"))
+        self.chats_collection.update_one_with_session(doc! { "_id": &chat.id }, update, None, &mut session).await?;
+        session.commit_transaction().await?;
+        Ok(String::from(message_id))
     }
 
-    async fn delete_message(&self, message_id: &str) -> Result<String, Error> {
-        if self.collection.delete_one(doc! { "_id": message_id }, None).await?.deleted_count > 0 {
-            return Ok(String::from(message_id));
+    async fn mark_delivered(&self, chat_id: &str, member_id: &str, up_to_message_id: &str) -> Result<Vec<String>, Error> {
+        self.mark_messages(chat_id, member_id, up_to_message_id, "is_delivered", doc! { "is_delivered": true }).await
+    }
+
+    async fn mark_read(&self, chat_id: &str, member_id: &str, up_to_message_id: &str) -> Result<Vec<String>, Error> {
+        self.mark_messages(chat_id, member_id, up_to_message_id, "is_read", doc! { "is_delivered": true, "is_read": true }).await
+    }
+
+    async fn count_unread(&self, chat_id: &str, member_id: &str) -> Result<u64, Error> {
+        let filter = doc! {
+            "chat_id": chat_id,
+            "sender_id": { "$ne": member_id },
+            "is_read": false
+        };
+        Ok(self.collection.count_documents(filter, None).await?)
+    }
+
+    async fn delete_chat_messages(&self, chat_ids: &[String]) -> Result<(), Error> {
+        self.collection.delete_many(doc! { "chat_id": { "$in": chat_ids } }, None).await?;
+        Ok(())
+    }
+
+    async fn get_chat_messages(&self, chat_ids: &[String]) -> Result<Vec<Message>, Error> {
+        let mut messages: Vec<Message> = vec![];
+        let options = FindOptions::builder().sort(doc! { "sent_at": 1 }).build();
+        let mut cursor = self.collection.find(doc! { "chat_id": { "$in": chat_ids } }, options).await?;
+        while let Some(message) = cursor.try_next().await? {
+            messages.push(message)
+        }
+        Ok(messages)
+    }
+}
+
+impl MessageRepositoryImpl {
+    fn unread_counts_update(chat: &Chat, sender_id: &str, value: i64) -> Document {
+        let mut document = doc! {};
+        for member_id in chat.member_ids.iter().filter(|member_id| *member_id != sender_id) {
+            document.insert(format!("unread_counts.{}", member_id), value);
+        }
+        document
+    }
+
+    async fn mark_messages(&self, chat_id: &str, member_id: &str, up_to_message_id: &str, flag: &str, update: Document) -> Result<Vec<String>, Error> {
+        let up_to_message = match self.get_message_by_id(up_to_message_id).await? {
+            Some(message) if message.chat_id == chat_id => message,
+            _ => return Err(DomainError::not_found("message not found"))
+        };
+        let filter = doc! {
+            "chat_id": chat_id,
+            "sender_id": { "$ne": member_id },
+            "sent_at": { "$lte": up_to_message.sent_at as i64 },
+            flag: false
+        };
+        let mut message_ids: Vec<String> = vec![];
+        let mut cursor = self.collection.find(filter, None).await?;
+        while let Some(message) = cursor.try_next().await? {
+            message_ids.push(message.id)
+        }
+        if !message_ids.is_empty() {
+            self.collection.update_many(doc! { "_id": { "$in": &message_ids } }, doc! { "$set": update }, None).await?;
         }
-        Err(make_error!("unable to delete message"))
+        Ok(message_ids)
     }
 }
\ No newline at end of file
@@ -6,13 +6,15 @@ fn main() {
     }
     tonic_build::configure()
         .compile(&[
+            "../../proto/account.proto",
             "../../proto/authentication.proto",
             "../../proto/conversation.proto",
             "../../proto/matchmaking.proto",
             "../../proto/profile.proto",
             "../../proto/recommendation.proto",
             "../../proto/safety.proto",
-            "../../proto/support.proto"
+            "../../proto/support.proto",
+            "../../proto/token.proto"
         ], &["../../proto"])
         .expect("unable to run protoc");
 }
\ No newline at end of file
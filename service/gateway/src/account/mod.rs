@@ -0,0 +1,5 @@
+pub mod service;
+
+pub mod pb {
+    tonic::include_proto!("account");
+}
\ No newline at end of file
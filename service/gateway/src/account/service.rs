@@ -0,0 +1,117 @@
+use tonic::{Request, Response, Status};
+use tonic::transport::Channel;
+
+use crate::interceptor::authorization::bind_identity;
+
+use crate::account::pb::{BanAccountRequest, BanAccountResponse, ChangeRoleRequest, ChangeRoleResponse, ConsumeRecoveryCodeRequest, ConsumeRecoveryCodeResponse, CreateAccountRequest, CreateAccountResponse, DeleteAccountRequest, DeleteAccountResponse, DownloadExportRequest, DownloadExportResponse, ExportMyDataRequest, ExportMyDataResponse, ExtendPremiumRequest, ExtendPremiumResponse, GetAccountByCredentialsRequest, GetAccountByCredentialsResponse, GetAccountByEmailRequest, GetAccountByEmailResponse, GetAccountByIdRequest, GetAccountByIdResponse, GetDeletionStatusRequest, GetDeletionStatusResponse, GetEntitlementsRequest, GetEntitlementsResponse, GetExportRequest, GetExportResponse, GrantPremiumRequest, GrantPremiumResponse, ReinstateAccountRequest, ReinstateAccountResponse, RevokePremiumRequest, RevokePremiumResponse, Role, SuspendAccountRequest, SuspendAccountResponse, UpdateAccountRequest, UpdateAccountResponse, UpdateTotpRequest, UpdateTotpResponse, VerifyEmailRequest, VerifyEmailResponse};
+use crate::account::pb::account_service_client::AccountServiceClient;
+use crate::account::pb::account_service_server::AccountService;
+
+const INTERNAL_METHOD: &str = "method is not exposed by the gateway";
+
+pub struct AccountServiceImpl {
+    client: AccountServiceClient<Channel>,
+}
+
+impl AccountServiceImpl {
+    pub fn new(client: AccountServiceClient<Channel>) -> impl AccountService {
+        AccountServiceImpl { client }
+    }
+}
+
+#[tonic::async_trait]
+impl AccountService for AccountServiceImpl {
+    async fn get_account_by_id(&self, _: Request<GetAccountByIdRequest>) -> Result<Response<GetAccountByIdResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
+
+    async fn get_account_by_email(&self, _: Request<GetAccountByEmailRequest>) -> Result<Response<GetAccountByEmailResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
+
+    async fn get_account_by_credentials(&self, _: Request<GetAccountByCredentialsRequest>) -> Result<Response<GetAccountByCredentialsResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
+
+    async fn create_account(&self, _: Request<CreateAccountRequest>) -> Result<Response<CreateAccountResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
+
+    async fn update_account(&self, _: Request<UpdateAccountRequest>) -> Result<Response<UpdateAccountResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
+
+    async fn verify_email(&self, _: Request<VerifyEmailRequest>) -> Result<Response<VerifyEmailResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
+
+    async fn update_totp(&self, _: Request<UpdateTotpRequest>) -> Result<Response<UpdateTotpResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
+
+    async fn consume_recovery_code(&self, _: Request<ConsumeRecoveryCodeRequest>) -> Result<Response<ConsumeRecoveryCodeResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
+
+    async fn grant_premium(&self, request: Request<GrantPremiumRequest>) -> Result<Response<GrantPremiumResponse>, Status> {
+        self.client.clone().grant_premium(request).await
+    }
+
+    async fn extend_premium(&self, request: Request<ExtendPremiumRequest>) -> Result<Response<ExtendPremiumResponse>, Status> {
+        self.client.clone().extend_premium(request).await
+    }
+
+    async fn revoke_premium(&self, request: Request<RevokePremiumRequest>) -> Result<Response<RevokePremiumResponse>, Status> {
+        self.client.clone().revoke_premium(request).await
+    }
+
+    async fn get_entitlements(&self, mut request: Request<GetEntitlementsRequest>) -> Result<Response<GetEntitlementsResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.id)?;
+        self.client.clone().get_entitlements(request).await
+    }
+
+    async fn suspend_account(&self, request: Request<SuspendAccountRequest>) -> Result<Response<SuspendAccountResponse>, Status> {
+        self.client.clone().suspend_account(request).await
+    }
+
+    async fn ban_account(&self, request: Request<BanAccountRequest>) -> Result<Response<BanAccountResponse>, Status> {
+        self.client.clone().ban_account(request).await
+    }
+
+    async fn reinstate_account(&self, request: Request<ReinstateAccountRequest>) -> Result<Response<ReinstateAccountResponse>, Status> {
+        self.client.clone().reinstate_account(request).await
+    }
+
+    async fn change_role(&self, mut request: Request<ChangeRoleRequest>) -> Result<Response<ChangeRoleResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.actor_id)?;
+        if !matches!(Role::try_from(request.get_ref().role), Ok(Role::Moderator | Role::User)) {
+            return Err(Status::invalid_argument("Role is unspecified"));
+        }
+        self.client.clone().change_role(request).await
+    }
+
+    async fn delete_account(&self, mut request: Request<DeleteAccountRequest>) -> Result<Response<DeleteAccountResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.id)?;
+        self.client.clone().delete_account(request).await
+    }
+
+    async fn get_deletion_status(&self, mut request: Request<GetDeletionStatusRequest>) -> Result<Response<GetDeletionStatusResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.id)?;
+        self.client.clone().get_deletion_status(request).await
+    }
+
+    async fn export_my_data(&self, mut request: Request<ExportMyDataRequest>) -> Result<Response<ExportMyDataResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.id)?;
+        self.client.clone().export_my_data(request).await
+    }
+
+    async fn get_export(&self, mut request: Request<GetExportRequest>) -> Result<Response<GetExportResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.id)?;
+        self.client.clone().get_export(request).await
+    }
+
+    async fn download_export(&self, mut request: Request<DownloadExportRequest>) -> Result<Response<DownloadExportResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.id)?;
+        self.client.clone().download_export(request).await
+    }
+}
\ No newline at end of file
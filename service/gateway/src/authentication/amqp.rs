@@ -0,0 +1,83 @@
+use std::str::from_utf8;
+use std::sync::Arc;
+
+use amqp::MessageQueue;
+
+use crate::authentication::denylist::Denylist;
+
+const EXCHANGE_NAME: &str = "action";
+const EXCHANGE_TYPE: &str = "direct";
+const TOKEN_INVALIDATED_ROUTING_KEY: &str = "token.invalidated";
+const SUBJECT_REVOKED_ROUTING_KEY: &str = "subject.revoked";
+const SESSION_REVOKED_ROUTING_KEY: &str = "session.revoked";
+
+pub struct MessageQueueHandler {
+    denylist: Arc<Denylist>,
+    message_queue: MessageQueue,
+}
+
+impl MessageQueueHandler {
+    pub fn new(denylist: Arc<Denylist>, message_queue: MessageQueue) -> Self {
+        Self {
+            denylist,
+            message_queue,
+        }
+    }
+
+    pub async fn consume_invalidated_tokens(&mut self) {
+        if let Ok(queue_name) = self.message_queue.bind_exclusive_queue(EXCHANGE_NAME, EXCHANGE_TYPE, TOKEN_INVALIDATED_ROUTING_KEY).await {
+            let denylist = Arc::clone(&self.denylist);
+            let handle_message = move |msg: &[u8]| {
+                if let Ok(value) = from_utf8(msg) {
+                    let values: Vec<&str> = value.split(",").collect();
+                    if let [jti, until] = values[..] {
+                        if let Ok(until) = until.parse::<u64>() {
+                            denylist.invalidate_token(jti, until);
+                        }
+                    }
+                }
+                Ok(())
+            };
+
+            let _ = self.message_queue.start_consuming(&queue_name, Box::new(handle_message)).await;
+        }
+    }
+
+    pub async fn consume_revoked_subjects(&mut self) {
+        if let Ok(queue_name) = self.message_queue.bind_exclusive_queue(EXCHANGE_NAME, EXCHANGE_TYPE, SUBJECT_REVOKED_ROUTING_KEY).await {
+            let denylist = Arc::clone(&self.denylist);
+            let handle_message = move |msg: &[u8]| {
+                if let Ok(value) = from_utf8(msg) {
+                    let values: Vec<&str> = value.split(",").collect();
+                    if let [subject, revoked_at, until] = values[..] {
+                        if let (Ok(revoked_at), Ok(until)) = (revoked_at.parse::<u64>(), until.parse::<u64>()) {
+                            denylist.revoke_subject(subject, revoked_at, until);
+                        }
+                    }
+                }
+                Ok(())
+            };
+
+            let _ = self.message_queue.start_consuming(&queue_name, Box::new(handle_message)).await;
+        }
+    }
+
+    pub async fn consume_revoked_sessions(&mut self) {
+        if let Ok(queue_name) = self.message_queue.bind_exclusive_queue(EXCHANGE_NAME, EXCHANGE_TYPE, SESSION_REVOKED_ROUTING_KEY).await {
+            let denylist = Arc::clone(&self.denylist);
+            let handle_message = move |msg: &[u8]| {
+                if let Ok(value) = from_utf8(msg) {
+                    let values: Vec<&str> = value.split(",").collect();
+                    if let [session_id, until] = values[..] {
+                        if let Ok(until) = until.parse::<u64>() {
+                            denylist.revoke_session(session_id, until);
+                        }
+                    }
+                }
+                Ok(())
+            };
+
+            let _ = self.message_queue.start_consuming(&queue_name, Box::new(handle_message)).await;
+        }
+    }
+}
\ No newline at end of file
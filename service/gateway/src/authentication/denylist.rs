@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::authentication::entity::Claims;
+
+#[derive(Default)]
+pub struct Denylist {
+    tokens: RwLock<HashMap<String, u64>>,
+    subjects: RwLock<HashMap<String, (u64, u64)>>,
+    sessions: RwLock<HashMap<String, u64>>,
+}
+
+impl Denylist {
+    fn timestamp_now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default()
+    }
+
+    pub fn invalidate_token(&self, jti: &str, until: u64) {
+        let now = Self::timestamp_now();
+        if let Ok(mut tokens) = self.tokens.write() {
+            tokens.retain(|_, until| now < *until);
+            tokens.insert(String::from(jti), until);
+        }
+    }
+
+    pub fn revoke_subject(&self, subject: &str, revoked_at: u64, until: u64) {
+        let now = Self::timestamp_now();
+        if let Ok(mut subjects) = self.subjects.write() {
+            subjects.retain(|_, (_, until)| now < *until);
+            subjects.insert(String::from(subject), (revoked_at, until));
+        }
+    }
+
+    pub fn revoke_session(&self, session_id: &str, until: u64) {
+        let now = Self::timestamp_now();
+        if let Ok(mut sessions) = self.sessions.write() {
+            sessions.retain(|_, until| now < *until);
+            sessions.insert(String::from(session_id), until);
+        }
+    }
+
+    pub fn is_denied(&self, claims: &Claims) -> bool {
+        let is_token_invalidated = self.tokens.read().map_or(true, |tokens| tokens.contains_key(&claims.jti));
+        let is_subject_revoked = self.subjects.read().map_or(true, |subjects| {
+            subjects.get(&claims.sub).is_some_and(|(revoked_at, _)| claims.iat <= *revoked_at)
+        });
+        let is_session_revoked = claims.sid.as_ref().is_some_and(|session_id| {
+            self.sessions.read().map_or(true, |sessions| sessions.contains_key(session_id))
+        });
+        is_token_invalidated || is_subject_revoked || is_session_revoked
+    }
+}
\ No newline at end of file
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub enum Role {
+    User,
+    Moderator,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum Entitlement {
+    Backtrack,
+    SeeWhoLikedYou,
+    UnlimitedLikes,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TokenPayload {
+    pub account_id: String,
+    pub role: Role,
+    #[serde(default)]
+    pub entitlements: Vec<Entitlement>,
+    #[serde(default)]
+    pub email_verified: bool,
+    #[serde(default)]
+    pub totp_enabled: bool,
+}
+
+impl TokenPayload {
+    pub fn from_str(s: &str) -> Result<TokenPayload, Error> {
+        serde_json::from_str(s).map_err(|e| Box::new(e) as Error)
+    }
+
+    pub fn from_claims(claims: Claims) -> Result<TokenPayload, Error> {
+        serde_json::from_value(Value::Object(claims.payload)).map_err(|e| Box::new(e) as Error)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TokenHeader {
+    pub alg: String,
+    pub typ: String,
+    pub kid: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Claims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub iat: u64,
+    pub nbf: u64,
+    pub exp: u64,
+    pub jti: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+    #[serde(flatten)]
+    pub payload: Map<String, Value>,
+}
\ No newline at end of file
@@ -1,10 +1,11 @@
+use crate::authentication::entity::TokenPayload;
 use crate::authentication::repository::AuthenticationRepository;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
 #[async_trait::async_trait]
 pub trait AuthenticationInteractor {
-    async fn is_token_valid(&self, access_token: &str) -> Result<bool, Error>;
+    async fn validate_token(&self, access_token: &str) -> Result<TokenPayload, Error>;
 }
 
 pub struct AuthenticationInteractorImpl {
@@ -19,7 +20,7 @@ impl AuthenticationInteractorImpl {
 
 #[async_trait::async_trait]
 impl AuthenticationInteractor for AuthenticationInteractorImpl {
-    async fn is_token_valid(&self, access_token: &str) -> Result<bool, Error> {
-        self.repository.is_token_valid(access_token).await
+    async fn validate_token(&self, access_token: &str) -> Result<TokenPayload, Error> {
+        self.repository.validate_token(access_token).await
     }
 }
\ No newline at end of file
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use tonic::Request;
+
+use error::make_error;
+
+use crate::authentication::entity::{Claims, TokenHeader};
+use crate::token::api::TokenApi;
+use crate::token::pb::{GetPublicKeysRequest, GetPublicKeysResponse, PublicKey};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+const ALGORITHM: &str = "EdDSA";
+const KEY_TYPE: &str = "OKP";
+const CURVE: &str = "Ed25519";
+const KEYSET_EXPIRATION_MILLIS: u64 = 1000 * 60 * 5;
+const KEYSET_REFRESH_COOLDOWN_MILLIS: u64 = 1000 * 10;
+
+#[async_trait::async_trait]
+pub trait Keyset {
+    async fn verify(&self, token: &str) -> Result<Option<Claims>, Error>;
+}
+
+pub struct KeysetImpl {
+    api: Box<dyn TokenApi + Send + Sync>,
+    keys: RwLock<HashMap<String, VerifyingKey>>,
+    refreshed_at: RwLock<u64>,
+}
+
+impl KeysetImpl {
+    fn timestamp_now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_millis() as u64).unwrap_or_default()
+    }
+
+    fn get_key(&self, kid: &str) -> Option<VerifyingKey> {
+        self.keys.read().ok().and_then(|keys| keys.get(kid).cloned())
+    }
+
+    async fn refresh(&self) -> Result<(), Error> {
+        let GetPublicKeysResponse { keys } = self.api.get_public_keys(Request::new(GetPublicKeysRequest {})).await?.into_inner();
+        let mut verifying_keys = HashMap::new();
+        for PublicKey { kid, kty, crv, alg, x } in keys {
+            if kty == KEY_TYPE && crv == CURVE && alg == ALGORITHM {
+                let public_key: [u8; 32] = URL_SAFE_NO_PAD.decode(&x)?.try_into().map_err(|_| make_error!("invalid verifying key"))?;
+                verifying_keys.insert(kid, VerifyingKey::from_bytes(&public_key)?);
+            }
+        }
+        if let Ok(mut keys) = self.keys.write() {
+            *keys = verifying_keys;
+        }
+        Ok(())
+    }
+
+    async fn find_key(&self, kid: &str) -> Option<VerifyingKey> {
+        let now = Self::timestamp_now();
+        let key = self.get_key(kid);
+        let refreshed_at = self.refreshed_at.read().map(|refreshed_at| *refreshed_at).unwrap_or_default();
+        let is_expired = now >= refreshed_at + KEYSET_EXPIRATION_MILLIS;
+        let is_refreshable = key.is_none() && now >= refreshed_at + KEYSET_REFRESH_COOLDOWN_MILLIS;
+        if is_expired || is_refreshable {
+            if let Ok(mut refreshed_at) = self.refreshed_at.write() {
+                *refreshed_at = now;
+            }
+            if self.refresh().await.is_ok() {
+                return self.get_key(kid);
+            }
+        }
+        key
+    }
+
+    pub fn new(api: Box<dyn TokenApi + Send + Sync>) -> Box<dyn Keyset + Send + Sync> {
+        Box::new(KeysetImpl { api, keys: RwLock::new(HashMap::new()), refreshed_at: RwLock::new(0) })
+    }
+}
+
+#[async_trait::async_trait]
+impl Keyset for KeysetImpl {
+    async fn verify(&self, token: &str) -> Result<Option<Claims>, Error> {
+        let parts: Vec<&str> = token.split('.').collect();
+        if let [header, claims, signature] = parts[..] {
+            let TokenHeader { alg, kid, .. } = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header)?)?;
+            if alg == ALGORITHM {
+                return match self.find_key(&kid).await {
+                    Some(key) => {
+                        let signature = Signature::from_slice(&URL_SAFE_NO_PAD.decode(signature)?)?;
+                        key.verify(format!("{}.{}", header, claims).as_bytes(), &signature)?;
+                        Ok(Some(serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims)?)?))
+                    }
+                    None => Ok(None)
+                };
+            }
+        }
+        Err(make_error!("invalid token"))
+    }
+}
\ No newline at end of file
@@ -1,4 +1,8 @@
+pub mod amqp;
+pub mod denylist;
+pub mod entity;
 pub mod interactor;
+pub mod keyset;
 pub mod repository;
 pub mod api;
 pub mod service;
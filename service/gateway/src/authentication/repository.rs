@@ -1,33 +1,72 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use tonic::Request;
 
+use error::make_error;
+
 use crate::authentication::api::AuthenticationApi;
-use crate::authentication::pb::ValidateTokenRequest;
+use crate::authentication::denylist::Denylist;
+use crate::authentication::entity::{Claims, TokenPayload};
+use crate::authentication::keyset::Keyset;
+use crate::authentication::pb::{ValidateTokenRequest, ValidateTokenResponse};
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
+const ISSUER: &str = "dddating";
+const ACCESS_TOKEN_AUDIENCE: &str = "access";
+
 #[async_trait::async_trait]
 pub trait AuthenticationRepository {
-    async fn is_token_valid(&self, access_token: &str) -> Result<bool, Error>;
+    async fn validate_token(&self, access_token: &str) -> Result<TokenPayload, Error>;
 }
 
 pub struct AuthenticationRepositoryImpl {
     service: Box<dyn AuthenticationApi + Send + Sync>,
+    keyset: Box<dyn Keyset + Send + Sync>,
+    denylist: Arc<Denylist>,
+    started_at: u64,
 }
 
 impl AuthenticationRepositoryImpl {
-    pub fn new(service: Box<dyn AuthenticationApi + Send + Sync>) -> Box<dyn AuthenticationRepository + Send + Sync> {
-        Box::new(AuthenticationRepositoryImpl { service })
+    fn timestamp_now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default()
+    }
+
+    async fn validate_token_remotely(&self, access_token: &str) -> Result<TokenPayload, Error> {
+        let ValidateTokenResponse { payload } = self.service.validate_token(Request::new(
+            ValidateTokenRequest {
+                access_token: String::from(access_token)
+            }
+        )).await?.into_inner();
+        TokenPayload::from_str(&payload)
+    }
+
+    fn check_claims(&self, claims: &Claims) -> Result<bool, Error> {
+        let now = Self::timestamp_now();
+        if claims.iss == ISSUER && claims.aud == ACCESS_TOKEN_AUDIENCE && claims.nbf <= now && now < claims.exp {
+            return Ok(claims.iat > self.started_at);
+        }
+        Err(make_error!("invalid token"))
+    }
+
+    pub fn new(service: Box<dyn AuthenticationApi + Send + Sync>, keyset: Box<dyn Keyset + Send + Sync>, denylist: Arc<Denylist>) -> Box<dyn AuthenticationRepository + Send + Sync> {
+        Box::new(AuthenticationRepositoryImpl { service, keyset, denylist, started_at: Self::timestamp_now() })
     }
 }
 
 #[async_trait::async_trait]
 impl AuthenticationRepository for AuthenticationRepositoryImpl {
-    async fn is_token_valid(&self, access_token: &str) -> Result<bool, Error> {
-        self.service.validate_token(Request::new(
-            ValidateTokenRequest {
-                access_token: String::from(access_token)
+    async fn validate_token(&self, access_token: &str) -> Result<TokenPayload, Error> {
+        if let Some(claims) = self.keyset.verify(access_token).await? {
+            if !self.check_claims(&claims)? {
+                return self.validate_token_remotely(access_token).await;
+            }
+            if self.denylist.is_denied(&claims) {
+                return Err(make_error!("invalid token"));
             }
-        )).await?;
-        Ok(true)
+            return TokenPayload::from_claims(claims);
+        }
+        self.validate_token_remotely(access_token).await
     }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use tonic::{Request, Response, Status};
 use tonic::transport::Channel;
 
-use crate::authentication::pb::{ChangePasswordRequest, ChangePasswordResponse, RefreshTokenRequest, RefreshTokenResponse, SignInRequest, SignInResponse, SignOutRequest, SignOutResponse, SignUpRequest, SignUpResponse, ValidateTokenRequest, ValidateTokenResponse};
+use crate::authentication::pb::{ChangeEmailRequest, ChangeEmailResponse, ChangePasswordRequest, ChangePasswordResponse, CompleteSignInRequest, CompleteSignInResponse, ConfirmPasswordResetRequest, ConfirmPasswordResetResponse, ConfirmTotpRequest, ConfirmTotpResponse, EnrollTotpRequest, EnrollTotpResponse, ListSessionsRequest, ListSessionsResponse, RefreshTokenRequest, RefreshTokenResponse, RequestPasswordResetRequest, RequestPasswordResetResponse, ResendVerificationRequest, ResendVerificationResponse, RevokeAllSessionsRequest, RevokeAllSessionsResponse, RevokeSessionRequest, RevokeSessionResponse, SignInRequest, SignInResponse, SignOutRequest, SignOutResponse, SignUpRequest, SignUpResponse, ValidateTokenRequest, ValidateTokenResponse, VerifyEmailRequest, VerifyEmailResponse};
 use crate::authentication::pb::authentication_service_client::AuthenticationServiceClient;
 use crate::authentication::pb::authentication_service_server::AuthenticationService;
 
@@ -13,16 +13,43 @@ impl AuthenticationServiceImpl {
     pub fn new(client: AuthenticationServiceClient<Channel>) -> impl AuthenticationService {
         AuthenticationServiceImpl { client }
     }
+
+    fn with_client_metadata<T>(mut request: Request<T>) -> Request<T> {
+        let ip_address = request.remote_addr().map(|addr| addr.ip().to_string());
+        let user_agent = request.metadata().get("user-agent").cloned();
+        let metadata = request.metadata_mut();
+        metadata.remove("x-client-ip");
+        metadata.remove("x-client-user-agent");
+        if let Some(ip_address) = ip_address.and_then(|ip_address| ip_address.parse().ok()) {
+            metadata.insert("x-client-ip", ip_address);
+        }
+        if let Some(user_agent) = user_agent {
+            metadata.insert("x-client-user-agent", user_agent);
+        }
+        request
+    }
 }
 
 #[tonic::async_trait]
 impl AuthenticationService for AuthenticationServiceImpl {
     async fn sign_up(&self, request: Request<SignUpRequest>) -> Result<Response<SignUpResponse>, Status> {
-        self.client.clone().sign_up(request).await
+        self.client.clone().sign_up(Self::with_client_metadata(request)).await
     }
 
     async fn sign_in(&self, request: Request<SignInRequest>) -> Result<Response<SignInResponse>, Status> {
-        self.client.clone().sign_in(request).await
+        self.client.clone().sign_in(Self::with_client_metadata(request)).await
+    }
+
+    async fn complete_sign_in(&self, request: Request<CompleteSignInRequest>) -> Result<Response<CompleteSignInResponse>, Status> {
+        self.client.clone().complete_sign_in(request).await
+    }
+
+    async fn enroll_totp(&self, request: Request<EnrollTotpRequest>) -> Result<Response<EnrollTotpResponse>, Status> {
+        self.client.clone().enroll_totp(request).await
+    }
+
+    async fn confirm_totp(&self, request: Request<ConfirmTotpRequest>) -> Result<Response<ConfirmTotpResponse>, Status> {
+        self.client.clone().confirm_totp(request).await
     }
 
     async fn sign_out(&self, request: Request<SignOutRequest>) -> Result<Response<SignOutResponse>, Status> {
@@ -33,6 +60,26 @@ impl AuthenticationService for AuthenticationServiceImpl {
         self.client.clone().change_password(request).await
     }
 
+    async fn change_email(&self, request: Request<ChangeEmailRequest>) -> Result<Response<ChangeEmailResponse>, Status> {
+        self.client.clone().change_email(request).await
+    }
+
+    async fn verify_email(&self, request: Request<VerifyEmailRequest>) -> Result<Response<VerifyEmailResponse>, Status> {
+        self.client.clone().verify_email(request).await
+    }
+
+    async fn resend_verification(&self, request: Request<ResendVerificationRequest>) -> Result<Response<ResendVerificationResponse>, Status> {
+        self.client.clone().resend_verification(request).await
+    }
+
+    async fn request_password_reset(&self, request: Request<RequestPasswordResetRequest>) -> Result<Response<RequestPasswordResetResponse>, Status> {
+        self.client.clone().request_password_reset(request).await
+    }
+
+    async fn confirm_password_reset(&self, request: Request<ConfirmPasswordResetRequest>) -> Result<Response<ConfirmPasswordResetResponse>, Status> {
+        self.client.clone().confirm_password_reset(request).await
+    }
+
     async fn refresh_token(&self, request: Request<RefreshTokenRequest>) -> Result<Response<RefreshTokenResponse>, Status> {
         self.client.clone().refresh_token(request).await
     }
@@ -40,4 +87,15 @@ impl AuthenticationService for AuthenticationServiceImpl {
     async fn validate_token(&self, request: Request<ValidateTokenRequest>) -> Result<Response<ValidateTokenResponse>, Status> {
         self.client.clone().validate_token(request).await
     }
+    async fn list_sessions(&self, request: Request<ListSessionsRequest>) -> Result<Response<ListSessionsResponse>, Status> {
+        self.client.clone().list_sessions(request).await
+    }
+
+    async fn revoke_session(&self, request: Request<RevokeSessionRequest>) -> Result<Response<RevokeSessionResponse>, Status> {
+        self.client.clone().revoke_session(request).await
+    }
+
+    async fn revoke_all_sessions(&self, request: Request<RevokeAllSessionsRequest>) -> Result<Response<RevokeAllSessionsResponse>, Status> {
+        self.client.clone().revoke_all_sessions(request).await
+    }
 }
\ No newline at end of file
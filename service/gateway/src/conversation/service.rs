@@ -1,10 +1,13 @@
-use tonic::{Request, Response, Status};
+use tonic::{Request, Response, Status, Streaming};
 use tonic::transport::Channel;
 
-use crate::conversation::pb::{DeleteChatRequest, DeleteChatResponse, DeleteMessageRequest, DeleteMessageResponse, GetChatByIdRequest, GetChatByIdResponse, GetChatsRequest, GetChatsResponse, GetMessageByIdRequest, GetMessageByIdResponse, GetMessagesRequest, GetMessagesResponse, SendMessageRequest, SendMessageResponse, UpdateChatRequest, UpdateChatResponse};
+use crate::interceptor::authorization::bind_identity;
+use crate::conversation::pb::{ChatEvent, DeleteChatRequest, DeleteChatResponse, DeleteMessageRequest, DeleteMessageResponse, EditMessageRequest, EditMessageResponse, ExportDataRequest, ExportDataResponse, GetChatByIdRequest, GetChatByIdResponse, GetChatsRequest, GetChatsResponse, GetMessageByIdRequest, GetMessageByIdResponse, GetMessagesRequest, GetMessagesResponse, MarkDeliveredRequest, MarkDeliveredResponse, MarkReadRequest, MarkReadResponse, SendMessageRequest, SendMessageResponse, SubscribeChatRequest, SubscribeInboxRequest, UpdateChatRequest, UpdateChatResponse};
 use crate::conversation::pb::conversation_service_client::ConversationServiceClient;
 use crate::conversation::pb::conversation_service_server::ConversationService;
 
+const INTERNAL_METHOD: &str = "method is not exposed by the gateway";
+
 pub struct ConversationServiceImpl {
     client: ConversationServiceClient<Channel>,
 }
@@ -17,35 +20,75 @@ impl ConversationServiceImpl {
 
 #[tonic::async_trait]
 impl ConversationService for ConversationServiceImpl {
-    async fn get_chats(&self, request: Request<GetChatsRequest>) -> Result<Response<GetChatsResponse>, Status> {
+    type SubscribeChatStream = Streaming<ChatEvent>;
+    type SubscribeInboxStream = Streaming<ChatEvent>;
+
+    async fn get_chats(&self, mut request: Request<GetChatsRequest>) -> Result<Response<GetChatsResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
         self.client.clone().get_chats(request).await
     }
 
-    async fn get_chat_by_id(&self, request: Request<GetChatByIdRequest>) -> Result<Response<GetChatByIdResponse>, Status> {
+    async fn get_chat_by_id(&self, mut request: Request<GetChatByIdRequest>) -> Result<Response<GetChatByIdResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
         self.client.clone().get_chat_by_id(request).await
     }
 
-    async fn update_chat(&self, request: Request<UpdateChatRequest>) -> Result<Response<UpdateChatResponse>, Status> {
+    async fn update_chat(&self, mut request: Request<UpdateChatRequest>) -> Result<Response<UpdateChatResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
         self.client.clone().update_chat(request).await
     }
 
-    async fn delete_chat(&self, request: Request<DeleteChatRequest>) -> Result<Response<DeleteChatResponse>, Status> {
+    async fn delete_chat(&self, mut request: Request<DeleteChatRequest>) -> Result<Response<DeleteChatResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
         self.client.clone().delete_chat(request).await
     }
 
-    async fn get_messages(&self, request: Request<GetMessagesRequest>) -> Result<Response<GetMessagesResponse>, Status> {
+    async fn get_messages(&self, mut request: Request<GetMessagesRequest>) -> Result<Response<GetMessagesResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
         self.client.clone().get_messages(request).await
     }
 
-    async fn get_message_by_id(&self, request: Request<GetMessageByIdRequest>) -> Result<Response<GetMessageByIdResponse>, Status> {
+    async fn get_message_by_id(&self, mut request: Request<GetMessageByIdRequest>) -> Result<Response<GetMessageByIdResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
         self.client.clone().get_message_by_id(request).await
     }
 
-    async fn send_message(&self, request: Request<SendMessageRequest>) -> Result<Response<SendMessageResponse>, Status> {
+    async fn send_message(&self, mut request: Request<SendMessageRequest>) -> Result<Response<SendMessageResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
         self.client.clone().send_message(request).await
     }
 
-    async fn delete_message(&self, request: Request<DeleteMessageRequest>) -> Result<Response<DeleteMessageResponse>, Status> {
+    async fn edit_message(&self, mut request: Request<EditMessageRequest>) -> Result<Response<EditMessageResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
+        self.client.clone().edit_message(request).await
+    }
+
+    async fn delete_message(&self, mut request: Request<DeleteMessageRequest>) -> Result<Response<DeleteMessageResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
         self.client.clone().delete_message(request).await
     }
+
+    async fn mark_delivered(&self, mut request: Request<MarkDeliveredRequest>) -> Result<Response<MarkDeliveredResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
+        self.client.clone().mark_delivered(request).await
+    }
+
+    async fn mark_read(&self, mut request: Request<MarkReadRequest>) -> Result<Response<MarkReadResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
+        self.client.clone().mark_read(request).await
+    }
+
+    async fn subscribe_chat(&self, mut request: Request<SubscribeChatRequest>) -> Result<Response<Self::SubscribeChatStream>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
+        self.client.clone().subscribe_chat(request).await
+    }
+
+    async fn subscribe_inbox(&self, mut request: Request<SubscribeInboxRequest>) -> Result<Response<Self::SubscribeInboxStream>, Status> {
+        bind_identity(&mut request, |message| &mut message.member_id)?;
+        self.client.clone().subscribe_inbox(request).await
+    }
+
+    async fn export_data(&self, _: Request<ExportDataRequest>) -> Result<Response<ExportDataResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
 }
\ No newline at end of file
@@ -3,10 +3,17 @@ use std::pin::Pin;
 use std::sync::Arc;
 
 use tonic::{Request, Status};
+use tonic::metadata::MetadataValue;
 use tonic_async_interceptor::{async_interceptor, AsyncInterceptedService, AsyncInterceptor};
 use tower::ServiceBuilder;
 
+use crate::authentication::entity::{Entitlement, Role, TokenPayload};
 use crate::authentication::interactor::AuthenticationInteractor;
+use crate::interceptor::policy::Policy;
+
+const ACCOUNT_ID_KEY: &str = "x-account-id";
+const ACCOUNT_ROLE_KEY: &str = "x-account-role";
+const ACCOUNT_ENTITLEMENTS_KEY: &str = "x-account-entitlements";
 
 #[derive(Clone)]
 pub struct AuthInterceptor {
@@ -16,14 +23,19 @@ pub struct AuthInterceptor {
 impl AsyncInterceptor for AuthInterceptor {
     type Future = Pin<Box<dyn Future<Output=Result<Request<()>, Status>> + Send + 'static>>;
 
-    fn call(&mut self, request: Request<()>) -> Self::Future {
+    fn call(&mut self, mut request: Request<()>) -> Self::Future {
         let interactor = self.interactor.clone();
 
         let future = async move {
-            let token = request.metadata().get("Authorization").and_then(|value| value.to_str().ok());
+            request.metadata_mut().remove(ACCOUNT_ID_KEY);
+            request.metadata_mut().remove(ACCOUNT_ROLE_KEY);
+            request.metadata_mut().remove(ACCOUNT_ENTITLEMENTS_KEY);
+
+            let token = request.metadata().get("Authorization").and_then(|value| value.to_str().ok()).map(String::from);
             match token {
                 Some(token) => {
-                    if let Ok(_) = interactor.is_token_valid(token).await {
+                    if let Ok(payload) = interactor.validate_token(&token).await {
+                        attach_identity(&mut request, payload)?;
                         Ok(request)
                     } else {
                         Err(Status::unauthenticated("Invalid token"))
@@ -37,6 +49,43 @@ impl AsyncInterceptor for AuthInterceptor {
     }
 }
 
+fn attach_identity(request: &mut Request<()>, payload: TokenPayload) -> Result<(), Status> {
+    let account_id = MetadataValue::try_from(payload.account_id.as_str()).map_err(|_| Status::unauthenticated("Invalid token"))?;
+    let role = MetadataValue::from_static(match payload.role {
+        Role::User => "user",
+        Role::Moderator => "moderator"
+    });
+    request.metadata_mut().insert(ACCOUNT_ID_KEY, account_id);
+    let entitlements = payload.entitlements.iter().map(|entitlement| match entitlement {
+        Entitlement::Backtrack => "backtrack",
+        Entitlement::SeeWhoLikedYou => "see_who_liked_you",
+        Entitlement::UnlimitedLikes => "unlimited_likes"
+    }).collect::<Vec<&str>>().join(",");
+    let entitlements = MetadataValue::try_from(entitlements.as_str()).map_err(|_| Status::unauthenticated("Invalid token"))?;
+    request.metadata_mut().insert(ACCOUNT_ROLE_KEY, role);
+    request.metadata_mut().insert(ACCOUNT_ENTITLEMENTS_KEY, entitlements);
+    request.extensions_mut().insert(payload);
+    Ok(())
+}
+
+pub fn bind_identity<T>(request: &mut Request<T>, field: impl FnOnce(&mut T) -> &mut String) -> Result<(), Status> {
+    let (account_id, role) = match request.extensions().get::<TokenPayload>() {
+        Some(payload) => (payload.account_id.clone(), payload.role.clone()),
+        None => return Err(Status::unauthenticated("Authenticated account is missing"))
+    };
+    let policy = request.extensions().get::<Policy>().copied().unwrap_or(Policy::Authenticated);
+    let id = field(request.get_mut());
+    if id.is_empty() {
+        *id = account_id;
+    } else if *id != account_id {
+        if policy == Policy::SelfOrModerator && role == Role::Moderator {
+            return Ok(());
+        }
+        return Err(Status::permission_denied("Identifier does not belong to the authenticated account"));
+    }
+    Ok(())
+}
+
 pub fn with_auth_interceptor<S>(
     service: S,
     interactor: Arc<Box<dyn AuthenticationInteractor + Send + Sync>>,
@@ -48,4 +97,65 @@ pub fn with_auth_interceptor<S>(
     ServiceBuilder::new()
         .layer(async_interceptor(AuthInterceptor { interactor }))
         .service(service)
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+
+    fn request(id: &str, role: Role, policy: Policy) -> Request<String> {
+        let mut request = Request::new(String::from(id));
+        request.extensions_mut().insert(TokenPayload {
+            account_id: String::from("self"),
+            role,
+            entitlements: vec![],
+            email_verified: true,
+            totp_enabled: true,
+        });
+        request.extensions_mut().insert(policy);
+        request
+    }
+
+    #[test]
+    fn bind_missing_identity() {
+        let mut request = request("", Role::User, Policy::Authenticated);
+        assert!(bind_identity(&mut request, |message| message).is_ok());
+        assert_eq!(request.get_ref(), "self");
+    }
+
+    #[test]
+    fn bind_own_identity() {
+        let mut request = request("self", Role::User, Policy::Authenticated);
+        assert!(bind_identity(&mut request, |message| message).is_ok());
+    }
+
+    #[test]
+    fn reject_foreign_identity() {
+        let mut request = request("other", Role::User, Policy::SelfOrModerator);
+        let status = bind_identity(&mut request, |message| message).unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+    }
+
+    #[test]
+    fn allow_moderator_on_behalf_of_another_account() {
+        let mut request = request("other", Role::Moderator, Policy::SelfOrModerator);
+        assert!(bind_identity(&mut request, |message| message).is_ok());
+        assert_eq!(request.get_ref(), "other");
+    }
+
+    #[test]
+    fn reject_moderator_outside_self_or_moderator_policy() {
+        let mut request = request("other", Role::Moderator, Policy::Moderator);
+        let status = bind_identity(&mut request, |message| message).unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+    }
+
+    #[test]
+    fn reject_missing_payload() {
+        let mut request = Request::new(String::new());
+        let status = bind_identity(&mut request, |message| message).unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+    }
 }
\ No newline at end of file
@@ -1 +1,2 @@
-pub mod authorization;
\ No newline at end of file
+pub mod authorization;
+pub mod policy;
\ No newline at end of file
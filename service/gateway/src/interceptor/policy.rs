@@ -0,0 +1,191 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tonic::body::BoxBody;
+use tonic::server::NamedService;
+use tonic::Status;
+use tower::Service;
+
+use crate::authentication::entity::{Entitlement, Role, TokenPayload};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Policy {
+    Authenticated,
+    SelfOrModerator,
+    Moderator,
+    Entitled(Entitlement),
+}
+
+const POLICIES: &[(&str, Policy)] = &[
+    ("/account.AccountService/GrantPremium", Policy::Moderator),
+    ("/account.AccountService/ExtendPremium", Policy::Moderator),
+    ("/account.AccountService/RevokePremium", Policy::Moderator),
+    ("/account.AccountService/GetEntitlements", Policy::SelfOrModerator),
+    ("/account.AccountService/SuspendAccount", Policy::Moderator),
+    ("/account.AccountService/BanAccount", Policy::Moderator),
+    ("/account.AccountService/ReinstateAccount", Policy::Moderator),
+    ("/account.AccountService/ChangeRole", Policy::Moderator),
+    ("/account.AccountService/DeleteAccount", Policy::SelfOrModerator),
+    ("/account.AccountService/GetDeletionStatus", Policy::SelfOrModerator),
+    ("/matchmaking.MatchmakingService/GetLikes", Policy::Entitled(Entitlement::SeeWhoLikedYou)),
+    ("/matchmaking.MatchmakingService/Backtrack", Policy::Entitled(Entitlement::Backtrack)),
+    ("/profile.ProfileService/UpdateProfile", Policy::SelfOrModerator),
+    ("/profile.ProfileService/DeleteProfile", Policy::SelfOrModerator),
+    ("/safety.SafetyService/GetBlockedUsers", Policy::SelfOrModerator),
+    ("/safety.SafetyService/ListReports", Policy::Moderator),
+    ("/safety.SafetyService/ResolveReport", Policy::Moderator),
+    ("/support.SupportService/GetTickets", Policy::SelfOrModerator),
+    ("/support.SupportService/DeleteTicket", Policy::SelfOrModerator),
+];
+
+const UNVERIFIED_METHODS: &[&str] = &[
+    "/account.AccountService/GetEntitlements",
+    "/account.AccountService/DeleteAccount",
+    "/account.AccountService/ExportMyData",
+    "/account.AccountService/GetExport",
+    "/account.AccountService/DownloadExport",
+    "/profile.ProfileService/GetProfileById",
+    "/profile.ProfileService/CreateProfile",
+    "/profile.ProfileService/UpdateProfile",
+    "/safety.SafetyService/BlockUser",
+    "/safety.SafetyService/ReportUser",
+    "/support.SupportService/GetTickets",
+    "/support.SupportService/GetTicketById",
+    "/support.SupportService/CreateTicket",
+];
+
+pub fn resolve_policy(path: &str) -> Policy {
+    POLICIES.iter()
+        .find(|(method, _)| *method == path)
+        .map(|(_, policy)| *policy)
+        .unwrap_or(Policy::Authenticated)
+}
+
+pub fn authorize(path: &str, payload: Option<&TokenPayload>) -> Result<Policy, Status> {
+    let policy = resolve_policy(path);
+    match payload {
+        None => Err(Status::unauthenticated("Authenticated account is missing")),
+        Some(payload) if payload.role == Role::Moderator && !payload.totp_enabled => {
+            Err(Status::permission_denied("Moderator accounts require two-factor authentication"))
+        }
+        Some(payload) if !payload.email_verified && !UNVERIFIED_METHODS.contains(&path) => {
+            Err(Status::permission_denied(format!("Method {} requires a verified email", path)))
+        }
+        Some(payload) if policy == Policy::Moderator && payload.role != Role::Moderator => {
+            Err(Status::permission_denied(format!("Method {} requires MODERATOR role", path)))
+        }
+        Some(payload) if matches!(policy, Policy::Entitled(entitlement) if !payload.entitlements.contains(&entitlement)) => {
+            Err(Status::permission_denied(format!("Method {} requires premium", path)))
+        }
+        Some(_) => Ok(policy)
+    }
+}
+
+#[derive(Clone)]
+pub struct PolicyService<S> {
+    inner: S,
+}
+
+impl<S, B> Service<http::Request<B>> for PolicyService<S>
+    where
+        S: Service<http::Request<B>, Response=http::Response<BoxBody>>,
+        S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output=Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        let path = request.uri().path().to_string();
+        match authorize(&path, request.extensions().get::<TokenPayload>()) {
+            Ok(policy) => {
+                request.extensions_mut().insert(policy);
+                Box::pin(self.inner.call(request))
+            }
+            Err(status) => {
+                let response = status.to_http();
+                Box::pin(async move { Ok(response) })
+            }
+        }
+    }
+}
+
+impl<S: NamedService> NamedService for PolicyService<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+pub fn with_policy_interceptor<S>(service: S) -> PolicyService<S> {
+    PolicyService { inner: service }
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+
+    fn payload(role: Role, entitlements: Vec<Entitlement>) -> TokenPayload {
+        TokenPayload { account_id: String::from("id"), role, entitlements, email_verified: true, totp_enabled: true }
+    }
+
+    #[test]
+    fn resolve_default_policy() {
+        assert_eq!(resolve_policy("/profile.ProfileService/GetProfileById"), Policy::Authenticated);
+        assert_eq!(resolve_policy("/support.SupportService/DeleteTicket"), Policy::SelfOrModerator);
+    }
+
+    #[test]
+    fn reject_missing_payload() {
+        let status = authorize("/profile.ProfileService/GetProfileById", None).unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+    }
+
+    #[test]
+    fn authenticated_policy() {
+        let policy = authorize("/profile.ProfileService/GetProfileById", Some(&payload(Role::User, vec![])));
+        assert_eq!(policy.unwrap(), Policy::Authenticated);
+    }
+
+    #[test]
+    fn self_or_moderator_policy() {
+        let path = "/support.SupportService/DeleteTicket";
+        assert_eq!(authorize(path, Some(&payload(Role::User, vec![]))).unwrap(), Policy::SelfOrModerator);
+        assert_eq!(authorize(path, Some(&payload(Role::Moderator, vec![]))).unwrap(), Policy::SelfOrModerator);
+    }
+
+    #[test]
+    fn moderator_policy() {
+        let path = "/safety.SafetyService/ResolveReport";
+        assert_eq!(authorize(path, Some(&payload(Role::Moderator, vec![]))).unwrap(), Policy::Moderator);
+        assert_eq!(authorize(path, Some(&payload(Role::User, vec![]))).unwrap_err().code(), Code::PermissionDenied);
+    }
+
+    #[test]
+    fn moderator_without_totp() {
+        let payload = TokenPayload { totp_enabled: false, ..payload(Role::Moderator, vec![]) };
+        let status = authorize("/safety.SafetyService/ResolveReport", Some(&payload)).unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+    }
+
+    #[test]
+    fn entitled_policy() {
+        let path = "/matchmaking.MatchmakingService/Backtrack";
+        let policy = authorize(path, Some(&payload(Role::User, vec![Entitlement::Backtrack])));
+        assert_eq!(policy.unwrap(), Policy::Entitled(Entitlement::Backtrack));
+        let status = authorize(path, Some(&payload(Role::User, vec![Entitlement::SeeWhoLikedYou]))).unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+    }
+
+    #[test]
+    fn unverified_methods() {
+        let payload = TokenPayload { email_verified: false, ..payload(Role::User, vec![]) };
+        assert!(authorize("/support.SupportService/CreateTicket", Some(&payload)).is_ok());
+        let status = authorize("/conversation.ConversationService/SendMessage", Some(&payload)).unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+    }
+}
\ No newline at end of file
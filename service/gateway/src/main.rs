@@ -6,6 +6,7 @@ use tonic::transport::{Channel, Server};
 
 mod interceptor;
 
+mod account;
 mod authentication;
 mod conversation;
 mod matchmaking;
@@ -13,8 +14,10 @@ mod profile;
 mod recommendation;
 mod safety;
 mod support;
+mod token;
 
 const SERVICE_NAME: &str = "gateway";
+const ACCOUNT_SERVICE_NAME: &str = "account";
 const AUTHENTICATION_SERVICE_NAME: &str = "authentication";
 const CONVERSATION_SERVICE_NAME: &str = "conversation";
 const MATCHMAKING_SERVICE_NAME: &str = "matchmaking";
@@ -22,10 +25,12 @@ const PROFILE_SERVICE_NAME: &str = "profile";
 const RECOMMENDATION_SERVICE_NAME: &str = "recommendation";
 const SAFETY_SERVICE_NAME: &str = "safety";
 const SUPPORT_SERVICE_NAME: &str = "support";
+const TOKEN_SERVICE_NAME: &str = "token";
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     let cfg = configuration::Config::default(SERVICE_NAME)?;
+    let account_cfg = configuration::Config::default(ACCOUNT_SERVICE_NAME)?;
     let authentication_cfg = configuration::Config::default(AUTHENTICATION_SERVICE_NAME)?;
     let conversation_cfg = configuration::Config::default(CONVERSATION_SERVICE_NAME)?;
     let matchmaking_cfg = configuration::Config::default(MATCHMAKING_SERVICE_NAME)?;
@@ -33,6 +38,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let recommendation_cfg = configuration::Config::default(RECOMMENDATION_SERVICE_NAME)?;
     let safety_cfg = configuration::Config::default(SAFETY_SERVICE_NAME)?;
     let support_cfg = configuration::Config::default(SUPPORT_SERVICE_NAME)?;
+    let token_cfg = configuration::Config::default(TOKEN_SERVICE_NAME)?;
 
     let create_channel_url: fn(&str, &str) -> &'static str = |hostname, port| Box::leak(format!("https://{}:{}", hostname, port).into_boxed_str());
 
@@ -41,10 +47,38 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let authentication_client = authentication::pb::authentication_service_client::AuthenticationServiceClient::new(authentication_channel);
     let authentication_service = authentication::service::AuthenticationServiceImpl::new(authentication_client.clone());
 
+    let token_channel_url = create_channel_url(&token_cfg.service_hostname.unwrap(), &token_cfg.service_port.unwrap());
+    let token_channel = Channel::from_static(token_channel_url).connect_lazy();
+    let token_client = token::pb::token_service_client::TokenServiceClient::new(token_channel);
+    let token_api = token::api::TokenApiImpl::new(token_client);
+
+    let amqp_hostname = cfg.amqp_hostname.unwrap();
+    let amqp_port = cfg.amqp_port.unwrap();
+
+    let denylist = Arc::new(authentication::denylist::Denylist::default());
+
+    let invalidated_tokens_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut invalidated_tokens_handler = authentication::amqp::MessageQueueHandler::new(Arc::clone(&denylist), invalidated_tokens_queue);
+    invalidated_tokens_handler.consume_invalidated_tokens().await;
+
+    let revoked_subjects_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut revoked_subjects_handler = authentication::amqp::MessageQueueHandler::new(Arc::clone(&denylist), revoked_subjects_queue);
+    revoked_subjects_handler.consume_revoked_subjects().await;
+
+    let revoked_sessions_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut revoked_sessions_handler = authentication::amqp::MessageQueueHandler::new(Arc::clone(&denylist), revoked_sessions_queue);
+    revoked_sessions_handler.consume_revoked_sessions().await;
+
     let authentication_api = authentication::api::AuthenticationApiImpl::new(authentication_client.clone());
-    let authentication_repository = authentication::repository::AuthenticationRepositoryImpl::new(authentication_api);
+    let authentication_keyset = authentication::keyset::KeysetImpl::new(token_api);
+    let authentication_repository = authentication::repository::AuthenticationRepositoryImpl::new(authentication_api, authentication_keyset, Arc::clone(&denylist));
     let authentication_interactor = Arc::new(authentication::interactor::AuthenticationInteractorImpl::new(authentication_repository));
 
+    let account_channel_url = create_channel_url(&account_cfg.service_hostname.unwrap(), &account_cfg.service_port.unwrap());
+    let account_channel = Channel::from_static(account_channel_url).connect_lazy();
+    let account_client = account::pb::account_service_client::AccountServiceClient::new(account_channel);
+    let account_service = account::service::AccountServiceImpl::new(account_client);
+
     let conversation_channel_url = create_channel_url(&conversation_cfg.service_hostname.unwrap(), &conversation_cfg.service_port.unwrap());
     let conversation_channel = Channel::from_static(conversation_channel_url).connect_lazy();
     let conversation_client = conversation::pb::conversation_service_client::ConversationServiceClient::new(conversation_channel);
@@ -81,12 +115,13 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     Server::builder()
         .add_service(authentication::pb::authentication_service_server::AuthenticationServiceServer::new(authentication_service))
-        .add_service(interceptor::authorization::with_auth_interceptor(conversation::pb::conversation_service_server::ConversationServiceServer::new(conversation_service), Arc::clone(&authentication_interactor)))
-        .add_service(interceptor::authorization::with_auth_interceptor(matchmaking::pb::matchmaking_service_server::MatchmakingServiceServer::new(matchmaking_service), Arc::clone(&authentication_interactor)))
-        .add_service(interceptor::authorization::with_auth_interceptor(profile::pb::profile_service_server::ProfileServiceServer::new(profile_service), Arc::clone(&authentication_interactor)))
-        .add_service(interceptor::authorization::with_auth_interceptor(recommendation::pb::recommendation_service_server::RecommendationServiceServer::new(recommendation_service), Arc::clone(&authentication_interactor)))
-        .add_service(interceptor::authorization::with_auth_interceptor(safety::pb::safety_service_server::SafetyServiceServer::new(safety_service), Arc::clone(&authentication_interactor)))
-        .add_service(interceptor::authorization::with_auth_interceptor(support::pb::support_service_server::SupportServiceServer::new(support_service), Arc::clone(&authentication_interactor)))
+        .add_service(interceptor::authorization::with_auth_interceptor(interceptor::policy::with_policy_interceptor(account::pb::account_service_server::AccountServiceServer::new(account_service)), Arc::clone(&authentication_interactor)))
+        .add_service(interceptor::authorization::with_auth_interceptor(interceptor::policy::with_policy_interceptor(conversation::pb::conversation_service_server::ConversationServiceServer::new(conversation_service)), Arc::clone(&authentication_interactor)))
+        .add_service(interceptor::authorization::with_auth_interceptor(interceptor::policy::with_policy_interceptor(matchmaking::pb::matchmaking_service_server::MatchmakingServiceServer::new(matchmaking_service)), Arc::clone(&authentication_interactor)))
+        .add_service(interceptor::authorization::with_auth_interceptor(interceptor::policy::with_policy_interceptor(profile::pb::profile_service_server::ProfileServiceServer::new(profile_service)), Arc::clone(&authentication_interactor)))
+        .add_service(interceptor::authorization::with_auth_interceptor(interceptor::policy::with_policy_interceptor(recommendation::pb::recommendation_service_server::RecommendationServiceServer::new(recommendation_service)), Arc::clone(&authentication_interactor)))
+        .add_service(interceptor::authorization::with_auth_interceptor(interceptor::policy::with_policy_interceptor(safety::pb::safety_service_server::SafetyServiceServer::new(safety_service)), Arc::clone(&authentication_interactor)))
+        .add_service(interceptor::authorization::with_auth_interceptor(interceptor::policy::with_policy_interceptor(support::pb::support_service_server::SupportServiceServer::new(support_service)), Arc::clone(&authentication_interactor)))
         .serve(server_addr)
         .await?;
 
@@ -1,10 +1,13 @@
 use tonic::{Request, Response, Status};
 use tonic::transport::Channel;
 
-use crate::matchmaking::pb::{BacktrackRequest, BacktrackResponse, CheckIdsRequest, CheckIdsResponse, DislikeRequest, DislikeResponse, GetLikesRequest, GetLikesResponse, LikeRequest, LikeResponse};
+use crate::interceptor::authorization::bind_identity;
+use crate::matchmaking::pb::{BacktrackRequest, BacktrackResponse, CheckIdsRequest, CheckIdsResponse, DislikeRequest, DislikeResponse, ExportDataRequest, ExportDataResponse, GetLikesRequest, GetLikesResponse, LikeRequest, LikeResponse};
 use crate::matchmaking::pb::matchmaking_service_client::MatchmakingServiceClient;
 use crate::matchmaking::pb::matchmaking_service_server::MatchmakingService;
 
+const INTERNAL_METHOD: &str = "method is not exposed by the gateway";
+
 pub struct MatchmakingServiceImpl {
     client: MatchmakingServiceClient<Channel>,
 }
@@ -17,23 +20,32 @@ impl MatchmakingServiceImpl {
 
 #[tonic::async_trait]
 impl MatchmakingService for MatchmakingServiceImpl {
-    async fn check_ids(&self, request: Request<CheckIdsRequest>) -> Result<Response<CheckIdsResponse>, Status> {
+    async fn check_ids(&self, mut request: Request<CheckIdsRequest>) -> Result<Response<CheckIdsResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.from_id)?;
         self.client.clone().check_ids(request).await
     }
 
-    async fn get_likes(&self, request: Request<GetLikesRequest>) -> Result<Response<GetLikesResponse>, Status> {
+    async fn get_likes(&self, mut request: Request<GetLikesRequest>) -> Result<Response<GetLikesResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.id)?;
         self.client.clone().get_likes(request).await
     }
 
-    async fn like(&self, request: Request<LikeRequest>) -> Result<Response<LikeResponse>, Status> {
+    async fn like(&self, mut request: Request<LikeRequest>) -> Result<Response<LikeResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.from_id)?;
         self.client.clone().like(request).await
     }
 
-    async fn dislike(&self, request: Request<DislikeRequest>) -> Result<Response<DislikeResponse>, Status> {
+    async fn dislike(&self, mut request: Request<DislikeRequest>) -> Result<Response<DislikeResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.from_id)?;
         self.client.clone().dislike(request).await
     }
 
-    async fn backtrack(&self, request: Request<BacktrackRequest>) -> Result<Response<BacktrackResponse>, Status> {
+    async fn backtrack(&self, mut request: Request<BacktrackRequest>) -> Result<Response<BacktrackResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.id)?;
         self.client.clone().backtrack(request).await
     }
+
+    async fn export_data(&self, _: Request<ExportDataRequest>) -> Result<Response<ExportDataResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
 }
\ No newline at end of file
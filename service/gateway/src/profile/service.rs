@@ -1,10 +1,13 @@
 use tonic::{Request, Response, Status};
 use tonic::transport::Channel;
 
-use crate::profile::pb::{CreateProfileRequest, CreateProfileResponse, DeleteProfileRequest, DeleteProfileResponse, GetProfileByIdRequest, GetProfileByIdResponse, GetProfilesRequest, GetProfilesResponse, GetRandomProfilesRequest, GetRandomProfilesResponse, UpdateProfileRequest, UpdateProfileResponse};
+use crate::interceptor::authorization::bind_identity;
+use crate::profile::pb::{CreateProfileRequest, CreateProfileResponse, DeleteProfileRequest, DeleteProfileResponse, ExportDataRequest, ExportDataResponse, GetProfileByIdRequest, GetProfileByIdResponse, GetProfilesRequest, GetProfilesResponse, GetRandomProfilesRequest, GetRandomProfilesResponse, UpdateProfileRequest, UpdateProfileResponse};
 use crate::profile::pb::profile_service_client::ProfileServiceClient;
 use crate::profile::pb::profile_service_server::ProfileService;
 
+const INTERNAL_METHOD: &str = "method is not exposed by the gateway";
+
 pub struct ProfileServiceImpl {
     client: ProfileServiceClient<Channel>,
 }
@@ -17,11 +20,13 @@ impl ProfileServiceImpl {
 
 #[tonic::async_trait]
 impl ProfileService for ProfileServiceImpl {
-    async fn get_random_profiles(&self, request: Request<GetRandomProfilesRequest>) -> Result<Response<GetRandomProfilesResponse>, Status> {
+    async fn get_random_profiles(&self, mut request: Request<GetRandomProfilesRequest>) -> Result<Response<GetRandomProfilesResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.excepted_id)?;
         self.client.clone().get_random_profiles(request).await
     }
 
-    async fn get_profiles(&self, request: Request<GetProfilesRequest>) -> Result<Response<GetProfilesResponse>, Status> {
+    async fn get_profiles(&self, mut request: Request<GetProfilesRequest>) -> Result<Response<GetProfilesResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.excepted_id)?;
         self.client.clone().get_profiles(request).await
     }
 
@@ -29,15 +34,22 @@ impl ProfileService for ProfileServiceImpl {
         self.client.clone().get_profile_by_id(request).await
     }
 
-    async fn create_profile(&self, request: Request<CreateProfileRequest>) -> Result<Response<CreateProfileResponse>, Status> {
+    async fn create_profile(&self, mut request: Request<CreateProfileRequest>) -> Result<Response<CreateProfileResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.id)?;
         self.client.clone().create_profile(request).await
     }
 
-    async fn update_profile(&self, request: Request<UpdateProfileRequest>) -> Result<Response<UpdateProfileResponse>, Status> {
+    async fn update_profile(&self, mut request: Request<UpdateProfileRequest>) -> Result<Response<UpdateProfileResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.id)?;
         self.client.clone().update_profile(request).await
     }
 
-    async fn delete_profile(&self, request: Request<DeleteProfileRequest>) -> Result<Response<DeleteProfileResponse>, Status> {
+    async fn delete_profile(&self, mut request: Request<DeleteProfileRequest>) -> Result<Response<DeleteProfileResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.id)?;
         self.client.clone().delete_profile(request).await
     }
+
+    async fn export_data(&self, _: Request<ExportDataRequest>) -> Result<Response<ExportDataResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
 }
\ No newline at end of file
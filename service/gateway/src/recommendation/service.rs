@@ -1,6 +1,7 @@
 use tonic::{Request, Response, Status};
 use tonic::transport::Channel;
 
+use crate::interceptor::authorization::bind_identity;
 use crate::recommendation::pb::{GetCandidatesRequest, GetCandidatesResponse};
 use crate::recommendation::pb::recommendation_service_client::RecommendationServiceClient;
 use crate::recommendation::pb::recommendation_service_server::RecommendationService;
@@ -17,7 +18,8 @@ impl RecommendationServiceImpl {
 
 #[tonic::async_trait]
 impl RecommendationService for RecommendationServiceImpl {
-    async fn get_candidates(&self, request: Request<GetCandidatesRequest>) -> Result<Response<GetCandidatesResponse>, Status> {
+    async fn get_candidates(&self, mut request: Request<GetCandidatesRequest>) -> Result<Response<GetCandidatesResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.excepted_id)?;
         self.client.clone().get_candidates(request).await
     }
 }
\ No newline at end of file
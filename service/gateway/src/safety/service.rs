@@ -1,10 +1,13 @@
 use tonic::{Request, Response, Status};
 use tonic::transport::Channel;
 
-use crate::safety::pb::{BlockUserRequest, BlockUserResponse, CheckUserIdRequest, CheckUserIdResponse, GetBlockedUsersRequest, GetBlockedUsersResponse, UnblockUserRequest, UnblockUserResponse};
+use crate::interceptor::authorization::bind_identity;
+use crate::safety::pb::{BlockUserRequest, BlockUserResponse, CheckUserIdRequest, CheckUserIdResponse, ExportDataRequest, ExportDataResponse, GetBlockedUsersRequest, GetBlockedUsersResponse, ListReportsRequest, ListReportsResponse, ReportUserRequest, ReportUserResponse, ResolveReportRequest, ResolveReportResponse, UnblockUserRequest, UnblockUserResponse};
 use crate::safety::pb::safety_service_client::SafetyServiceClient;
 use crate::safety::pb::safety_service_server::SafetyService;
 
+const INTERNAL_METHOD: &str = "method is not exposed by the gateway";
+
 pub struct SafetyServiceImpl {
     client: SafetyServiceClient<Channel>,
 }
@@ -17,19 +20,41 @@ impl SafetyServiceImpl {
 
 #[tonic::async_trait]
 impl SafetyService for SafetyServiceImpl {
-    async fn check_user_id(&self, request: Request<CheckUserIdRequest>) -> Result<Response<CheckUserIdResponse>, Status> {
+    async fn check_user_id(&self, mut request: Request<CheckUserIdRequest>) -> Result<Response<CheckUserIdResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.from_id)?;
         self.client.clone().check_user_id(request).await
     }
 
-    async fn get_blocked_users(&self, request: Request<GetBlockedUsersRequest>) -> Result<Response<GetBlockedUsersResponse>, Status> {
+    async fn get_blocked_users(&self, mut request: Request<GetBlockedUsersRequest>) -> Result<Response<GetBlockedUsersResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.from_id)?;
         self.client.clone().get_blocked_users(request).await
     }
 
-    async fn block_user(&self, request: Request<BlockUserRequest>) -> Result<Response<BlockUserResponse>, Status> {
+    async fn block_user(&self, mut request: Request<BlockUserRequest>) -> Result<Response<BlockUserResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.from_id)?;
         self.client.clone().block_user(request).await
     }
 
-    async fn unblock_user(&self, request: Request<UnblockUserRequest>) -> Result<Response<UnblockUserResponse>, Status> {
+    async fn unblock_user(&self, mut request: Request<UnblockUserRequest>) -> Result<Response<UnblockUserResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.from_id)?;
         self.client.clone().unblock_user(request).await
     }
+
+    async fn report_user(&self, mut request: Request<ReportUserRequest>) -> Result<Response<ReportUserResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.from_id)?;
+        self.client.clone().report_user(request).await
+    }
+
+    async fn list_reports(&self, request: Request<ListReportsRequest>) -> Result<Response<ListReportsResponse>, Status> {
+        self.client.clone().list_reports(request).await
+    }
+
+    async fn resolve_report(&self, mut request: Request<ResolveReportRequest>) -> Result<Response<ResolveReportResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.moderator_id)?;
+        self.client.clone().resolve_report(request).await
+    }
+
+    async fn export_data(&self, _: Request<ExportDataRequest>) -> Result<Response<ExportDataResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
 }
\ No newline at end of file
@@ -1,10 +1,13 @@
 use tonic::{Request, Response, Status};
 use tonic::transport::Channel;
 
-use crate::support::pb::{CreateTicketRequest, CreateTicketResponse, DeleteTicketRequest, DeleteTicketResponse, GetTicketByIdRequest, GetTicketByIdResponse, GetTicketsRequest, GetTicketsResponse, UpdateTicketRequest, UpdateTicketResponse};
+use crate::interceptor::authorization::bind_identity;
+use crate::support::pb::{CreateTicketRequest, CreateTicketResponse, DeleteTicketRequest, DeleteTicketResponse, ExportDataRequest, ExportDataResponse, GetTicketByIdRequest, GetTicketByIdResponse, GetTicketsRequest, GetTicketsResponse, UpdateTicketRequest, UpdateTicketResponse};
 use crate::support::pb::support_service_client::SupportServiceClient;
 use crate::support::pb::support_service_server::SupportService;
 
+const INTERNAL_METHOD: &str = "method is not exposed by the gateway";
+
 pub struct SupportServiceImpl {
     client: SupportServiceClient<Channel>,
 }
@@ -17,23 +20,32 @@ impl SupportServiceImpl {
 
 #[tonic::async_trait]
 impl SupportService for SupportServiceImpl {
-    async fn get_tickets(&self, request: Request<GetTicketsRequest>) -> Result<Response<GetTicketsResponse>, Status> {
+    async fn get_tickets(&self, mut request: Request<GetTicketsRequest>) -> Result<Response<GetTicketsResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.user_id)?;
         self.client.clone().get_tickets(request).await
     }
 
-    async fn get_ticket_by_id(&self, request: Request<GetTicketByIdRequest>) -> Result<Response<GetTicketByIdResponse>, Status> {
+    async fn get_ticket_by_id(&self, mut request: Request<GetTicketByIdRequest>) -> Result<Response<GetTicketByIdResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.user_id)?;
         self.client.clone().get_ticket_by_id(request).await
     }
 
-    async fn create_ticket(&self, request: Request<CreateTicketRequest>) -> Result<Response<CreateTicketResponse>, Status> {
+    async fn create_ticket(&self, mut request: Request<CreateTicketRequest>) -> Result<Response<CreateTicketResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.user_id)?;
         self.client.clone().create_ticket(request).await
     }
 
-    async fn update_ticket(&self, request: Request<UpdateTicketRequest>) -> Result<Response<UpdateTicketResponse>, Status> {
+    async fn update_ticket(&self, mut request: Request<UpdateTicketRequest>) -> Result<Response<UpdateTicketResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.user_id)?;
         self.client.clone().update_ticket(request).await
     }
 
-    async fn delete_ticket(&self, request: Request<DeleteTicketRequest>) -> Result<Response<DeleteTicketResponse>, Status> {
+    async fn delete_ticket(&self, mut request: Request<DeleteTicketRequest>) -> Result<Response<DeleteTicketResponse>, Status> {
+        bind_identity(&mut request, |message| &mut message.user_id)?;
         self.client.clone().delete_ticket(request).await
     }
+
+    async fn export_data(&self, _: Request<ExportDataRequest>) -> Result<Response<ExportDataResponse>, Status> {
+        Err(Status::unimplemented(INTERNAL_METHOD))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,27 @@
+use tonic::{Request, Response, Status};
+use tonic::transport::Channel;
+
+use crate::token::pb::{GetPublicKeysRequest, GetPublicKeysResponse};
+use crate::token::pb::token_service_client::TokenServiceClient;
+
+#[tonic::async_trait]
+pub trait TokenApi {
+    async fn get_public_keys(&self, request: Request<GetPublicKeysRequest>) -> Result<Response<GetPublicKeysResponse>, Status>;
+}
+
+pub struct TokenApiImpl {
+    client: TokenServiceClient<Channel>,
+}
+
+impl TokenApiImpl {
+    pub fn new(client: TokenServiceClient<Channel>) -> Box<dyn TokenApi + Send + Sync> {
+        Box::new(TokenApiImpl { client })
+    }
+}
+
+#[tonic::async_trait]
+impl TokenApi for TokenApiImpl {
+    async fn get_public_keys(&self, request: Request<GetPublicKeysRequest>) -> Result<Response<GetPublicKeysResponse>, Status> {
+        self.client.clone().get_public_keys(request).await
+    }
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+pub mod api;
+
+pub mod pb {
+    tonic::include_proto!("token");
+}
\ No newline at end of file
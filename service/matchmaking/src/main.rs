@@ -1,5 +1,6 @@
 use std::error::Error;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use tonic::transport::Server;
 
@@ -18,11 +19,22 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     let redis_client = redis::Client::open(format!("redis://{}:{}", cfg.redis_hostname.unwrap(), cfg.redis_port.unwrap()))?;
 
-    let message_queue = amqp::MessageQueue::connect(&cfg.amqp_hostname.unwrap(), &cfg.amqp_port.unwrap()).await?;
+    let amqp_hostname = cfg.amqp_hostname.unwrap();
+    let amqp_port = cfg.amqp_port.unwrap();
+
+    let message_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
 
     let repository = matchmaking::repository::MatchmakingRepositoryImpl::new(collection, redis_client, message_queue).await;
-    let interactor = matchmaking::interactor::MatchmakingInteractorImpl::new(repository);
-    let service = matchmaking::service::MatchmakingServiceImpl::new(interactor);
+    let interactor = Arc::new(matchmaking::interactor::MatchmakingInteractorImpl::new(repository));
+    let service = matchmaking::service::MatchmakingServiceImpl::new(Arc::clone(&interactor));
+
+    let blocked_users_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut message_queue_handler = matchmaking::amqp::MessageQueueHandler::new(Arc::clone(&interactor), blocked_users_queue);
+    message_queue_handler.consume_blocked_users().await;
+
+    let deleted_accounts_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut deleted_accounts_handler = matchmaking::amqp::MessageQueueHandler::new(Arc::clone(&interactor), deleted_accounts_queue);
+    deleted_accounts_handler.consume_deleted_accounts().await;
 
     let server_addr = SocketAddr::new(cfg.service_hostname.unwrap().parse().unwrap(), cfg.service_port.unwrap().parse().unwrap());
 
@@ -0,0 +1,71 @@
+use std::str::from_utf8;
+use std::sync::Arc;
+
+use tokio::spawn;
+
+use amqp::MessageQueue;
+
+use crate::matchmaking::interactor::MatchmakingInteractor;
+
+const EXCHANGE_NAME: &str = "action";
+const QUEUE_NAME: &str = "matchmaking.user.blocked";
+const ROUTING_KEY: &str = "user.blocked";
+const DELETED_QUEUE_NAME: &str = "matchmaking.account.deleted";
+const DELETED_ROUTING_KEY: &str = "account.deleted";
+const ERASED_ROUTING_KEY: &str = "account.erased";
+const SERVICE_NAME: &str = "matchmaking";
+
+pub struct MessageQueueHandler {
+    interactor: Arc<Box<dyn MatchmakingInteractor + Send + Sync>>,
+    message_queue: MessageQueue,
+}
+
+impl MessageQueueHandler {
+    pub fn new(interactor: Arc<Box<dyn MatchmakingInteractor + Send + Sync>>, message_queue: MessageQueue) -> Self {
+        Self {
+            interactor,
+            message_queue,
+        }
+    }
+
+    pub async fn consume_blocked_users(&mut self) {
+        let _ = self.message_queue.bind_queue(EXCHANGE_NAME, "direct", QUEUE_NAME, ROUTING_KEY).await;
+
+        let interactor = Arc::clone(&self.interactor);
+        let handle_message = move |msg: &[u8]| {
+            let interactor = Arc::clone(&interactor);
+            if let Ok(value) = from_utf8(msg) {
+                let user_ids: Vec<String> = value.split(",").map(String::from).collect();
+                if user_ids.len() == 2 {
+                    spawn(async move {
+                        let _ = interactor.delete_likes(&user_ids[0], &user_ids[1]).await;
+                    });
+                }
+            }
+            Ok(())
+        };
+
+        let _ = self.message_queue.start_consuming(QUEUE_NAME, Box::new(handle_message)).await;
+    }
+
+    pub async fn consume_deleted_accounts(&mut self) {
+        let _ = self.message_queue.bind_queue(EXCHANGE_NAME, "direct", DELETED_QUEUE_NAME, DELETED_ROUTING_KEY).await;
+
+        let interactor = Arc::clone(&self.interactor);
+        let message_queue = self.message_queue.clone();
+        let handle_message = move |msg: Vec<u8>| {
+            let interactor = Arc::clone(&interactor);
+            let message_queue = message_queue.clone();
+            async move {
+                if let Ok(id) = String::from_utf8(msg) {
+                    interactor.erase_account(&id).await?;
+                    let message = [id.as_str(), SERVICE_NAME].join(",");
+                    message_queue.publish(EXCHANGE_NAME, ERASED_ROUTING_KEY, message.as_bytes()).await?;
+                }
+                Ok(())
+            }
+        };
+
+        let _ = self.message_queue.start_consuming_async(DELETED_QUEUE_NAME, handle_message).await;
+    }
+}
\ No newline at end of file
@@ -1,14 +1,23 @@
+use serde_json::json;
+
+use error::DomainError;
+
 use crate::matchmaking::repository::MatchmakingRepository;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
+const DAILY_LIKE_LIMIT: u64 = 100;
+
 #[async_trait::async_trait]
 pub trait MatchmakingInteractor {
     async fn check_ids(&self, from_id: &str, to_ids: Vec<String>) -> Result<Vec<bool>, Error>;
     async fn get_likes(&self, id: &str, skip: u64, limit: u64) -> Result<Vec<String>, Error>;
-    async fn like(&self, from_id: &str, to_id: &str) -> Result<bool, Error>;
+    async fn like(&self, from_id: &str, to_id: &str, has_unlimited_likes: bool) -> Result<bool, Error>;
     async fn dislike(&self, from_id: &str, to_id: &str) -> Result<(), Error>;
     async fn backtrack(&self, id: &str) -> Result<String, Error>;
+    async fn delete_likes(&self, from_id: &str, to_id: &str) -> Result<(), Error>;
+    async fn erase_account(&self, account_id: &str) -> Result<(), Error>;
+    async fn export_data(&self, account_id: &str) -> Result<Vec<u8>, Error>;
 }
 
 pub struct MatchmakingInteractorImpl {
@@ -31,8 +40,20 @@ impl MatchmakingInteractor for MatchmakingInteractorImpl {
         self.repository.get_likes(id, skip, limit).await
     }
 
-    async fn like(&self, from_id: &str, to_id: &str) -> Result<bool, Error> {
-        self.repository.like(from_id, to_id).await
+    async fn like(&self, from_id: &str, to_id: &str, has_unlimited_likes: bool) -> Result<bool, Error> {
+        // the like is counted before it is stored so that concurrent likes cannot exceed the limit
+        let count = self.repository.increment_daily_like_count(from_id).await?;
+        if !has_unlimited_likes && count > DAILY_LIKE_LIMIT {
+            self.repository.decrement_daily_like_count(from_id).await?;
+            return Err(DomainError::resource_exhausted("daily like limit reached"));
+        }
+        match self.repository.like(from_id, to_id).await {
+            Ok(has_match) => Ok(has_match),
+            Err(error) => {
+                self.repository.decrement_daily_like_count(from_id).await?;
+                Err(error)
+            }
+        }
     }
 
     async fn dislike(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
@@ -42,4 +63,17 @@ impl MatchmakingInteractor for MatchmakingInteractorImpl {
     async fn backtrack(&self, id: &str) -> Result<String, Error> {
         self.repository.backtrack(id).await
     }
+
+    async fn delete_likes(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
+        self.repository.delete_likes(from_id, to_id).await
+    }
+
+    async fn erase_account(&self, account_id: &str) -> Result<(), Error> {
+        self.repository.erase_likes(account_id).await
+    }
+
+    async fn export_data(&self, account_id: &str) -> Result<Vec<u8>, Error> {
+        let (given, received) = self.repository.export_likes(account_id).await?;
+        Ok(serde_json::to_vec(&json!({ "likes_given": given, "likes_received": received }))?)
+    }
 }
\ No newline at end of file
@@ -9,7 +9,7 @@ use mongodb::options::FindOptions;
 use redis::{Client as RedisClient, Commands, transaction};
 
 use amqp::MessageQueue;
-use error::make_error;
+use error::DomainError;
 
 use crate::matchmaking::entity::Like;
 
@@ -18,14 +18,20 @@ type Error = Box<dyn std::error::Error + Send + Sync>;
 const EXCHANGE_NAME: &str = "action";
 const MATCH_ROUTING_KEY: &str = "match";
 const DISLIKE_ROUTING_KEY: &str = "dislike";
+const DAILY_LIKES_EXPIRATION_SECONDS: i64 = 60 * 60 * 24;
 
 #[async_trait::async_trait]
 pub trait MatchmakingRepository {
     async fn check_ids(&self, from_id: &str, to_ids: Vec<String>) -> Result<Vec<bool>, Error>;
     async fn get_likes(&self, id: &str, skip: u64, limit: u64) -> Result<Vec<String>, Error>;
     async fn like(&self, from_id: &str, to_id: &str) -> Result<bool, Error>;
+    async fn increment_daily_like_count(&self, from_id: &str) -> Result<u64, Error>;
+    async fn decrement_daily_like_count(&self, from_id: &str) -> Result<(), Error>;
     async fn dislike(&self, from_id: &str, to_id: &str) -> Result<(), Error>;
     async fn backtrack(&self, id: &str) -> Result<String, Error>;
+    async fn delete_likes(&self, from_id: &str, to_id: &str) -> Result<(), Error>;
+    async fn erase_likes(&self, id: &str) -> Result<(), Error>;
+    async fn export_likes(&self, id: &str) -> Result<(Vec<Like>, Vec<Like>), Error>;
 }
 
 pub struct MatchmakingRepositoryImpl {
@@ -35,6 +41,10 @@ pub struct MatchmakingRepositoryImpl {
 }
 
 impl MatchmakingRepositoryImpl {
+    fn daily_likes_key(from_id: &str) -> String {
+        format!("likes:{}", from_id)
+    }
+
     pub async fn new(collection: Collection<Like>, redis_client: RedisClient, message_queue: MessageQueue) -> Box<dyn MatchmakingRepository + Send + Sync> {
         Box::new(MatchmakingRepositoryImpl { collection, redis_client, message_queue })
     }
@@ -69,14 +79,28 @@ impl MatchmakingRepository for MatchmakingRepositoryImpl {
 
     async fn like(&self, from_id: &str, to_id: &str) -> Result<bool, Error> {
         let id = ObjectId::new().to_hex();
-        if let Ok(_) = self.collection.insert_one(Like::new(&id, from_id, to_id), None).await {
-            if let Some(_) = self.collection.find_one(doc! { "from_id": to_id, "to_id": from_id }, None).await? {
-                let _ = self.message_queue.publish(EXCHANGE_NAME, MATCH_ROUTING_KEY, vec![to_id, from_id].join(",").as_bytes());
-                return Ok(true);
-            }
-            return Ok(false);
+        self.collection.insert_one(Like::new(&id, from_id, to_id), None).await?;
+        if let Some(_) = self.collection.find_one(doc! { "from_id": to_id, "to_id": from_id }, None).await? {
+            let _ = self.message_queue.publish(EXCHANGE_NAME, MATCH_ROUTING_KEY, vec![to_id, from_id].join(",").as_bytes());
+            return Ok(true);
         }
-        Err(make_error!("unable to like"))
+        Ok(false)
+    }
+
+    async fn increment_daily_like_count(&self, from_id: &str) -> Result<u64, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let key = Self::daily_likes_key(from_id);
+        let count: u64 = redis.incr(&key, 1)?;
+        if count == 1 {
+            let _: () = redis.expire(&key, DAILY_LIKES_EXPIRATION_SECONDS)?;
+        }
+        Ok(count)
+    }
+
+    async fn decrement_daily_like_count(&self, from_id: &str) -> Result<(), Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let _: i64 = redis.decr(Self::daily_likes_key(from_id), 1)?;
+        Ok(())
     }
 
     async fn dislike(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
@@ -85,13 +109,13 @@ impl MatchmakingRepository for MatchmakingRepositoryImpl {
             pipe
                 .sadd(from_id, to_id)
                 .expire(from_id, 60 * 60 * 12 * 30)
-                .query(con)
+                .query::<Option<()>>(con)
         })?;
 
         let message = [to_id, from_id].join(",");
         let _ = self.message_queue.publish(EXCHANGE_NAME, DISLIKE_ROUTING_KEY, message.as_bytes());
 
-        Err(make_error!("unable to dislike"))
+        Ok(())
     }
 
     async fn backtrack(&self, id: &str) -> Result<String, Error> {
@@ -99,6 +123,53 @@ impl MatchmakingRepository for MatchmakingRepositoryImpl {
         if let Ok(backtracked_id) = redis.spop::<&str, String>(id) {
             return Ok(backtracked_id);
         }
-        Err(make_error!("unable to backtrack"))
+        Err(DomainError::failed_precondition("nothing to backtrack"))
+    }
+
+    async fn delete_likes(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
+        let filter = doc! {
+            "$or": [
+                { "from_id": from_id, "to_id": to_id },
+                { "from_id": to_id, "to_id": from_id }
+            ]
+        };
+        self.collection.delete_many(filter, None).await?;
+        let mut redis = self.redis_client.get_connection()?;
+        let _: () = redis.srem(from_id, to_id)?;
+        let _: () = redis.srem(to_id, from_id)?;
+        Ok(())
+    }
+
+    async fn erase_likes(&self, id: &str) -> Result<(), Error> {
+        let filter = doc! {
+            "$or": [
+                { "from_id": id },
+                { "to_id": id }
+            ]
+        };
+        self.collection.delete_many(filter, None).await?;
+        let mut redis = self.redis_client.get_connection()?;
+        let _: () = redis.del(&[String::from(id), Self::daily_likes_key(id)])?;
+        Ok(())
+    }
+
+    async fn export_likes(&self, id: &str) -> Result<(Vec<Like>, Vec<Like>), Error> {
+        let (mut given, mut received): (Vec<Like>, Vec<Like>) = (vec![], vec![]);
+        let filter = doc! {
+            "$or": [
+                { "from_id": id },
+                { "to_id": id }
+            ]
+        };
+        let options = FindOptions::builder().sort(doc! { "created_at": 1 }).build();
+        let mut cursor = self.collection.find(filter, options).await?;
+        while let Some(like) = cursor.try_next().await? {
+            if like.from_id == id {
+                given.push(like)
+            } else {
+                received.push(like)
+            }
+        }
+        Ok((given, received))
     }
 }
\ No newline at end of file
@@ -1,17 +1,29 @@
+use std::sync::Arc;
+
 use tonic::{Request, Response, Status};
 
 use crate::matchmaking::interactor::MatchmakingInteractor;
-use crate::matchmaking::pb::{BacktrackRequest, BacktrackResponse, CheckIdsRequest, CheckIdsResponse, DislikeRequest, DislikeResponse, GetLikesRequest, GetLikesResponse, LikeRequest, LikeResponse};
+use crate::matchmaking::pb::{BacktrackRequest, BacktrackResponse, CheckIdsRequest, CheckIdsResponse, DislikeRequest, DislikeResponse, ExportDataRequest, ExportDataResponse, GetLikesRequest, GetLikesResponse, LikeRequest, LikeResponse};
 use crate::matchmaking::pb::matchmaking_service_server::MatchmakingService;
 
+const ACCOUNT_ENTITLEMENTS_KEY: &str = "x-account-entitlements";
+const UNLIMITED_LIKES_ENTITLEMENT: &str = "unlimited_likes";
+
 pub struct MatchmakingServiceImpl {
-    interactor: Box<dyn MatchmakingInteractor + Send + Sync>,
+    interactor: Arc<Box<dyn MatchmakingInteractor + Send + Sync>>,
 }
 
 impl MatchmakingServiceImpl {
-    pub fn new(interactor: Box<dyn MatchmakingInteractor + Send + Sync>) -> impl MatchmakingService {
+    pub fn new(interactor: Arc<Box<dyn MatchmakingInteractor + Send + Sync>>) -> impl MatchmakingService {
         MatchmakingServiceImpl { interactor }
     }
+
+    fn has_entitlement<T>(request: &Request<T>, entitlement: &str) -> bool {
+        request.metadata()
+            .get(ACCOUNT_ENTITLEMENTS_KEY)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.split(',').any(|value| value == entitlement))
+    }
 }
 
 #[tonic::async_trait]
@@ -24,7 +36,7 @@ impl MatchmakingService for MatchmakingServiceImpl {
 
         match self.interactor.check_ids(&from_id, to_ids).await {
             Ok(values) => Ok(Response::new(CheckIdsResponse { values })),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -36,19 +48,20 @@ impl MatchmakingService for MatchmakingServiceImpl {
 
         match self.interactor.get_likes(&id, skip, limit).await {
             Ok(identifiers) => Ok(Response::new(GetLikesResponse { identifiers })),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn like(&self, request: Request<LikeRequest>) -> Result<Response<LikeResponse>, Status> {
+        let has_unlimited_likes = Self::has_entitlement(&request, UNLIMITED_LIKES_ENTITLEMENT);
         let LikeRequest { from_id, to_id } = request.into_inner();
         if from_id.is_empty() || to_id.is_empty() {
             return status::Status::invalid_arguments(vec!["from_id", "to_id"]);
         }
 
-        match self.interactor.like(&from_id, &to_id).await {
+        match self.interactor.like(&from_id, &to_id, has_unlimited_likes).await {
             Ok(has_match) => Ok(Response::new(LikeResponse { has_match })),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -60,7 +73,7 @@ impl MatchmakingService for MatchmakingServiceImpl {
 
         match self.interactor.dislike(&from_id, &to_id).await {
             Ok(_) => Ok(Response::new(DislikeResponse {})),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -72,7 +85,19 @@ impl MatchmakingService for MatchmakingServiceImpl {
 
         match self.interactor.backtrack(id).await {
             Ok(id) => Ok(Response::new(BacktrackResponse { id })),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn export_data(&self, request: Request<ExportDataRequest>) -> Result<Response<ExportDataResponse>, Status> {
+        let ExportDataRequest { id } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        match self.interactor.export_data(&id).await {
+            Ok(data) => Ok(Response::new(ExportDataResponse { data })),
+            Err(error) => status::Status::from_error(error)
         }
     }
 }
\ No newline at end of file
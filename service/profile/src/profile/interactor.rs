@@ -34,6 +34,8 @@ pub trait ProfileInteractor {
         bio: Option<String>,
     ) -> Result<Profile, Error>;
     async fn delete_profile(&self, id: &str) -> Result<String, Error>;
+    async fn erase_account(&self, account_id: &str) -> Result<(), Error>;
+    async fn export_data(&self, account_id: &str) -> Result<Vec<u8>, Error>;
 }
 
 pub struct ProfileInteractorImpl {
@@ -72,4 +74,13 @@ impl ProfileInteractor for ProfileInteractorImpl {
     async fn delete_profile(&self, id: &str) -> Result<String, Error> {
         self.repository.delete_profile(id).await
     }
+
+    async fn erase_account(&self, account_id: &str) -> Result<(), Error> {
+        self.repository.erase_profile(account_id).await
+    }
+
+    async fn export_data(&self, account_id: &str) -> Result<Vec<u8>, Error> {
+        let profile = self.repository.get_profile_by_id(account_id).await?;
+        Ok(serde_json::to_vec(&profile)?)
+    }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+pub mod amqp;
 pub mod entity;
 pub mod mapper;
 pub mod interactor;
@@ -3,7 +3,7 @@ use mongodb::{bson, Collection};
 use mongodb::bson::{bson, doc};
 use mongodb::options::FindOptions;
 
-use error::make_error;
+use error::{DomainError, make_error};
 
 use crate::profile::entity::{Basics, Filter, Profile};
 
@@ -40,6 +40,7 @@ pub trait ProfileRepository {
         bio: Option<String>,
     ) -> Result<Profile, Error>;
     async fn delete_profile(&self, id: &str) -> Result<String, Error>;
+    async fn erase_profile(&self, id: &str) -> Result<(), Error>;
 }
 
 pub struct ProfileRepositoryImpl {
@@ -111,10 +112,7 @@ impl ProfileRepository for ProfileRepositoryImpl {
     }
 
     async fn get_profile_by_id(&self, id: &str) -> Result<Option<Profile>, Error> {
-        if let Ok(profile) = self.collection.find_one(doc! { "_id": id }, None).await {
-            return Ok(profile);
-        }
-        Err(make_error!("unable to get profile by id"))
+        Ok(self.collection.find_one(doc! { "_id": id }, None).await?)
     }
 
     async fn create_profile(&self, id: &str, name: &str, basics: Basics, bio: Option<String>) -> Result<String, Error> {
@@ -142,13 +140,18 @@ impl ProfileRepository for ProfileRepositoryImpl {
                 return Ok(profile);
             }
         }
-        Err(make_error!("unable to update profile"))
+        Err(DomainError::not_found("profile not found"))
     }
 
     async fn delete_profile(&self, id: &str) -> Result<String, Error> {
         if self.collection.delete_one(doc! { "_id": id }, None).await?.deleted_count > 0 {
             return Ok(String::from(id));
         }
-        Err(make_error!("unable to delete profile"))
+        Err(DomainError::not_found("profile not found"))
+    }
+
+    async fn erase_profile(&self, id: &str) -> Result<(), Error> {
+        self.collection.delete_one(doc! { "_id": id }, None).await?;
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,15 +1,17 @@
+use std::sync::Arc;
+
 use tonic::{Request, Response, Status};
 
 use crate::profile::interactor::ProfileInteractor;
-use crate::profile::pb::{CreateProfileRequest, CreateProfileResponse, DeleteProfileRequest, DeleteProfileResponse, GetProfileByIdRequest, GetProfileByIdResponse, GetProfilesRequest, GetProfilesResponse, GetRandomProfilesRequest, GetRandomProfilesResponse, UpdateProfileRequest, UpdateProfileResponse};
+use crate::profile::pb::{CreateProfileRequest, CreateProfileResponse, DeleteProfileRequest, DeleteProfileResponse, ExportDataRequest, ExportDataResponse, GetProfileByIdRequest, GetProfileByIdResponse, GetProfilesRequest, GetProfilesResponse, GetRandomProfilesRequest, GetRandomProfilesResponse, UpdateProfileRequest, UpdateProfileResponse};
 use crate::profile::pb::profile_service_server::ProfileService;
 
 pub struct ProfileServiceImpl {
-    interactor: Box<dyn ProfileInteractor + Send + Sync>,
+    interactor: Arc<Box<dyn ProfileInteractor + Send + Sync>>,
 }
 
 impl ProfileServiceImpl {
-    pub fn new(interactor: Box<dyn ProfileInteractor + Send + Sync>) -> impl ProfileService {
+    pub fn new(interactor: Arc<Box<dyn ProfileInteractor + Send + Sync>>) -> impl ProfileService {
         ProfileServiceImpl { interactor }
     }
 }
@@ -24,7 +26,7 @@ impl ProfileService for ProfileServiceImpl {
 
         match self.interactor.get_random_profiles(&excepted_id, filter.unwrap().into(), count).await {
             Ok(profiles) => Ok(Response::new(GetRandomProfilesResponse { profiles: profiles.into_iter().map(|profile| profile.into()).collect() })),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -36,7 +38,7 @@ impl ProfileService for ProfileServiceImpl {
 
         match self.interactor.get_profiles(&excepted_id, filter.unwrap().into(), skip, limit).await {
             Ok(profiles) => Ok(Response::new(GetProfilesResponse { profiles: profiles.into_iter().map(|profile| profile.into()).collect() })),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -52,7 +54,7 @@ impl ProfileService for ProfileServiceImpl {
                     GetProfileByIdResponse { profile: profile.map(|profile| profile.into()) }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -70,7 +72,7 @@ impl ProfileService for ProfileServiceImpl {
                     CreateProfileResponse { id: String::from(id) }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -87,7 +89,7 @@ impl ProfileService for ProfileServiceImpl {
                     UpdateProfileResponse { profile: Some(profile.into()) }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -99,7 +101,19 @@ impl ProfileService for ProfileServiceImpl {
 
         match self.interactor.delete_profile(&id).await {
             Ok(id) => Ok(Response::new(DeleteProfileResponse { id: String::from(id) })),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn export_data(&self, request: Request<ExportDataRequest>) -> Result<Response<ExportDataResponse>, Status> {
+        let ExportDataRequest { id } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        match self.interactor.export_data(&id).await {
+            Ok(data) => Ok(Response::new(ExportDataResponse { data })),
+            Err(error) => status::Status::from_error(error)
         }
     }
 }
\ No newline at end of file
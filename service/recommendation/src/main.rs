@@ -12,6 +12,7 @@ mod matchmaking;
 mod recommendation;
 
 const SERVICE_NAME: &str = "recommendation";
+const BLOCK_COLLECTION: &str = "blocks";
 const MATCHMAKING_SERVICE_NAME: &str = "matchmaking";
 const PROFILE_SERVICE_NAME: &str = "profile";
 
@@ -21,6 +22,10 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let matchmaking_cfg = configuration::Config::default(MATCHMAKING_SERVICE_NAME)?;
     let profile_cfg = configuration::Config::default(PROFILE_SERVICE_NAME)?;
 
+    let mongodb = mongodb::Client::with_uri_str(format!("mongodb://{}:{}", cfg.mongo_hostname.unwrap(), cfg.mongo_port.unwrap())).await?;
+    let database = mongodb.database(SERVICE_NAME);
+    let collection = database.collection(BLOCK_COLLECTION);
+
     let redis_client = redis::Client::open(format!("redis://{}:{}", cfg.redis_hostname.unwrap(), cfg.redis_port.unwrap()))?;
 
     let create_channel_url: fn(&str, &str) -> &'static str = |hostname, port| Box::leak(format!("https://{}:{}", hostname, port).into_boxed_str());
@@ -35,14 +40,29 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let profile_client = ProfileServiceClient::new(profile_channel);
     let profile_api = profile::api::ProfileApiImpl::new(profile_client);
 
-    let repository = recommendation::repository::RecommendationRepositoryImpl::new(redis_client, matchmaking_api, profile_api).await;
+    let repository = recommendation::repository::RecommendationRepositoryImpl::new(collection, redis_client, matchmaking_api, profile_api).await;
     let interactor = Arc::new(recommendation::interactor::RecommendationInteractorImpl::new(repository));
     let service = recommendation::service::RecommendationServiceImpl::new(Arc::clone(&interactor));
 
-    let message_queue = amqp::MessageQueue::connect(&cfg.amqp_hostname.unwrap(), &cfg.amqp_port.unwrap()).await?;
+    let amqp_hostname = cfg.amqp_hostname.unwrap();
+    let amqp_port = cfg.amqp_port.unwrap();
+
+    let message_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
     let mut message_queue_handler = recommendation::amqp::MessageQueueHandler::new(Arc::clone(&interactor), message_queue);
     message_queue_handler.consume_new_chats().await;
 
+    let blocked_users_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut blocked_users_handler = recommendation::amqp::MessageQueueHandler::new(Arc::clone(&interactor), blocked_users_queue);
+    blocked_users_handler.consume_blocked_users().await;
+
+    let unblocked_users_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut unblocked_users_handler = recommendation::amqp::MessageQueueHandler::new(Arc::clone(&interactor), unblocked_users_queue);
+    unblocked_users_handler.consume_unblocked_users().await;
+
+    let deleted_accounts_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut deleted_accounts_handler = recommendation::amqp::MessageQueueHandler::new(Arc::clone(&interactor), deleted_accounts_queue);
+    deleted_accounts_handler.consume_deleted_accounts().await;
+
     let server_addr = SocketAddr::new(cfg.service_hostname.unwrap().parse().unwrap(), cfg.service_port.unwrap().parse().unwrap());
 
     println!("Service '{}' started at address: {}", SERVICE_NAME, server_addr);
@@ -1,7 +1,7 @@
 use tonic::Request;
 use tonic::transport::Channel;
 
-use error::make_error;
+use error::DomainError;
 
 use crate::matchmaking::pb::{CheckIdsRequest, CheckIdsResponse};
 use crate::matchmaking::pb::matchmaking_service_client::MatchmakingServiceClient;
@@ -32,10 +32,12 @@ impl MatchmakingApi for MatchmakingApiImpl {
                 to_ids,
             }
         );
-        if let Ok(response) = self.client.clone().check_ids(request).await {
-            let CheckIdsResponse { values } = response.into_inner();
-            return Ok(values);
+        match self.client.clone().check_ids(request).await {
+            Ok(response) => {
+                let CheckIdsResponse { values } = response.into_inner();
+                Ok(values)
+            }
+            Err(status) => Err(DomainError::unavailable(Box::new(status)))
         }
-        Err(make_error!("unable to check identifiers"))
     }
 }
\ No newline at end of file
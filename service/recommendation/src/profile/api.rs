@@ -1,7 +1,7 @@
 use tonic::Request;
 use tonic::transport::Channel;
 
-use error::make_error;
+use error::DomainError;
 
 use crate::profile::entity::{Filter, Profile};
 use crate::profile::pb::{GetRandomProfilesRequest, GetRandomProfilesResponse};
@@ -34,10 +34,12 @@ impl ProfileApi for ProfileApiImpl {
                 count,
             }
         );
-        if let Ok(response) = self.client.clone().get_random_profiles(request).await {
-            let GetRandomProfilesResponse { profiles } = response.into_inner();
-            return Ok(profiles.into_iter().map(|profile| profile.into()).collect());
+        match self.client.clone().get_random_profiles(request).await {
+            Ok(response) => {
+                let GetRandomProfilesResponse { profiles } = response.into_inner();
+                Ok(profiles.into_iter().map(|profile| profile.into()).collect())
+            }
+            Err(status) => Err(DomainError::unavailable(Box::new(status)))
         }
-        Err(make_error!("unable to get profiles"))
     }
 }
\ No newline at end of file
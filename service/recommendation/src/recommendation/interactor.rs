@@ -7,6 +7,9 @@ type Error = Box<dyn std::error::Error + Send + Sync>;
 pub trait RecommendationInteractor {
     async fn get_candidates(&self, excepted_id: &str, filter: Filter) -> Result<Vec<String>, Error>;
     async fn delete_candidate(&self, excepted_id: &str, candidate_id: &str) -> Result<(), Error>;
+    async fn block_pair(&self, from_id: &str, to_id: &str) -> Result<(), Error>;
+    async fn unblock_pair(&self, from_id: &str, to_id: &str) -> Result<(), Error>;
+    async fn erase_account(&self, account_id: &str) -> Result<(), Error>;
 }
 
 pub struct RecommendationInteractorImpl {
@@ -28,4 +31,16 @@ impl RecommendationInteractor for RecommendationInteractorImpl {
     async fn delete_candidate(&self, excepted_id: &str, candidate_id: &str) -> Result<(), Error> {
         self.repository.delete_candidate(excepted_id, candidate_id).await
     }
+
+    async fn block_pair(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
+        self.repository.block_pair(from_id, to_id).await
+    }
+
+    async fn unblock_pair(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
+        self.repository.unblock_pair(from_id, to_id).await
+    }
+
+    async fn erase_account(&self, account_id: &str) -> Result<(), Error> {
+        self.repository.erase_candidates(account_id).await
+    }
 }
\ No newline at end of file
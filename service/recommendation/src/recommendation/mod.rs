@@ -1,3 +1,4 @@
+pub mod entity;
 pub mod mapper;
 pub mod repository;
 pub mod interactor;
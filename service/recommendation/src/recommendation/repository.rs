@@ -1,20 +1,36 @@
-use redis::{Client as RedisClient, Commands, transaction};
+use std::collections::{HashMap, HashSet};
+
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::Collection;
+use mongodb::options::ReplaceOptions;
+use redis::{Client as RedisClient, Commands, Connection, transaction};
 
 use crate::matchmaking::api::MatchmakingApi;
 use crate::profile::api::ProfileApi;
 use crate::profile::entity::Filter;
+use crate::recommendation::entity::Block;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
 const CHUNK_SIZE: u64 = 10;
+const BLOCKS_EXPIRATION_SECONDS: i64 = 60 * 60 * 12;
+
+fn blocks_key(id: &str) -> String {
+    format!("blocks:{}", id)
+}
 
 #[async_trait::async_trait]
 pub trait RecommendationRepository {
     async fn get_candidate_identifiers(&self, excepted_id: &str, filter: Filter) -> Result<Vec<String>, Error>;
     async fn delete_candidate(&self, excepted_id: &str, candidate_id: &str) -> Result<(), Error>;
+    async fn block_pair(&self, from_id: &str, to_id: &str) -> Result<(), Error>;
+    async fn unblock_pair(&self, from_id: &str, to_id: &str) -> Result<(), Error>;
+    async fn erase_candidates(&self, id: &str) -> Result<(), Error>;
 }
 
 pub struct RecommendationRepositoryImpl {
+    collection: Collection<Block>,
     redis_client: RedisClient,
     matchmaking_api: Box<dyn MatchmakingApi + Send + Sync>,
     profile_api: Box<dyn ProfileApi + Send + Sync>,
@@ -22,11 +38,34 @@ pub struct RecommendationRepositoryImpl {
 
 impl RecommendationRepositoryImpl {
     pub async fn new(
+        collection: Collection<Block>,
         redis_client: RedisClient,
         matchmaking_api: Box<dyn MatchmakingApi + Send + Sync>,
         profile_api: Box<dyn ProfileApi + Send + Sync>,
     ) -> Box<dyn RecommendationRepository + Send + Sync> {
-        Box::new(RecommendationRepositoryImpl { redis_client, matchmaking_api, profile_api })
+        Box::new(RecommendationRepositoryImpl { collection, redis_client, matchmaking_api, profile_api })
+    }
+
+    async fn get_blocks(&self, id: &str) -> Result<Vec<Block>, Error> {
+        let filter = doc! { "$or": [{ "from_id": id }, { "to_id": id }] };
+        Ok(self.collection.find(filter, None).await?.try_collect().await?)
+    }
+
+    async fn get_blocked_identifiers(&self, redis: &mut Connection, id: &str) -> Result<HashSet<String>, Error> {
+        let mut blocked_identifiers: HashSet<String> = redis.smembers(blocks_key(id))?;
+        if blocked_identifiers.is_empty() {
+            // the own identifier keeps the cached set non-empty for users without blocks
+            blocked_identifiers = self.get_blocks(id).await?.into_iter()
+                .map(|block| if block.from_id == id { block.to_id } else { block.from_id })
+                .chain([String::from(id)])
+                .collect();
+            redis::pipe()
+                .atomic()
+                .sadd(blocks_key(id), &blocked_identifiers)
+                .expire(blocks_key(id), BLOCKS_EXPIRATION_SECONDS)
+                .query::<()>(redis)?;
+        }
+        Ok(blocked_identifiers)
     }
 }
 
@@ -34,11 +73,16 @@ impl RecommendationRepositoryImpl {
 impl RecommendationRepository for RecommendationRepositoryImpl {
     async fn get_candidate_identifiers(&self, excepted_id: &str, filter: Filter) -> Result<Vec<String>, Error> {
         let mut redis = self.redis_client.get_connection()?;
+        let blocked_identifiers = self.get_blocked_identifiers(&mut redis, excepted_id).await?;
         // try to get cached candidate identifiers
         if let Ok(count) = redis.hlen::<&str, u64>(excepted_id) {
             if count >= CHUNK_SIZE {
-                if let Ok(candidate_identifiers) = redis.hgetall::<&str, Vec<String>>(excepted_id) {
-                    return Ok(candidate_identifiers.into_iter().take(CHUNK_SIZE as usize).collect());
+                if let Ok(candidate_identifiers) = redis.hgetall::<&str, HashMap<String, bool>>(excepted_id) {
+                    return Ok(candidate_identifiers.into_iter()
+                        .filter(|(id, liked_or_disliked)| !liked_or_disliked && !blocked_identifiers.contains(id))
+                        .map(|(id, _)| id)
+                        .take(CHUNK_SIZE as usize)
+                        .collect());
                 }
             }
         }
@@ -48,7 +92,14 @@ impl RecommendationRepository for RecommendationRepositoryImpl {
             // no profiles to check
             return Ok(vec![]);
         }
-        let profile_identifiers: Vec<String> = profiles.into_iter().map(|profile| profile.id).collect();
+        let profile_identifiers: Vec<String> = profiles.into_iter()
+            .map(|profile| profile.id)
+            .filter(|id| !blocked_identifiers.contains(id))
+            .collect();
+        if profile_identifiers.is_empty() {
+            // all profiles are blocked
+            return Ok(vec![]);
+        }
         let checked_identifiers: Vec<bool> = self.matchmaking_api.check_ids(excepted_id, profile_identifiers.clone()).await?;
         let mut candidate_identifiers: Vec<String> = vec![];
         for (id, liked_or_disliked) in profile_identifiers.into_iter().zip(checked_identifiers.into_iter()).collect::<Vec<(String, bool)>>() {
@@ -69,7 +120,39 @@ impl RecommendationRepository for RecommendationRepositoryImpl {
 
     async fn delete_candidate(&self, excepted_id: &str, candidate_id: &str) -> Result<(), Error> {
         let mut redis = self.redis_client.get_connection()?;
-        let _ = redis.hdel(excepted_id, candidate_id)?;
+        let _: () = redis.hdel(excepted_id, candidate_id)?;
+        Ok(())
+    }
+
+    async fn block_pair(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
+        let options = ReplaceOptions::builder().upsert(true).build();
+        self.collection.replace_one(doc! { "_id": Block::make_id(from_id, to_id) }, Block::new(from_id, to_id), options).await?;
+        let mut redis = self.redis_client.get_connection()?;
+        redis::pipe()
+            .atomic()
+            .del(&[blocks_key(from_id), blocks_key(to_id)])
+            .hdel(from_id, to_id)
+            .hdel(to_id, from_id)
+            .query::<()>(&mut redis)?;
+        Ok(())
+    }
+
+    async fn unblock_pair(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
+        self.collection.delete_one(doc! { "_id": Block::make_id(from_id, to_id) }, None).await?;
+        let mut redis = self.redis_client.get_connection()?;
+        let _: () = redis.del(&[blocks_key(from_id), blocks_key(to_id)])?;
+        Ok(())
+    }
+
+    async fn erase_candidates(&self, id: &str) -> Result<(), Error> {
+        let blocks = self.get_blocks(id).await?;
+        self.collection.delete_many(doc! { "$or": [{ "from_id": id }, { "to_id": id }] }, None).await?;
+        let mut keys: Vec<String> = blocks.into_iter()
+            .map(|block| if block.from_id == id { blocks_key(&block.to_id) } else { blocks_key(&block.from_id) })
+            .collect();
+        keys.extend([String::from(id), blocks_key(id)]);
+        let mut redis = self.redis_client.get_connection()?;
+        let _: () = redis.del(keys)?;
         Ok(())
     }
 }
\ No newline at end of file
@@ -26,7 +26,7 @@ impl RecommendationService for RecommendationServiceImpl {
 
         match self.interactor.get_candidates(&excepted_id, filter.unwrap().into()).await {
             Ok(candidate_identifiers) => Ok(Response::new(GetCandidatesResponse { candidate_identifiers })),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 }
\ No newline at end of file
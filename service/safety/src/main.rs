@@ -1,14 +1,18 @@
 use std::error::Error;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use tonic::transport::Server;
 
+use crate::report::entity::Report;
 use crate::safety::entity::BlockedUser;
 
+mod report;
 mod safety;
 
 const SERVICE_NAME: &str = "safety";
 const BLOCKED_USERS_COLLECTION: &str = "blocked_users";
+const REPORTS_COLLECTION: &str = "reports";
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
@@ -18,9 +22,21 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let database = mongodb.database(SERVICE_NAME);
     let collection = database.collection::<BlockedUser>(BLOCKED_USERS_COLLECTION);
 
-    let repository = safety::repository::SafetyRepositoryImpl::new(collection);
-    let interactor = safety::interactor::SafetyInteractorImpl::new(repository);
-    let service = safety::service::SafetyServiceImpl::new(interactor);
+    let reports_collection = database.collection::<Report>(REPORTS_COLLECTION);
+
+    let amqp_hostname = cfg.amqp_hostname.unwrap();
+    let amqp_port = cfg.amqp_port.unwrap();
+
+    let message_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+
+    let repository = safety::repository::SafetyRepositoryImpl::new(collection, message_queue.clone());
+    let report_repository = report::repository::ReportRepositoryImpl::new(reports_collection, message_queue);
+    let interactor = Arc::new(safety::interactor::SafetyInteractorImpl::new(repository, report_repository));
+    let service = safety::service::SafetyServiceImpl::new(Arc::clone(&interactor));
+
+    let deleted_accounts_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut deleted_accounts_handler = safety::amqp::MessageQueueHandler::new(Arc::clone(&interactor), deleted_accounts_queue);
+    deleted_accounts_handler.consume_deleted_accounts().await;
 
     let server_addr = SocketAddr::new(cfg.service_hostname.unwrap().parse().unwrap(), cfg.service_port.unwrap().parse().unwrap());
 
@@ -0,0 +1,66 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum Reason {
+    Spam,
+    Harassment,
+    InappropriateContent,
+    FakeProfile,
+    Underage,
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum Status {
+    Pending,
+    Resolved,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum Action {
+    Dismiss,
+    Warn,
+    Suspend,
+    Ban,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Report {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub from_id: String,
+    pub to_id: String,
+    pub reason: Reason,
+    pub comment: String,
+    pub message_ids: Vec<String>,
+    pub status: Status,
+    pub action: Option<Action>,
+    pub moderator_id: Option<String>,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+impl Report {
+    pub fn timestamp_now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64
+    }
+
+    pub fn new(id: &str, from_id: &str, to_id: &str, reason: Reason, comment: &str, message_ids: Vec<String>) -> Self {
+        let now = Self::timestamp_now();
+        Self {
+            id: String::from(id),
+            from_id: String::from(from_id),
+            to_id: String::from(to_id),
+            reason,
+            comment: String::from(comment),
+            message_ids,
+            status: Status::Pending,
+            action: None,
+            moderator_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+use mongodb::bson::Bson;
+
+use error::DomainError;
+
+use crate::report::entity::{Action, Reason, Report, Status};
+use crate::safety::pb::{Report as ReportMessage, ReportAction, ReportReason, ReportStatus};
+
+impl From<Status> for Bson {
+    fn from(value: Status) -> Self {
+        let status = match value {
+            Status::Pending => "Pending",
+            Status::Resolved => "Resolved",
+        };
+        Bson::String(status.to_owned())
+    }
+}
+
+impl From<Action> for Bson {
+    fn from(value: Action) -> Self {
+        let action = match value {
+            Action::Dismiss => "Dismiss",
+            Action::Warn => "Warn",
+            Action::Suspend => "Suspend",
+            Action::Ban => "Ban",
+        };
+        Bson::String(action.to_owned())
+    }
+}
+
+impl From<ReportReason> for Reason {
+    fn from(value: ReportReason) -> Self {
+        match value {
+            ReportReason::Spam => Reason::Spam,
+            ReportReason::Harassment => Reason::Harassment,
+            ReportReason::InappropriateContent => Reason::InappropriateContent,
+            ReportReason::FakeProfile => Reason::FakeProfile,
+            ReportReason::Underage => Reason::Underage,
+            ReportReason::Other => Reason::Other,
+        }
+    }
+}
+
+impl From<ReportStatus> for Status {
+    fn from(value: ReportStatus) -> Self {
+        match value {
+            ReportStatus::Pending => Status::Pending,
+            ReportStatus::Resolved => Status::Resolved,
+        }
+    }
+}
+
+impl TryFrom<ReportAction> for Action {
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn try_from(value: ReportAction) -> Result<Self, Self::Error> {
+        match value {
+            ReportAction::Unspecified => Err(DomainError::invalid_argument("report action is unspecified")),
+            ReportAction::Dismiss => Ok(Action::Dismiss),
+            ReportAction::Warn => Ok(Action::Warn),
+            ReportAction::Suspend => Ok(Action::Suspend),
+            ReportAction::Ban => Ok(Action::Ban),
+        }
+    }
+}
+
+impl From<Action> for ReportAction {
+    fn from(value: Action) -> Self {
+        match value {
+            Action::Dismiss => ReportAction::Dismiss,
+            Action::Warn => ReportAction::Warn,
+            Action::Suspend => ReportAction::Suspend,
+            Action::Ban => ReportAction::Ban,
+        }
+    }
+}
+
+impl From<Report> for ReportMessage {
+    fn from(value: Report) -> Self {
+        Self {
+            id: value.id,
+            from_id: value.from_id,
+            to_id: value.to_id,
+            reason: value.reason as i32,
+            comment: value.comment,
+            message_ids: value.message_ids,
+            status: value.status as i32,
+            action: value.action.map(|action| ReportAction::from(action) as i32),
+            moderator_id: value.moderator_id.unwrap_or_default(),
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+pub mod entity;
+pub mod mapper;
+pub mod repository;
\ No newline at end of file
@@ -0,0 +1,136 @@
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::bson::oid::ObjectId;
+use mongodb::Collection;
+use mongodb::options::FindOptions;
+
+use amqp::MessageQueue;
+use error::DomainError;
+
+use crate::report::entity::{Action, Reason, Report, Status};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+const EXCHANGE_NAME: &str = "action";
+const SUSPENDED_ROUTING_KEY: &str = "user.suspended";
+const BANNED_ROUTING_KEY: &str = "user.banned";
+const ANONYMIZED_ID: &str = "deleted";
+
+#[async_trait::async_trait]
+pub trait ReportRepository {
+    async fn get_reports(
+        &self,
+        status: Option<Status>,
+        skip: u64,
+        limit: u64,
+    ) -> Result<Vec<Report>, Error>;
+    async fn get_report_by_id(&self, report_id: &str) -> Result<Option<Report>, Error>;
+    async fn create_report(
+        &self,
+        from_id: &str,
+        to_id: &str,
+        reason: Reason,
+        comment: &str,
+        message_ids: Vec<String>,
+    ) -> Result<String, Error>;
+    async fn resolve_report(
+        &self,
+        report_id: &str,
+        moderator_id: &str,
+        action: Action,
+        suspension_duration: u64,
+    ) -> Result<Report, Error>;
+    async fn anonymize_reports(&self, id: &str) -> Result<(), Error>;
+    async fn get_filed_reports(&self, from_id: &str) -> Result<Vec<Report>, Error>;
+}
+
+pub struct ReportRepositoryImpl {
+    collection: Collection<Report>,
+    message_queue: MessageQueue,
+}
+
+impl ReportRepositoryImpl {
+    pub fn new(collection: Collection<Report>, message_queue: MessageQueue) -> Box<dyn ReportRepository + Send + Sync> {
+        Box::new(ReportRepositoryImpl { collection, message_queue })
+    }
+}
+
+#[async_trait::async_trait]
+impl ReportRepository for ReportRepositoryImpl {
+    async fn get_reports(&self, status: Option<Status>, skip: u64, limit: u64) -> Result<Vec<Report>, Error> {
+        let filter = match status {
+            Some(status) => doc! { "status": status },
+            None => doc! {}
+        };
+        let mut reports: Vec<Report> = vec![];
+        let options = FindOptions::builder().sort(doc! { "created_at": 1 }).skip(skip).limit(limit.try_into().ok()).build();
+        let mut cursor = self.collection.find(filter, options).await?;
+        while let Some(report) = cursor.try_next().await? {
+            reports.push(report)
+        }
+        Ok(reports)
+    }
+
+    async fn get_report_by_id(&self, report_id: &str) -> Result<Option<Report>, Error> {
+        Ok(self.collection.find_one(doc! { "_id": report_id }, None).await?)
+    }
+
+    async fn create_report(&self, from_id: &str, to_id: &str, reason: Reason, comment: &str, message_ids: Vec<String>) -> Result<String, Error> {
+        let id = ObjectId::new().to_hex();
+        let report = Report::new(&id, from_id, to_id, reason, comment, message_ids);
+        self.collection.insert_one(report, None).await?;
+        Ok(id)
+    }
+
+    async fn resolve_report(&self, report_id: &str, moderator_id: &str, action: Action, suspension_duration: u64) -> Result<Report, Error> {
+        let report = self.collection.find_one(doc! { "_id": report_id, "status": Status::Pending }, None).await?
+            .ok_or_else(|| DomainError::failed_precondition("report is not pending"))?;
+        let published = match action {
+            Action::Dismiss | Action::Warn => None,
+            Action::Suspend => {
+                let suspended_until = Report::timestamp_now() + suspension_duration;
+                Some((SUSPENDED_ROUTING_KEY, [report.to_id.clone(), suspended_until.to_string()].join(",")))
+            }
+            Action::Ban => Some((BANNED_ROUTING_KEY, report.to_id.clone())),
+        };
+        // the action is published before the report is resolved so that a failure leaves it pending to be retried
+        if let Some((routing_key, message)) = published {
+            self.message_queue.publish(EXCHANGE_NAME, routing_key, message.as_bytes()).await.map_err(DomainError::unavailable)?;
+        }
+        let document = doc! {
+            "status": Status::Resolved,
+            "action": action.clone(),
+            "moderator_id": moderator_id,
+            "updated_at": Report::timestamp_now() as i64
+        };
+        let filter = doc! { "_id": report_id, "status": Status::Pending };
+        if self.collection.update_one(filter, doc! { "$set": document }, None).await?.modified_count > 0 {
+            if let Some(report) = self.get_report_by_id(report_id).await? {
+                return Ok(report);
+            }
+        }
+        Err(DomainError::failed_precondition("report is not pending"))
+    }
+
+    async fn anonymize_reports(&self, id: &str) -> Result<(), Error> {
+        let updates = [
+            (doc! { "from_id": id }, doc! { "from_id": ANONYMIZED_ID, "comment": "" }),
+            (doc! { "to_id": id }, doc! { "to_id": ANONYMIZED_ID }),
+            (doc! { "moderator_id": id }, doc! { "moderator_id": ANONYMIZED_ID }),
+        ];
+        for (filter, document) in updates {
+            self.collection.update_many(filter, doc! { "$set": document }, None).await?;
+        }
+        Ok(())
+    }
+
+    async fn get_filed_reports(&self, from_id: &str) -> Result<Vec<Report>, Error> {
+        let mut reports: Vec<Report> = vec![];
+        let options = FindOptions::builder().sort(doc! { "created_at": 1 }).build();
+        let mut cursor = self.collection.find(doc! { "from_id": from_id }, options).await?;
+        while let Some(report) = cursor.try_next().await? {
+            reports.push(report)
+        }
+        Ok(reports)
+    }
+}
\ No newline at end of file
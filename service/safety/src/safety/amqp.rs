@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use amqp::MessageQueue;
+
+use crate::safety::interactor::SafetyInteractor;
+
+const EXCHANGE_NAME: &str = "action";
+const DELETED_QUEUE_NAME: &str = "safety.account.deleted";
+const DELETED_ROUTING_KEY: &str = "account.deleted";
+const ERASED_ROUTING_KEY: &str = "account.erased";
+const SERVICE_NAME: &str = "safety";
+
+pub struct MessageQueueHandler {
+    interactor: Arc<Box<dyn SafetyInteractor + Send + Sync>>,
+    message_queue: MessageQueue,
+}
+
+impl MessageQueueHandler {
+    pub fn new(interactor: Arc<Box<dyn SafetyInteractor + Send + Sync>>, message_queue: MessageQueue) -> Self {
+        Self {
+            interactor,
+            message_queue,
+        }
+    }
+
+    pub async fn consume_deleted_accounts(&mut self) {
+        let _ = self.message_queue.bind_queue(EXCHANGE_NAME, "direct", DELETED_QUEUE_NAME, DELETED_ROUTING_KEY).await;
+
+        let interactor = Arc::clone(&self.interactor);
+        let message_queue = self.message_queue.clone();
+        let handle_message = move |msg: Vec<u8>| {
+            let interactor = Arc::clone(&interactor);
+            let message_queue = message_queue.clone();
+            async move {
+                if let Ok(id) = String::from_utf8(msg) {
+                    interactor.erase_account(&id).await?;
+                    let message = [id.as_str(), SERVICE_NAME].join(",");
+                    message_queue.publish(EXCHANGE_NAME, ERASED_ROUTING_KEY, message.as_bytes()).await?;
+                }
+                Ok(())
+            }
+        };
+
+        let _ = self.message_queue.start_consuming_async(DELETED_QUEUE_NAME, handle_message).await;
+    }
+}
\ No newline at end of file
@@ -1,3 +1,9 @@
+use serde_json::json;
+
+use error::DomainError;
+
+use crate::report::entity::{Action, Reason, Report, Status};
+use crate::report::repository::ReportRepository;
 use crate::safety::repository::SafetyRepository;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
@@ -25,15 +31,42 @@ pub trait SafetyInteractor {
         from_id: &str,
         to_id: &str,
     ) -> Result<(), Error>;
+    async fn report_user(
+        &self,
+        from_id: &str,
+        to_id: &str,
+        reason: Reason,
+        comment: &str,
+        message_ids: Vec<String>,
+    ) -> Result<String, Error>;
+    async fn get_reports(
+        &self,
+        status: Option<Status>,
+        skip: u64,
+        limit: u64,
+    ) -> Result<Vec<Report>, Error>;
+    async fn resolve_report(
+        &self,
+        report_id: &str,
+        moderator_id: &str,
+        action: Action,
+        suspension_duration: u64,
+    ) -> Result<Report, Error>;
+    async fn erase_account(&self, account_id: &str) -> Result<(), Error>;
+    async fn export_data(&self, account_id: &str) -> Result<Vec<u8>, Error>;
 }
 
 pub struct SafetyInteractorImpl {
     repository: Box<dyn SafetyRepository + Send + Sync>,
+    report_repository: Box<dyn ReportRepository + Send + Sync>,
 }
 
 impl SafetyInteractorImpl {
-    pub fn new(repository: Box<dyn SafetyRepository + Send + Sync>) -> Box<dyn SafetyInteractor + Send + Sync> {
-        Box::new(SafetyInteractorImpl { repository })
+    pub fn new(
+        repository: Box<dyn SafetyRepository + Send + Sync>,
+        report_repository: Box<dyn ReportRepository + Send + Sync>,
+    ) -> Box<dyn SafetyInteractor + Send + Sync> {
+        Box::new(SafetyInteractorImpl { repository, report_repository })
     }
 }
 
@@ -54,4 +87,34 @@ impl SafetyInteractor for SafetyInteractorImpl {
     async fn unblock_user(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
         self.repository.unblock_user(from_id, to_id).await
     }
+
+    async fn report_user(&self, from_id: &str, to_id: &str, reason: Reason, comment: &str, message_ids: Vec<String>) -> Result<String, Error> {
+        if from_id == to_id {
+            return Err(DomainError::invalid_argument("users cannot report themselves"));
+        }
+        let report_id = self.report_repository.create_report(from_id, to_id, reason, comment, message_ids).await?;
+        if !self.repository.check_user_id(from_id, to_id).await? {
+            self.repository.block_user(from_id, to_id).await?;
+        }
+        Ok(report_id)
+    }
+
+    async fn get_reports(&self, status: Option<Status>, skip: u64, limit: u64) -> Result<Vec<Report>, Error> {
+        self.report_repository.get_reports(status, skip, limit).await
+    }
+
+    async fn resolve_report(&self, report_id: &str, moderator_id: &str, action: Action, suspension_duration: u64) -> Result<Report, Error> {
+        self.report_repository.resolve_report(report_id, moderator_id, action, suspension_duration).await
+    }
+
+    async fn erase_account(&self, account_id: &str) -> Result<(), Error> {
+        self.repository.erase_blocks(account_id).await?;
+        self.report_repository.anonymize_reports(account_id).await
+    }
+
+    async fn export_data(&self, account_id: &str) -> Result<Vec<u8>, Error> {
+        let blocked_users = self.repository.get_all_blocked_users(account_id).await?;
+        let reports = self.report_repository.get_filed_reports(account_id).await?;
+        Ok(serde_json::to_vec(&json!({ "blocked_users": blocked_users, "reports": reports }))?)
+    }
 }
\ No newline at end of file
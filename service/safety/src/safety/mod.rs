@@ -1,3 +1,4 @@
+pub mod amqp;
 pub mod entity;
 pub mod repository;
 pub mod interactor;
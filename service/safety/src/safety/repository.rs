@@ -4,12 +4,17 @@ use mongodb::bson::oid::ObjectId;
 use mongodb::Collection;
 use mongodb::options::FindOptions;
 
-use error::make_error;
+use amqp::MessageQueue;
+use error::DomainError;
 
 use crate::safety::entity::BlockedUser;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
+const EXCHANGE_NAME: &str = "action";
+const BLOCKED_ROUTING_KEY: &str = "user.blocked";
+const UNBLOCKED_ROUTING_KEY: &str = "user.unblocked";
+
 #[async_trait::async_trait]
 pub trait SafetyRepository {
     async fn check_user_id(
@@ -33,15 +38,18 @@ pub trait SafetyRepository {
         from_id: &str,
         to_id: &str,
     ) -> Result<(), Error>;
+    async fn erase_blocks(&self, id: &str) -> Result<(), Error>;
+    async fn get_all_blocked_users(&self, from_id: &str) -> Result<Vec<BlockedUser>, Error>;
 }
 
 pub struct SafetyRepositoryImpl {
     collection: Collection<BlockedUser>,
+    message_queue: MessageQueue,
 }
 
 impl SafetyRepositoryImpl {
-    pub fn new(collection: Collection<BlockedUser>) -> Box<dyn SafetyRepository + Send + Sync> {
-        Box::new(SafetyRepositoryImpl { collection })
+    pub fn new(collection: Collection<BlockedUser>, message_queue: MessageQueue) -> Box<dyn SafetyRepository + Send + Sync> {
+        Box::new(SafetyRepositoryImpl { collection, message_queue })
     }
 }
 
@@ -66,17 +74,46 @@ impl SafetyRepository for SafetyRepositoryImpl {
     async fn block_user(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
         let id = ObjectId::new().to_hex();
         let blocked_user = BlockedUser::new(&id, from_id, to_id);
-        let result = self.collection.insert_one(blocked_user.clone(), None).await;
-        if let Ok(_) = result {
-            return Ok(());
+        self.collection.insert_one(blocked_user.clone(), None).await?;
+        let message = [from_id, to_id].join(",");
+        if let Err(error) = self.message_queue.publish(EXCHANGE_NAME, BLOCKED_ROUTING_KEY, message.as_bytes()).await {
+            // the block is undone so that other services never miss it
+            self.collection.delete_one(doc! { "_id": &id }, None).await?;
+            return Err(DomainError::unavailable(error));
         }
-        Err(make_error!("unable to block user"))
+        Ok(())
     }
 
     async fn unblock_user(&self, from_id: &str, to_id: &str) -> Result<(), Error> {
-        if self.collection.delete_one(doc! { "from_id": from_id, "to_id": to_id }, None).await?.deleted_count > 0 {
+        if let Some(blocked_user) = self.collection.find_one_and_delete(doc! { "from_id": from_id, "to_id": to_id }, None).await? {
+            let message = [from_id, to_id].join(",");
+            if let Err(error) = self.message_queue.publish(EXCHANGE_NAME, UNBLOCKED_ROUTING_KEY, message.as_bytes()).await {
+                self.collection.insert_one(blocked_user, None).await?;
+                return Err(DomainError::unavailable(error));
+            }
             return Ok(());
         };
-        Err(make_error!("unable to block user"))
+        Err(DomainError::not_found("user is not blocked"))
+    }
+
+    async fn erase_blocks(&self, id: &str) -> Result<(), Error> {
+        let filter = doc! {
+            "$or": [
+                { "from_id": id },
+                { "to_id": id }
+            ]
+        };
+        self.collection.delete_many(filter, None).await?;
+        Ok(())
+    }
+
+    async fn get_all_blocked_users(&self, from_id: &str) -> Result<Vec<BlockedUser>, Error> {
+        let mut blocked_users: Vec<BlockedUser> = vec![];
+        let options = FindOptions::builder().sort(doc! { "created_at": 1 }).build();
+        let mut cursor = self.collection.find(doc! { "from_id": from_id }, options).await?;
+        while let Some(blocked_user) = cursor.try_next().await? {
+            blocked_users.push(blocked_user)
+        }
+        Ok(blocked_users)
     }
 }
\ No newline at end of file
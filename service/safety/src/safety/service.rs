@@ -1,15 +1,18 @@
+use std::sync::Arc;
+
 use tonic::{Request, Response, Status};
 
+use crate::report::entity::Action;
 use crate::safety::interactor::SafetyInteractor;
-use crate::safety::pb::{BlockUserRequest, BlockUserResponse, CheckUserIdRequest, CheckUserIdResponse, GetBlockedUsersRequest, GetBlockedUsersResponse, UnblockUserRequest, UnblockUserResponse};
+use crate::safety::pb::{BlockUserRequest, BlockUserResponse, CheckUserIdRequest, CheckUserIdResponse, ExportDataRequest, ExportDataResponse, GetBlockedUsersRequest, GetBlockedUsersResponse, ListReportsRequest, ListReportsResponse, ReportAction, ReportReason, ReportStatus, ReportUserRequest, ReportUserResponse, ResolveReportRequest, ResolveReportResponse, UnblockUserRequest, UnblockUserResponse};
 use crate::safety::pb::safety_service_server::SafetyService;
 
 pub struct SafetyServiceImpl {
-    interactor: Box<dyn SafetyInteractor + Send + Sync>,
+    interactor: Arc<Box<dyn SafetyInteractor + Send + Sync>>,
 }
 
 impl SafetyServiceImpl {
-    pub fn new(interactor: Box<dyn SafetyInteractor + Send + Sync>) -> impl SafetyService {
+    pub fn new(interactor: Arc<Box<dyn SafetyInteractor + Send + Sync>>) -> impl SafetyService {
         SafetyServiceImpl { interactor }
     }
 }
@@ -28,7 +31,7 @@ impl SafetyService for SafetyServiceImpl {
                     CheckUserIdResponse { is_blocked }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -44,7 +47,7 @@ impl SafetyService for SafetyServiceImpl {
                     GetBlockedUsersResponse { user_ids: user_ids.into_iter().collect() }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -60,7 +63,7 @@ impl SafetyService for SafetyServiceImpl {
                     BlockUserResponse::default()
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -76,7 +79,79 @@ impl SafetyService for SafetyServiceImpl {
                     UnblockUserResponse::default()
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn report_user(&self, request: Request<ReportUserRequest>) -> Result<Response<ReportUserResponse>, Status> {
+        let ReportUserRequest { from_id, to_id, reason, comment, message_ids } = request.into_inner();
+        let reason = ReportReason::try_from(reason);
+        if from_id.is_empty() || to_id.is_empty() || from_id == to_id || reason.is_err() {
+            return status::Status::invalid_arguments(vec!["from_id", "to_id", "reason"]);
+        }
+
+        match self.interactor.report_user(&from_id, &to_id, reason.unwrap().into(), &comment, message_ids).await {
+            Ok(report_id) => Ok(
+                Response::new(
+                    ReportUserResponse { report_id }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn list_reports(&self, request: Request<ListReportsRequest>) -> Result<Response<ListReportsResponse>, Status> {
+        let ListReportsRequest { status: report_status, skip, limit } = request.into_inner();
+        let report_status = match report_status.map(ReportStatus::try_from) {
+            Some(Ok(report_status)) => Some(report_status.into()),
+            Some(Err(_)) => return status::Status::invalid_arguments(vec!["status"]),
+            None => None
+        };
+
+        match self.interactor.get_reports(report_status, skip, limit).await {
+            Ok(reports) => Ok(
+                Response::new(
+                    ListReportsResponse {
+                        reports: reports.into_iter().map(|report| report.into()).collect()
+                    }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn resolve_report(&self, request: Request<ResolveReportRequest>) -> Result<Response<ResolveReportResponse>, Status> {
+        let ResolveReportRequest { report_id, moderator_id, action, suspension_duration } = request.into_inner();
+        let action = ReportAction::try_from(action).ok().and_then(|action| Action::try_from(action).ok());
+        if report_id.is_empty() || moderator_id.is_empty() || action.is_none() || (matches!(action, Some(Action::Suspend)) && suspension_duration == 0) {
+            return status::Status::invalid_arguments(vec!["report_id", "moderator_id", "action", "suspension_duration"]);
+        }
+
+        match self.interactor.resolve_report(&report_id, &moderator_id, action.unwrap(), suspension_duration).await {
+            Ok(report) => Ok(
+                Response::new(
+                    ResolveReportResponse {
+                        report: Some(report.into())
+                    }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn export_data(&self, request: Request<ExportDataRequest>) -> Result<Response<ExportDataResponse>, Status> {
+        let ExportDataRequest { id } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        match self.interactor.export_data(&id).await {
+            Ok(data) => Ok(
+                Response::new(
+                    ExportDataResponse { data }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
         }
     }
 }
\ No newline at end of file
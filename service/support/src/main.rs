@@ -1,5 +1,6 @@
 use std::error::Error;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use tonic::transport::Server;
 
@@ -19,8 +20,15 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let collection = database.collection::<Ticket>(TICKETS_COLLECTION);
 
     let repository = support::repository::SupportRepositoryImpl::new(collection);
-    let interactor = support::interactor::SupportInteractorImpl::new(repository);
-    let service = support::service::SupportServiceImpl::new(interactor);
+    let interactor = Arc::new(support::interactor::SupportInteractorImpl::new(repository));
+    let service = support::service::SupportServiceImpl::new(Arc::clone(&interactor));
+
+    let amqp_hostname = cfg.amqp_hostname.unwrap();
+    let amqp_port = cfg.amqp_port.unwrap();
+
+    let deleted_accounts_queue = amqp::MessageQueue::connect(&amqp_hostname, &amqp_port).await?;
+    let mut deleted_accounts_handler = support::amqp::MessageQueueHandler::new(Arc::clone(&interactor), deleted_accounts_queue);
+    deleted_accounts_handler.consume_deleted_accounts().await;
 
     let server_addr = SocketAddr::new(cfg.service_hostname.unwrap().parse().unwrap(), cfg.service_port.unwrap().parse().unwrap());
 
@@ -11,7 +11,7 @@ pub trait SupportInteractor {
         skip: u64,
         limit: u64,
     ) -> Result<Vec<Ticket>, Error>;
-    async fn get_ticket_by_id(&self, id: &str) -> Result<Ticket, Error>;
+    async fn get_ticket_by_id(&self, id: &str, user_id: &str) -> Result<Ticket, Error>;
     async fn create_ticket(
         &self,
         user_id: &str,
@@ -21,9 +21,12 @@ pub trait SupportInteractor {
     async fn update_ticket(
         &self,
         ticket_id: &str,
+        user_id: &str,
         description: &str,
     ) -> Result<Status, Error>;
-    async fn delete_ticket(&self, ticket_id: &str) -> Result<String, Error>;
+    async fn delete_ticket(&self, ticket_id: &str, user_id: &str) -> Result<String, Error>;
+    async fn erase_account(&self, account_id: &str) -> Result<(), Error>;
+    async fn export_data(&self, account_id: &str) -> Result<Vec<u8>, Error>;
 }
 
 pub struct SupportInteractorImpl {
@@ -42,19 +45,28 @@ impl SupportInteractor for SupportInteractorImpl {
         self.repository.get_tickets(user_id, skip, limit).await
     }
 
-    async fn get_ticket_by_id(&self, id: &str) -> Result<Ticket, Error> {
-        self.repository.get_ticket_by_id(id).await
+    async fn get_ticket_by_id(&self, id: &str, user_id: &str) -> Result<Ticket, Error> {
+        self.repository.get_ticket_by_id(id, user_id).await
     }
 
     async fn create_ticket(&self, user_id: &str, topic: &str, description: &str) -> Result<Status, Error> {
         self.repository.create_ticket(user_id, topic, description).await
     }
 
-    async fn update_ticket(&self, ticket_id: &str, description: &str) -> Result<Status, Error> {
-        self.repository.update_ticket(ticket_id, description).await
+    async fn update_ticket(&self, ticket_id: &str, user_id: &str, description: &str) -> Result<Status, Error> {
+        self.repository.update_ticket(ticket_id, user_id, description).await
+    }
+
+    async fn delete_ticket(&self, ticket_id: &str, user_id: &str) -> Result<String, Error> {
+        self.repository.delete_ticket(ticket_id, user_id).await
+    }
+
+    async fn erase_account(&self, account_id: &str) -> Result<(), Error> {
+        self.repository.delete_tickets(account_id).await
     }
 
-    async fn delete_ticket(&self, ticket_id: &str) -> Result<String, Error> {
-        self.repository.delete_ticket(ticket_id).await
+    async fn export_data(&self, account_id: &str) -> Result<Vec<u8>, Error> {
+        let tickets = self.repository.get_all_tickets(account_id).await?;
+        Ok(serde_json::to_vec(&tickets)?)
     }
 }
\ No newline at end of file
@@ -4,7 +4,7 @@ use mongodb::bson::{bson, doc};
 use mongodb::bson::oid::ObjectId;
 use mongodb::options::FindOptions;
 
-use error::make_error;
+use error::DomainError;
 
 use crate::support::entity::{Status, Ticket};
 
@@ -18,7 +18,7 @@ pub trait SupportRepository {
         skip: u64,
         limit: u64,
     ) -> Result<Vec<Ticket>, Error>;
-    async fn get_ticket_by_id(&self, id: &str) -> Result<Ticket, Error>;
+    async fn get_ticket_by_id(&self, id: &str, user_id: &str) -> Result<Ticket, Error>;
     async fn create_ticket(
         &self,
         user_id: &str,
@@ -28,9 +28,12 @@ pub trait SupportRepository {
     async fn update_ticket(
         &self,
         ticket_id: &str,
+        user_id: &str,
         description: &str,
     ) -> Result<Status, Error>;
-    async fn delete_ticket(&self, ticket_id: &str) -> Result<String, Error>;
+    async fn delete_ticket(&self, ticket_id: &str, user_id: &str) -> Result<String, Error>;
+    async fn delete_tickets(&self, user_id: &str) -> Result<(), Error>;
+    async fn get_all_tickets(&self, user_id: &str) -> Result<Vec<Ticket>, Error>;
 }
 
 pub struct SupportRepositoryImpl {
@@ -56,40 +59,52 @@ impl SupportRepository for SupportRepositoryImpl {
         Ok(tickets)
     }
 
-    async fn get_ticket_by_id(&self, id: &str) -> Result<Ticket, Error> {
-        if let Some(ticket) = self.collection.find_one(doc! { "_id": id }, None).await? {
+    async fn get_ticket_by_id(&self, id: &str, user_id: &str) -> Result<Ticket, Error> {
+        if let Some(ticket) = self.collection.find_one(doc! { "_id": id, "user_id": user_id }, None).await? {
             return Ok(ticket);
         }
-        Err(make_error!("unable to get ticket by id"))
+        Err(DomainError::not_found("ticket not found"))
     }
 
     async fn create_ticket(&self, user_id: &str, topic: &str, description: &str) -> Result<Status, Error> {
         let id = ObjectId::new().to_hex();
         let ticket = Ticket::new(&id, user_id, topic, description);
-        let result = self.collection.insert_one(ticket.clone(), None).await;
-        if let Ok(_) = result {
-            return Ok(ticket.status);
-        }
-        Err(make_error!("unable to create ticket"))
+        self.collection.insert_one(ticket.clone(), None).await?;
+        Ok(ticket.status)
     }
 
-    async fn update_ticket(&self, ticket_id: &str, description: &str) -> Result<Status, Error> {
+    async fn update_ticket(&self, ticket_id: &str, user_id: &str, description: &str) -> Result<Status, Error> {
         let timestamp = bson!(Ticket::timestamp_now() as i64);
         let document = doc! {
             "description": description,
             "updated_at": timestamp
         };
-        let result = self.collection.update_one(doc! { "_id": ticket_id }, doc! { "$set": document }, None).await?;
+        let result = self.collection.update_one(doc! { "_id": ticket_id, "user_id": user_id }, doc! { "$set": document }, None).await?;
         if result.modified_count > 0 {
-            return self.get_ticket_by_id(ticket_id).await.map(|ticket| ticket.status);
+            return self.get_ticket_by_id(ticket_id, user_id).await.map(|ticket| ticket.status);
         }
-        Err(make_error!("unable to update ticket"))
+        Err(DomainError::not_found("ticket not found"))
     }
 
-    async fn delete_ticket(&self, ticket_id: &str) -> Result<String, Error> {
-        if self.collection.delete_one(doc! { "_id": ticket_id }, None).await?.deleted_count > 0 {
+    async fn delete_ticket(&self, ticket_id: &str, user_id: &str) -> Result<String, Error> {
+        if self.collection.delete_one(doc! { "_id": ticket_id, "user_id": user_id }, None).await?.deleted_count > 0 {
             return Ok(String::from(ticket_id));
         }
-        Err(make_error!("unable to delete ticket"))
+        Err(DomainError::not_found("ticket not found"))
+    }
+
+    async fn delete_tickets(&self, user_id: &str) -> Result<(), Error> {
+        self.collection.delete_many(doc! { "user_id": user_id }, None).await?;
+        Ok(())
+    }
+
+    async fn get_all_tickets(&self, user_id: &str) -> Result<Vec<Ticket>, Error> {
+        let mut tickets: Vec<Ticket> = vec![];
+        let options = FindOptions::builder().sort(doc! { "created_at": 1 }).build();
+        let mut cursor = self.collection.find(doc! { "user_id": user_id }, options).await?;
+        while let Some(ticket) = cursor.try_next().await? {
+            tickets.push(ticket)
+        }
+        Ok(tickets)
     }
 }
\ No newline at end of file
@@ -1,15 +1,17 @@
+use std::sync::Arc;
+
 use tonic::{Request, Response, Status};
 
 use crate::support::interactor::SupportInteractor;
-use crate::support::pb::{CreateTicketRequest, CreateTicketResponse, DeleteTicketRequest, DeleteTicketResponse, GetTicketByIdRequest, GetTicketByIdResponse, GetTicketsRequest, GetTicketsResponse, UpdateTicketRequest, UpdateTicketResponse};
+use crate::support::pb::{CreateTicketRequest, CreateTicketResponse, DeleteTicketRequest, DeleteTicketResponse, ExportDataRequest, ExportDataResponse, GetTicketByIdRequest, GetTicketByIdResponse, GetTicketsRequest, GetTicketsResponse, UpdateTicketRequest, UpdateTicketResponse};
 use crate::support::pb::support_service_server::SupportService;
 
 pub struct SupportServiceImpl {
-    interactor: Box<dyn SupportInteractor + Send + Sync>,
+    interactor: Arc<Box<dyn SupportInteractor + Send + Sync>>,
 }
 
 impl SupportServiceImpl {
-    pub fn new(interactor: Box<dyn SupportInteractor + Send + Sync>) -> impl SupportService {
+    pub fn new(interactor: Arc<Box<dyn SupportInteractor + Send + Sync>>) -> impl SupportService {
         SupportServiceImpl { interactor }
     }
 }
@@ -28,23 +30,23 @@ impl SupportService for SupportServiceImpl {
                     GetTicketsResponse { tickets: tickets.into_iter().map(|ticket| ticket.into()).collect() }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn get_ticket_by_id(&self, request: Request<GetTicketByIdRequest>) -> Result<Response<GetTicketByIdResponse>, Status> {
-        let GetTicketByIdRequest { id } = request.into_inner();
-        if id.is_empty() {
-            return status::Status::invalid_arguments(vec!["id"]);
+        let GetTicketByIdRequest { id, user_id } = request.into_inner();
+        if id.is_empty() || user_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id", "user_id"]);
         }
 
-        match self.interactor.get_ticket_by_id(&id).await {
+        match self.interactor.get_ticket_by_id(&id, &user_id).await {
             Ok(ticket) => Ok(
                 Response::new(
                     GetTicketByIdResponse { ticket: Some(ticket.into()) }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -60,39 +62,55 @@ impl SupportService for SupportServiceImpl {
                     CreateTicketResponse { status: status as i32 }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn update_ticket(&self, request: Request<UpdateTicketRequest>) -> Result<Response<UpdateTicketResponse>, Status> {
-        let UpdateTicketRequest { ticket_id, description } = request.into_inner();
-        if ticket_id.is_empty() || description.is_empty() {
-            return status::Status::invalid_arguments(vec!["ticket_id", "description"]);
+        let UpdateTicketRequest { ticket_id, description, user_id } = request.into_inner();
+        if ticket_id.is_empty() || description.is_empty() || user_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["ticket_id", "description", "user_id"]);
         }
 
-        match self.interactor.update_ticket(&ticket_id, &description).await {
+        match self.interactor.update_ticket(&ticket_id, &user_id, &description).await {
             Ok(status) => Ok(
                 Response::new(
                     UpdateTicketResponse { status: status as i32 }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn delete_ticket(&self, request: Request<DeleteTicketRequest>) -> Result<Response<DeleteTicketResponse>, Status> {
-        let DeleteTicketRequest { ticket_id } = request.into_inner();
-        if ticket_id.is_empty() {
-            return status::Status::invalid_arguments(vec!["ticket_id"]);
+        let DeleteTicketRequest { ticket_id, user_id } = request.into_inner();
+        if ticket_id.is_empty() || user_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["ticket_id", "user_id"]);
         }
 
-        match self.interactor.delete_ticket(&ticket_id).await {
+        match self.interactor.delete_ticket(&ticket_id, &user_id).await {
             Ok(ticket_id) => Ok(
                 Response::new(
                     DeleteTicketResponse { ticket_id }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn export_data(&self, request: Request<ExportDataRequest>) -> Result<Response<ExportDataResponse>, Status> {
+        let ExportDataRequest { id } = request.into_inner();
+        if id.is_empty() {
+            return status::Status::invalid_arguments(vec!["id"]);
+        }
+
+        match self.interactor.export_data(&id).await {
+            Ok(data) => Ok(
+                Response::new(
+                    ExportDataResponse { data }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
         }
     }
 }
\ No newline at end of file
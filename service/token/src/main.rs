@@ -13,7 +13,21 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     let redis_client = redis::Client::open(format!("redis://{}:{}", cfg.redis_hostname.unwrap(), cfg.redis_port.unwrap()))?;
 
-    let repository = token::repository::TokenRepositoryImpl::new(cfg.secret_key.unwrap(), redis_client);
+    let message_queue = amqp::MessageQueue::connect(&cfg.amqp_hostname.unwrap(), &cfg.amqp_port.unwrap()).await?;
+
+    let parse_ttl = |ttl: Option<String>, default: u64| ttl.and_then(|ttl| ttl.parse::<u64>().ok()).unwrap_or(default);
+    let default_lifetimes = token::entity::TokenLifetimes::default();
+    let lifetimes = token::entity::TokenLifetimes {
+        access: parse_ttl(cfg.access_token_ttl, default_lifetimes.access),
+        refresh: parse_ttl(cfg.refresh_token_ttl, default_lifetimes.refresh),
+        email_verification: parse_ttl(cfg.email_verification_token_ttl, default_lifetimes.email_verification),
+        password_reset: parse_ttl(cfg.password_reset_token_ttl, default_lifetimes.password_reset),
+        websocket_ticket: parse_ttl(cfg.websocket_ticket_ttl, default_lifetimes.websocket_ticket),
+        data_export: parse_ttl(cfg.data_export_token_ttl, default_lifetimes.data_export),
+    };
+
+    let keyring = token::keyring::KeyringImpl::new(redis_client.clone(), lifetimes.max());
+    let repository = token::repository::TokenRepositoryImpl::new(keyring, redis_client, message_queue, lifetimes);
     let interactor = token::interactor::TokenInteractorImpl::new(repository);
     let service = token::service::TokenServiceImpl::new(interactor);
 
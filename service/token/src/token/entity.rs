@@ -0,0 +1,144 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Serialize, Deserialize)]
+pub struct Session {
+    pub id: String,
+    pub device_name: String,
+    pub ip_address: String,
+    pub user_agent: String,
+    pub created_at: u64,
+    pub last_seen_at: u64,
+}
+
+impl Session {
+    pub fn timestamp_now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64
+    }
+
+    pub fn new(id: &str, device_name: &str, ip_address: &str, user_agent: &str) -> Self {
+        let now = Session::timestamp_now();
+        Self {
+            id: String::from(id),
+            device_name: String::from(device_name),
+            ip_address: String::from(ip_address),
+            user_agent: String::from(user_agent),
+            created_at: now,
+            last_seen_at: now,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Key {
+    pub kid: String,
+    pub private_key: String,
+    pub public_key: String,
+    pub created_at: u64,
+}
+
+impl Key {
+    pub fn new(kid: &str, private_key: &str, public_key: &str) -> Self {
+        Self {
+            kid: String::from(kid),
+            private_key: String::from(private_key),
+            public_key: String::from(public_key),
+            created_at: Session::timestamp_now(),
+        }
+    }
+}
+
+pub struct PublicKey {
+    pub kid: String,
+    pub x: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TokenHeader {
+    pub alg: String,
+    pub typ: String,
+    pub kid: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Claims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub iat: u64,
+    pub nbf: u64,
+    pub exp: u64,
+    pub jti: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+    #[serde(flatten)]
+    pub payload: Map<String, Value>,
+}
+
+impl Claims {
+    pub const REGISTERED_NAMES: [&'static str; 8] = ["iss", "sub", "aud", "iat", "nbf", "exp", "jti", "sid"];
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    Access,
+    Refresh,
+    EmailVerification,
+    PasswordReset,
+    WebsocketTicket,
+    DataExport,
+}
+
+impl TokenKind {
+    pub fn audience(&self) -> &'static str {
+        match self {
+            TokenKind::Access => "access",
+            TokenKind::Refresh => "refresh",
+            TokenKind::EmailVerification => "email_verification",
+            TokenKind::PasswordReset => "password_reset",
+            TokenKind::WebsocketTicket => "websocket_ticket",
+            TokenKind::DataExport => "data_export",
+        }
+    }
+}
+
+pub struct TokenLifetimes {
+    pub access: u64,
+    pub refresh: u64,
+    pub email_verification: u64,
+    pub password_reset: u64,
+    pub websocket_ticket: u64,
+    pub data_export: u64,
+}
+
+impl TokenLifetimes {
+    pub fn get(&self, kind: TokenKind) -> u64 {
+        match kind {
+            TokenKind::Access => self.access,
+            TokenKind::Refresh => self.refresh,
+            TokenKind::EmailVerification => self.email_verification,
+            TokenKind::PasswordReset => self.password_reset,
+            TokenKind::WebsocketTicket => self.websocket_ticket,
+            TokenKind::DataExport => self.data_export,
+        }
+    }
+
+    pub fn max(&self) -> u64 {
+        [self.access, self.refresh, self.email_verification, self.password_reset, self.websocket_ticket, self.data_export].into_iter().max().unwrap_or_default()
+    }
+}
+
+impl Default for TokenLifetimes {
+    fn default() -> Self {
+        Self {
+            access: 60 * 30,
+            refresh: 60 * 60 * 24,
+            email_verification: 60 * 60 * 24,
+            password_reset: 60 * 15,
+            websocket_ticket: 30,
+            data_export: 60 * 60,
+        }
+    }
+}
\ No newline at end of file
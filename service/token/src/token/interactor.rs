@@ -1,13 +1,22 @@
+use crate::token::entity::{PublicKey, Session, TokenKind};
 use crate::token::repository::TokenRepository;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
 #[async_trait::async_trait]
 pub trait TokenInteractor {
-    async fn generate_access_token(&self, payload: &str) -> Result<String, Error>;
-    async fn generate_refresh_token(&self, payload: &str) -> Result<String, Error>;
-    async fn validate_token(&self, token: &str) -> Result<String, Error>;
+    async fn generate_access_token(&self, subject: &str, payload: &str, family_id: Option<String>, ttl: Option<u64>) -> Result<String, Error>;
+    async fn generate_refresh_token(&self, subject: &str, payload: &str, family_id: Option<String>, ttl: Option<u64>) -> Result<String, Error>;
+    async fn generate_token(&self, kind: TokenKind, subject: &str, payload: &str, ttl: Option<u64>) -> Result<String, Error>;
+    async fn validate_token(&self, token: &str, kind: TokenKind) -> Result<String, Error>;
     async fn invalidate_token(&self, token: &str) -> Result<(), Error>;
+    async fn revoke_tokens(&self, subject: &str) -> Result<(), Error>;
+    async fn rotate_refresh_token(&self, token: &str) -> Result<(String, String, String), Error>;
+    async fn create_session(&self, subject: &str, device_name: &str, ip_address: &str, user_agent: &str) -> Result<String, Error>;
+    async fn list_sessions(&self, subject: &str) -> Result<Vec<Session>, Error>;
+    async fn revoke_session(&self, subject: &str, session_id: &str) -> Result<(), Error>;
+    async fn revoke_all_sessions(&self, subject: &str, except_session_id: Option<String>) -> Result<(), Error>;
+    async fn get_public_keys(&self) -> Result<Vec<PublicKey>, Error>;
 }
 
 pub struct TokenInteractorImpl {
@@ -22,19 +31,51 @@ impl TokenInteractorImpl {
 
 #[async_trait::async_trait]
 impl TokenInteractor for TokenInteractorImpl {
-    async fn generate_access_token(&self, payload: &str) -> Result<String, Error> {
-        self.repository.generate_access_token(payload).await
+    async fn generate_access_token(&self, subject: &str, payload: &str, family_id: Option<String>, ttl: Option<u64>) -> Result<String, Error> {
+        self.repository.generate_access_token(subject, payload, family_id, ttl).await
     }
 
-    async fn generate_refresh_token(&self, payload: &str) -> Result<String, Error> {
-        self.repository.generate_refresh_token(payload).await
+    async fn generate_refresh_token(&self, subject: &str, payload: &str, family_id: Option<String>, ttl: Option<u64>) -> Result<String, Error> {
+        self.repository.generate_refresh_token(subject, payload, family_id, ttl).await
     }
 
-    async fn validate_token(&self, token: &str) -> Result<String, Error> {
-        self.repository.validate_token(token).await
+    async fn generate_token(&self, kind: TokenKind, subject: &str, payload: &str, ttl: Option<u64>) -> Result<String, Error> {
+        self.repository.generate_token(kind, subject, payload, ttl).await
+    }
+
+    async fn validate_token(&self, token: &str, kind: TokenKind) -> Result<String, Error> {
+        self.repository.validate_token(token, kind).await
     }
 
     async fn invalidate_token(&self, token: &str) -> Result<(), Error> {
         self.repository.invalidate_token(token).await
     }
+
+    async fn revoke_tokens(&self, subject: &str) -> Result<(), Error> {
+        self.repository.revoke_tokens(subject).await
+    }
+
+    async fn rotate_refresh_token(&self, token: &str) -> Result<(String, String, String), Error> {
+        self.repository.rotate_refresh_token(token).await
+    }
+
+    async fn create_session(&self, subject: &str, device_name: &str, ip_address: &str, user_agent: &str) -> Result<String, Error> {
+        self.repository.create_session(subject, device_name, ip_address, user_agent).await
+    }
+
+    async fn list_sessions(&self, subject: &str) -> Result<Vec<Session>, Error> {
+        self.repository.list_sessions(subject).await
+    }
+
+    async fn revoke_session(&self, subject: &str, session_id: &str) -> Result<(), Error> {
+        self.repository.revoke_session(subject, session_id).await
+    }
+
+    async fn revoke_all_sessions(&self, subject: &str, except_session_id: Option<String>) -> Result<(), Error> {
+        self.repository.revoke_all_sessions(subject, except_session_id).await
+    }
+
+    async fn get_public_keys(&self) -> Result<Vec<PublicKey>, Error> {
+        self.repository.get_public_keys().await
+    }
 }
\ No newline at end of file
@@ -0,0 +1,215 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::rngs::OsRng;
+use redis::{Client as RedisClient, Commands, Connection};
+use uuid::Uuid;
+
+use error::{DomainError, make_error};
+
+use crate::token::entity::{Claims, Key, PublicKey, Session, TokenHeader};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+const KEYS_KEY: &str = "signing_keys";
+const ALGORITHM: &str = "EdDSA";
+const TOKEN_TYPE: &str = "JWT";
+const KEY_ROTATION_MILLIS: u64 = 1000 * 60 * 60 * 24 * 7;
+const KEY_PUBLICATION_MILLIS: u64 = 1000 * 60 * 60;
+
+pub trait Keyring {
+    fn sign(&self, claims: &Claims) -> Result<String, Error>;
+    fn verify(&self, token: &str) -> Result<Claims, Error>;
+    fn get_public_keys(&self) -> Result<Vec<PublicKey>, Error>;
+}
+
+pub struct KeyringImpl {
+    redis_client: RedisClient,
+    retention_millis: u64,
+}
+
+impl KeyringImpl {
+    fn invalid_token<E>(_: E) -> Error {
+        DomainError::unauthenticated("invalid token")
+    }
+
+    fn generate_key() -> Key {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        Key::new(
+            &Uuid::new_v4().simple().to_string(),
+            &STANDARD.encode(signing_key.to_bytes()),
+            &URL_SAFE_NO_PAD.encode(signing_key.verifying_key().to_bytes()),
+        )
+    }
+
+    fn get_keys(&self, redis: &mut Connection) -> Result<Vec<Key>, Error> {
+        let now = Session::timestamp_now();
+        let keys: HashMap<String, String> = redis.hgetall(KEYS_KEY)?;
+        let (mut keys, retired): (Vec<Key>, Vec<Key>) = keys.values()
+            .map(|key| serde_json::from_str::<Key>(key))
+            .collect::<Result<Vec<Key>, _>>()?
+            .into_iter()
+            .partition(|key| now < key.created_at + KEY_ROTATION_MILLIS + KEY_PUBLICATION_MILLIS + self.retention_millis);
+        if !retired.is_empty() {
+            let kids: Vec<String> = retired.into_iter().map(|key| key.kid).collect();
+            redis.hdel::<&str, Vec<String>, ()>(KEYS_KEY, kids)?;
+        }
+        keys.sort_by_key(|key| Reverse(key.created_at));
+        Ok(keys)
+    }
+
+    fn get_active_key(&self) -> Result<Key, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let now = Session::timestamp_now();
+        let mut keys = self.get_keys(&mut redis)?;
+        if keys.first().is_none_or(|key| now >= key.created_at + KEY_ROTATION_MILLIS) {
+            let key = Self::generate_key();
+            redis.hset::<&str, &str, String, ()>(KEYS_KEY, &key.kid, serde_json::to_string(&key)?)?;
+            keys.insert(0, key);
+        }
+        Ok(Self::select_active_key(keys, now))
+    }
+
+    fn select_active_key(mut keys: Vec<Key>, now: u64) -> Key {
+        // a new key signs only once verifiers have had time to fetch it, until then the previous key keeps signing
+        let index = keys.iter().position(|key| now >= key.created_at + KEY_PUBLICATION_MILLIS).unwrap_or(0);
+        keys.swap_remove(index)
+    }
+
+    fn sign_with_key(key: &Key, claims: &Claims) -> Result<String, Error> {
+        let header = TokenHeader { alg: String::from(ALGORITHM), typ: String::from(TOKEN_TYPE), kid: key.kid.clone() };
+        let message = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(&header)?),
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims)?)
+        );
+        let private_key: [u8; 32] = STANDARD.decode(&key.private_key)?.try_into().map_err(|_| make_error!("invalid signing key"))?;
+        let signature = SigningKey::from_bytes(&private_key).sign(message.as_bytes());
+        Ok(format!("{}.{}", message, URL_SAFE_NO_PAD.encode(signature.to_bytes())))
+    }
+
+    fn get_kid(token: &str) -> Result<String, Error> {
+        let header = token.split('.').next().unwrap_or_default();
+        let TokenHeader { alg, kid, .. } = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header).map_err(Self::invalid_token)?).map_err(Self::invalid_token)?;
+        if alg == ALGORITHM {
+            return Ok(kid);
+        }
+        Err(Self::invalid_token(()))
+    }
+
+    fn verify_with_key(key: &Key, token: &str) -> Result<Claims, Error> {
+        let parts: Vec<&str> = token.split('.').collect();
+        if let [header, claims, signature] = parts[..] {
+            if Self::get_kid(token)? == key.kid {
+                let public_key: [u8; 32] = URL_SAFE_NO_PAD.decode(&key.public_key)?.try_into().map_err(|_| make_error!("invalid verifying key"))?;
+                let signature = URL_SAFE_NO_PAD.decode(signature).map_err(Self::invalid_token)?;
+                let signature = Signature::from_slice(&signature).map_err(Self::invalid_token)?;
+                VerifyingKey::from_bytes(&public_key)?.verify(format!("{}.{}", header, claims).as_bytes(), &signature).map_err(Self::invalid_token)?;
+                return serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims).map_err(Self::invalid_token)?).map_err(Self::invalid_token);
+            }
+        }
+        Err(Self::invalid_token(()))
+    }
+
+    pub fn new(redis_client: RedisClient, retention_seconds: u64) -> Box<dyn Keyring + Send + Sync> {
+        Box::new(KeyringImpl { redis_client, retention_millis: retention_seconds * 1000 })
+    }
+}
+
+impl Keyring for KeyringImpl {
+    fn sign(&self, claims: &Claims) -> Result<String, Error> {
+        Self::sign_with_key(&self.get_active_key()?, claims)
+    }
+
+    fn verify(&self, token: &str) -> Result<Claims, Error> {
+        let kid = Self::get_kid(token)?;
+        let mut redis = self.redis_client.get_connection()?;
+        let key: Option<String> = redis.hget(KEYS_KEY, &kid)?;
+        match key {
+            Some(key) => Self::verify_with_key(&serde_json::from_str(&key)?, token),
+            None => Err(Self::invalid_token(()))
+        }
+    }
+
+    fn get_public_keys(&self) -> Result<Vec<PublicKey>, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        Ok(self.get_keys(&mut redis)?
+            .into_iter()
+            .map(|key| PublicKey { kid: key.kid, x: key.public_key })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Map;
+
+    use super::*;
+
+    fn key(created_at: u64) -> Key {
+        Key { created_at, ..KeyringImpl::generate_key() }
+    }
+
+    fn claims() -> Claims {
+        let now = Session::timestamp_now() / 1000;
+        Claims {
+            iss: String::from("dddating"),
+            sub: String::from("subject"),
+            aud: String::from("access"),
+            iat: now,
+            nbf: now,
+            exp: now + 60,
+            jti: Uuid::new_v4().to_string(),
+            sid: None,
+            payload: Map::new(),
+        }
+    }
+
+    #[test]
+    fn select_previous_key_until_new_key_is_published() {
+        let now = Session::timestamp_now();
+        let previous = key(now - KEY_ROTATION_MILLIS - 1000);
+        let new = key(now - 1000);
+        let kid = previous.kid.clone();
+        assert_eq!(KeyringImpl::select_active_key(vec![new, previous], now).kid, kid);
+    }
+
+    #[test]
+    fn select_new_key_once_published() {
+        let now = Session::timestamp_now();
+        let previous = key(now - KEY_ROTATION_MILLIS - KEY_PUBLICATION_MILLIS);
+        let new = key(now - KEY_PUBLICATION_MILLIS);
+        let kid = new.kid.clone();
+        assert_eq!(KeyringImpl::select_active_key(vec![new, previous], now).kid, kid);
+    }
+
+    #[test]
+    fn verify_with_rotated_keys() {
+        let now = Session::timestamp_now();
+        let previous = key(now - KEY_ROTATION_MILLIS - KEY_PUBLICATION_MILLIS);
+        let new = key(now - KEY_PUBLICATION_MILLIS);
+        let previous_token = KeyringImpl::sign_with_key(&previous, &claims()).unwrap();
+        let new_token = KeyringImpl::sign_with_key(&new, &claims()).unwrap();
+        assert_eq!(KeyringImpl::get_kid(&previous_token).unwrap(), previous.kid);
+        assert_eq!(KeyringImpl::get_kid(&new_token).unwrap(), new.kid);
+        assert_eq!(KeyringImpl::verify_with_key(&previous, &previous_token).unwrap().sub, "subject");
+        assert_eq!(KeyringImpl::verify_with_key(&new, &new_token).unwrap().sub, "subject");
+        assert!(KeyringImpl::verify_with_key(&new, &previous_token).is_err());
+        assert!(KeyringImpl::verify_with_key(&previous, &new_token).is_err());
+    }
+
+    #[test]
+    fn reject_tampered_token() {
+        let key = key(Session::timestamp_now());
+        let token = KeyringImpl::sign_with_key(&key, &claims()).unwrap();
+        let [header, _, signature] = token.split('.').collect::<Vec<&str>>()[..] else { panic!() };
+        let mut tampered = claims();
+        tampered.sub = String::from("another subject");
+        let tampered = format!("{}.{}.{}", header, URL_SAFE_NO_PAD.encode(serde_json::to_vec(&tampered).unwrap()), signature);
+        let result = KeyringImpl::verify_with_key(&key, &tampered);
+        assert!(matches!(result.err().as_deref().and_then(|error| error.downcast_ref::<DomainError>()), Some(DomainError::Unauthenticated(_))));
+    }
+}
\ No newline at end of file
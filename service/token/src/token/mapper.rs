@@ -0,0 +1,40 @@
+use crate::token::entity::{PublicKey, Session, TokenKind};
+use crate::token::pb::{PublicKey as PublicKeyMessage, Session as SessionMessage, TokenKind as TokenKindMessage};
+
+impl From<Session> for SessionMessage {
+    fn from(value: Session) -> Self {
+        Self {
+            id: value.id,
+            device_name: value.device_name,
+            ip_address: value.ip_address,
+            user_agent: value.user_agent,
+            created_at: value.created_at,
+            last_seen_at: value.last_seen_at,
+        }
+    }
+}
+
+impl From<PublicKey> for PublicKeyMessage {
+    fn from(value: PublicKey) -> Self {
+        Self {
+            kid: value.kid,
+            kty: String::from("OKP"),
+            crv: String::from("Ed25519"),
+            alg: String::from("EdDSA"),
+            x: value.x,
+        }
+    }
+}
+
+impl From<TokenKindMessage> for TokenKind {
+    fn from(value: TokenKindMessage) -> Self {
+        match value {
+            TokenKindMessage::Access => TokenKind::Access,
+            TokenKindMessage::Refresh => TokenKind::Refresh,
+            TokenKindMessage::EmailVerification => TokenKind::EmailVerification,
+            TokenKindMessage::PasswordReset => TokenKind::PasswordReset,
+            TokenKindMessage::WebsocketTicket => TokenKind::WebsocketTicket,
+            TokenKindMessage::DataExport => TokenKind::DataExport,
+        }
+    }
+}
\ No newline at end of file
@@ -1,7 +1,10 @@
+pub mod entity;
+pub mod mapper;
+pub mod keyring;
 pub mod interactor;
 pub mod repository;
 pub mod service;
 
 pub mod pb {
     tonic::include_proto!("token");
-}
\ No newline at end of file
+}
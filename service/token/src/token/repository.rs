@@ -1,94 +1,299 @@
-use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use hmac::{digest::KeyInit, Hmac};
-use jwt::{SignWithKey, VerifyWithKey};
-use redis::{Client as RedisClient, Commands};
-use sha2::Sha256;
+use redis::{Client as RedisClient, Commands, Connection, ExistenceCheck, SetExpiry, SetOptions};
+use serde_json::{Map, Value};
+use uuid::Uuid;
 
-use error::make_error;
+use amqp::MessageQueue;
+use error::{DomainError, make_error};
+
+use crate::token::entity::{Claims, PublicKey, Session, TokenKind, TokenLifetimes};
+use crate::token::keyring::Keyring;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
 #[async_trait::async_trait]
 pub trait TokenRepository {
-    async fn generate_access_token(&self, payload: &str) -> Result<String, Error>;
-    async fn generate_refresh_token(&self, payload: &str) -> Result<String, Error>;
-    async fn validate_token(&self, token: &str) -> Result<String, Error>;
+    async fn generate_access_token(&self, subject: &str, payload: &str, family_id: Option<String>, ttl: Option<u64>) -> Result<String, Error>;
+    async fn generate_refresh_token(&self, subject: &str, payload: &str, family_id: Option<String>, ttl: Option<u64>) -> Result<String, Error>;
+    async fn generate_token(&self, kind: TokenKind, subject: &str, payload: &str, ttl: Option<u64>) -> Result<String, Error>;
+    async fn validate_token(&self, token: &str, kind: TokenKind) -> Result<String, Error>;
     async fn invalidate_token(&self, token: &str) -> Result<(), Error>;
+    async fn revoke_tokens(&self, subject: &str) -> Result<(), Error>;
+    async fn rotate_refresh_token(&self, token: &str) -> Result<(String, String, String), Error>;
+    async fn create_session(&self, subject: &str, device_name: &str, ip_address: &str, user_agent: &str) -> Result<String, Error>;
+    async fn list_sessions(&self, subject: &str) -> Result<Vec<Session>, Error>;
+    async fn revoke_session(&self, subject: &str, session_id: &str) -> Result<(), Error>;
+    async fn revoke_all_sessions(&self, subject: &str, except_session_id: Option<String>) -> Result<(), Error>;
+    async fn get_public_keys(&self) -> Result<Vec<PublicKey>, Error>;
 }
 
 pub struct TokenRepositoryImpl {
-    secret_key: String,
+    keyring: Box<dyn Keyring + Send + Sync>,
     redis_client: RedisClient,
+    message_queue: MessageQueue,
+    lifetimes: TokenLifetimes,
 }
 
+const EXCHANGE_NAME: &str = "action";
+const TOKEN_INVALIDATED_ROUTING_KEY: &str = "token.invalidated";
+const SUBJECT_REVOKED_ROUTING_KEY: &str = "subject.revoked";
+const SESSION_REVOKED_ROUTING_KEY: &str = "session.revoked";
+const ISSUER: &str = "dddating";
+
 impl TokenRepositoryImpl {
-    fn generate_token(&self, payload: &str, expiration_millis: u64) -> Result<String, Error> {
-        let key: Hmac<Sha256> = Hmac::new_from_slice(self.secret_key.as_ref())?;
-        let mut claims = BTreeMap::new();
-        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
-        let now_millis = now.as_millis() as u64;
-        let exp = now_millis + expiration_millis;
-        claims.insert("iat", now_millis.to_string());
-        claims.insert("exp", exp.to_string());
-        claims.insert("payload", payload.to_string());
-        if let Ok(token) = claims.sign_with_key(&key) {
-            return Ok(token);
+    fn revocation_key(subject: &str) -> String {
+        format!("revoked_at:{}", subject)
+    }
+
+    fn rotation_key(jti: &str) -> String {
+        format!("rotated:{}", jti)
+    }
+
+    fn family_key(family_id: &str) -> String {
+        format!("family:{}", family_id)
+    }
+
+    fn sessions_key(subject: &str) -> String {
+        format!("sessions:{}", subject)
+    }
+
+    fn touch_session(&self, redis: &mut Connection, subject: &str, session_id: &str) -> Result<(), Error> {
+        let session: Option<String> = redis.hget(Self::sessions_key(subject), session_id)?;
+        if let Some(session) = session {
+            let mut session: Session = serde_json::from_str(&session)?;
+            session.last_seen_at = Session::timestamp_now();
+            redis.hset::<String, &str, String, ()>(Self::sessions_key(subject), session_id, serde_json::to_string(&session)?)?;
         }
-        Err(make_error!("unable to generate token"))
-    }
-
-    fn verify_token(&self, token: &str) -> Result<BTreeMap<String, String>, Error> {
-        let key: Hmac<Sha256> = Hmac::new_from_slice(self.secret_key.as_ref())?;
-        let claims: BTreeMap<String, String> = token.verify_with_key(&key)?;
-        if vec!("iat", "exp", "payload").iter().all(|&key| claims.contains_key(key)) {
-            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
-            if let Some(exp) = claims.get("exp") {
-                if now < exp.parse::<u64>()? {
-                    return Ok(claims);
-                }
+        Ok(())
+    }
+
+    async fn publish(&self, routing_key: &str, values: &[&str]) -> Result<(), Error> {
+        self.message_queue.publish(EXCHANGE_NAME, routing_key, values.join(",").as_bytes()).await.map_err(DomainError::unavailable)
+    }
+
+    async fn revoke_family(&self, redis: &mut Connection, subject: &str, family_id: &str) -> Result<(), Error> {
+        redis.set_ex::<String, &str, ()>(Self::family_key(family_id), "", self.lifetimes.refresh)?;
+        redis.hdel::<String, &str, ()>(Self::sessions_key(subject), family_id)?;
+        let until = Self::timestamp_now()? + self.lifetimes.refresh;
+        self.publish(SESSION_REVOKED_ROUTING_KEY, &[family_id, &until.to_string()]).await
+    }
+
+    fn timestamp_now() -> Result<u64, Error> {
+        Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())
+    }
+
+    fn sign_token(&self, kind: TokenKind, subject: &str, payload: &str, family_id: Option<String>, ttl: Option<u64>) -> Result<String, Error> {
+        let payload: Map<String, Value> = serde_json::from_str(payload).map_err(|_| DomainError::invalid_argument("invalid payload"))?;
+        if payload.keys().any(|name| Claims::REGISTERED_NAMES.contains(&name.as_str())) {
+            return Err(DomainError::invalid_argument("invalid payload"));
+        }
+        let lifetime = self.lifetimes.get(kind);
+        let lifetime = ttl.map_or(lifetime, |ttl| ttl.min(lifetime));
+        let now = Self::timestamp_now()?;
+        let claims = Claims {
+            iss: String::from(ISSUER),
+            sub: String::from(subject),
+            aud: String::from(kind.audience()),
+            iat: now,
+            nbf: now,
+            exp: now + lifetime,
+            jti: Uuid::new_v4().to_string(),
+            sid: family_id,
+            payload,
+        };
+        self.keyring.sign(&claims).map_err(|_| make_error!("unable to generate token"))
+    }
+
+    fn verify_token(&self, token: &str, audience: Option<&str>) -> Result<Claims, Error> {
+        let claims = self.keyring.verify(token)?;
+        let now = Self::timestamp_now()?;
+        let is_audience_valid = audience.is_none_or(|audience| claims.aud == audience);
+        if claims.iss == ISSUER && is_audience_valid && claims.nbf <= now && now < claims.exp {
+            return Ok(claims);
+        }
+        Err(DomainError::unauthenticated("invalid token"))
+    }
+
+    fn check_token(&self, redis: &mut Connection, token: &str, kind: TokenKind) -> Result<Claims, Error> {
+        if let false = redis.exists(token)? {
+            let claims = self.verify_token(token, Some(kind.audience()))?;
+            let revoked_at: Option<u64> = redis.get(Self::revocation_key(&claims.sub))?;
+            let is_family_revoked = match &claims.sid {
+                Some(family_id) => redis.exists(Self::family_key(family_id))?,
+                None => false
+            };
+            if revoked_at.is_none_or(|revoked_at| claims.iat > revoked_at) && !is_family_revoked {
+                return Ok(claims);
             }
         }
-        Err(make_error!("invalid token"))
+        Err(DomainError::unauthenticated("invalid token"))
     }
 
-    pub fn new(secret_key: String, redis_client: RedisClient) -> Box<dyn TokenRepository + Send + Sync> {
-        Box::new(TokenRepositoryImpl { secret_key, redis_client })
+    pub fn new(keyring: Box<dyn Keyring + Send + Sync>, redis_client: RedisClient, message_queue: MessageQueue, lifetimes: TokenLifetimes) -> Box<dyn TokenRepository + Send + Sync> {
+        Box::new(TokenRepositoryImpl { keyring, redis_client, message_queue, lifetimes })
     }
 }
 
 #[async_trait::async_trait]
 impl TokenRepository for TokenRepositoryImpl {
-    async fn generate_access_token(&self, payload: &str) -> Result<String, Error> {
-        self.generate_token(payload, 1000 * 60 * 30)
+    async fn generate_access_token(&self, subject: &str, payload: &str, family_id: Option<String>, ttl: Option<u64>) -> Result<String, Error> {
+        self.sign_token(TokenKind::Access, subject, payload, family_id, ttl)
     }
 
-    async fn generate_refresh_token(&self, payload: &str) -> Result<String, Error> {
-        self.generate_token(payload, 1000 * 60 * 60 * 24)
+    async fn generate_refresh_token(&self, subject: &str, payload: &str, family_id: Option<String>, ttl: Option<u64>) -> Result<String, Error> {
+        let family_id = family_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+        self.sign_token(TokenKind::Refresh, subject, payload, Some(family_id), ttl)
     }
 
-    async fn validate_token(&self, token: &str) -> Result<String, Error> {
+    async fn generate_token(&self, kind: TokenKind, subject: &str, payload: &str, ttl: Option<u64>) -> Result<String, Error> {
+        self.sign_token(kind, subject, payload, None, ttl)
+    }
+
+    async fn validate_token(&self, token: &str, kind: TokenKind) -> Result<String, Error> {
         let mut redis = self.redis_client.get_connection()?;
-        if let false = redis.exists(token)? {
-            if let Ok(claims) = self.verify_token(token) {
-                if let Some(payload) = claims.get("payload") {
-                    return Ok(String::from(payload));
-                }
+        let claims = self.check_token(&mut redis, token, kind)?;
+        Ok(serde_json::to_string(&claims.payload)?)
+    }
+
+    async fn invalidate_token(&self, token: &str) -> Result<(), Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        if let Ok(claims) = self.verify_token(token, None) {
+            let now = Self::timestamp_now()?;
+            let _ = redis.set_ex::<&str, &str, u64>(token, "", claims.exp - now);
+            self.publish(TOKEN_INVALIDATED_ROUTING_KEY, &[&claims.jti, &claims.exp.to_string()]).await?;
+        }
+        Ok(())
+    }
+
+    async fn revoke_tokens(&self, subject: &str) -> Result<(), Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let now = Self::timestamp_now()?;
+        redis.set_ex::<String, u64, ()>(Self::revocation_key(subject), now, self.lifetimes.max())?;
+        let until = now + self.lifetimes.max();
+        self.publish(SUBJECT_REVOKED_ROUTING_KEY, &[subject, &now.to_string(), &until.to_string()]).await
+    }
+
+    async fn rotate_refresh_token(&self, token: &str) -> Result<(String, String, String), Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let claims = self.check_token(&mut redis, token, TokenKind::Refresh)?;
+        if let Some(family_id) = &claims.sid {
+            let options = SetOptions::default()
+                .conditional_set(ExistenceCheck::NX)
+                .with_expiration(SetExpiry::EX(self.lifetimes.refresh as usize));
+            let is_rotated: Option<String> = redis.set_options(Self::rotation_key(&claims.jti), "", options)?;
+            if is_rotated.is_none() {
+                self.revoke_family(&mut redis, &claims.sub, family_id).await?;
+                return Err(DomainError::unauthenticated("refresh token reuse detected"));
             }
+            self.touch_session(&mut redis, &claims.sub, family_id)?;
+            return Ok((claims.sub.clone(), serde_json::to_string(&claims.payload)?, family_id.clone()));
         }
-        Err(make_error!("invalid token"))
+        Err(DomainError::unauthenticated("invalid refresh token"))
+    }
+    async fn create_session(&self, subject: &str, device_name: &str, ip_address: &str, user_agent: &str) -> Result<String, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let session = Session::new(&Uuid::new_v4().to_string(), device_name, ip_address, user_agent);
+        redis.hset::<String, &str, String, ()>(Self::sessions_key(subject), &session.id, serde_json::to_string(&session)?)?;
+        Ok(session.id)
     }
 
-    async fn invalidate_token(&self, token: &str) -> Result<(), Error> {
+    async fn list_sessions(&self, subject: &str) -> Result<Vec<Session>, Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        let entries: HashMap<String, String> = redis.hgetall(Self::sessions_key(subject))?;
+        let now = Session::timestamp_now();
+        let mut sessions: Vec<Session> = vec![];
+        for (session_id, session) in entries {
+            let session: Session = serde_json::from_str(&session)?;
+            if session.last_seen_at + self.lifetimes.refresh * 1000 > now {
+                sessions.push(session);
+            } else {
+                redis.hdel::<String, String, ()>(Self::sessions_key(subject), session_id)?;
+            }
+        }
+        sessions.sort_by_key(|session| std::cmp::Reverse(session.last_seen_at));
+        Ok(sessions)
+    }
+
+    async fn revoke_session(&self, subject: &str, session_id: &str) -> Result<(), Error> {
+        let mut redis = self.redis_client.get_connection()?;
+        if let true = redis.hexists(Self::sessions_key(subject), session_id)? {
+            return self.revoke_family(&mut redis, subject, session_id).await;
+        }
+        Err(DomainError::not_found("session not found"))
+    }
+
+    async fn revoke_all_sessions(&self, subject: &str, except_session_id: Option<String>) -> Result<(), Error> {
         let mut redis = self.redis_client.get_connection()?;
-        if let Ok(claims) = self.verify_token(token) {
-            if let Some(exp) = claims.get("exp") {
-                if let Ok(exp) = exp.parse::<u64>() {
-                    let _ = redis.set_ex::<&str, &str, u64>(token, "", exp);
-                }
+        let session_ids: Vec<String> = redis.hkeys(Self::sessions_key(subject))?;
+        for session_id in session_ids {
+            if except_session_id.as_ref() != Some(&session_id) {
+                self.revoke_family(&mut redis, subject, &session_id).await?;
             }
         }
         Ok(())
     }
+
+    async fn get_public_keys(&self) -> Result<Vec<PublicKey>, Error> {
+        self.keyring.get_public_keys()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::token::keyring::KeyringImpl;
+
+    use super::*;
+
+    const REDIS_URL: &str = "redis://127.0.0.1:6379";
+    const AMQP_HOSTNAME: &str = "127.0.0.1";
+    const AMQP_PORT: &str = "5672";
+    const PAYLOAD: &str = "{}";
+
+    async fn create_repository() -> Box<dyn TokenRepository + Send + Sync> {
+        let redis_client = RedisClient::open(REDIS_URL).unwrap();
+        let message_queue = MessageQueue::connect(AMQP_HOSTNAME, AMQP_PORT).await.expect("failed to connect to message queue");
+        let lifetimes = TokenLifetimes::default();
+        let keyring = KeyringImpl::new(redis_client.clone(), lifetimes.max());
+        TokenRepositoryImpl::new(keyring, redis_client, message_queue, lifetimes)
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_reuse_revokes_family() {
+        let repository = create_repository().await;
+        let subject = Uuid::new_v4().to_string();
+        let family_id = repository.create_session(&subject, "device", "127.0.0.1", "test").await.unwrap();
+
+        let refresh_token = repository.generate_refresh_token(&subject, PAYLOAD, Some(family_id.clone()), None).await.unwrap();
+        let (_, _, rotated_family_id) = repository.rotate_refresh_token(&refresh_token).await.unwrap();
+        assert_eq!(rotated_family_id, family_id);
+
+        let access_token = repository.generate_access_token(&subject, PAYLOAD, Some(family_id.clone()), None).await.unwrap();
+        let next_refresh_token = repository.generate_refresh_token(&subject, PAYLOAD, Some(family_id.clone()), None).await.unwrap();
+        assert!(repository.validate_token(&access_token, TokenKind::Access).await.is_ok());
+
+        let reused = repository.rotate_refresh_token(&refresh_token).await;
+        assert!(matches!(reused.err().as_deref().and_then(|error| error.downcast_ref::<DomainError>()), Some(DomainError::Unauthenticated(_))));
+        assert!(repository.validate_token(&access_token, TokenKind::Access).await.is_err());
+        assert!(repository.rotate_refresh_token(&next_refresh_token).await.is_err());
+        assert!(repository.list_sessions(&subject).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_reuse_keeps_other_families() {
+        let repository = create_repository().await;
+        let subject = Uuid::new_v4().to_string();
+        let family_id = repository.create_session(&subject, "device", "127.0.0.1", "test").await.unwrap();
+        let other_family_id = repository.create_session(&subject, "other device", "127.0.0.1", "test").await.unwrap();
+
+        let refresh_token = repository.generate_refresh_token(&subject, PAYLOAD, Some(family_id), None).await.unwrap();
+        let other_access_token = repository.generate_access_token(&subject, PAYLOAD, Some(other_family_id.clone()), None).await.unwrap();
+        repository.rotate_refresh_token(&refresh_token).await.unwrap();
+        assert!(repository.rotate_refresh_token(&refresh_token).await.is_err());
+
+        assert!(repository.validate_token(&other_access_token, TokenKind::Access).await.is_ok());
+        let sessions = repository.list_sessions(&subject).await.unwrap();
+        assert_eq!(sessions.into_iter().map(|session| session.id).collect::<Vec<String>>(), vec![other_family_id]);
+    }
 }
\ No newline at end of file
@@ -1,7 +1,8 @@
 use tonic::{Request, Response, Status};
 
+use crate::token::entity::TokenKind;
 use crate::token::interactor::TokenInteractor;
-use crate::token::pb::{GenerateAccessTokenRequest, GenerateAccessTokenResponse, GenerateRefreshTokenRequest, GenerateRefreshTokenResponse, InvalidateTokenRequest, InvalidateTokenResponse, ValidateTokenRequest, ValidateTokenResponse};
+use crate::token::pb::{CreateSessionRequest, CreateSessionResponse, GenerateAccessTokenRequest, GenerateAccessTokenResponse, GenerateRefreshTokenRequest, GenerateRefreshTokenResponse, GenerateTokenRequest, GenerateTokenResponse, GetPublicKeysRequest, GetPublicKeysResponse, InvalidateTokenRequest, InvalidateTokenResponse, ListSessionsRequest, ListSessionsResponse, RevokeAllSessionsRequest, RevokeAllSessionsResponse, RevokeSessionRequest, RevokeSessionResponse, RevokeTokensRequest, RevokeTokensResponse, RotateRefreshTokenRequest, RotateRefreshTokenResponse, TokenKind as TokenKindMessage, ValidateTokenRequest, ValidateTokenResponse};
 use crate::token::pb::token_service_server::TokenService;
 
 pub struct TokenServiceImpl {
@@ -17,50 +18,79 @@ impl TokenServiceImpl {
 #[tonic::async_trait]
 impl TokenService for TokenServiceImpl {
     async fn generate_access_token(&self, request: Request<GenerateAccessTokenRequest>) -> Result<Response<GenerateAccessTokenResponse>, Status> {
-        let GenerateAccessTokenRequest { payload } = request.into_inner();
-        if payload.is_empty() {
-            return status::Status::invalid_arguments(vec!["payload"]);
+        let GenerateAccessTokenRequest { payload, subject, family_id, ttl } = request.into_inner();
+        if payload.is_empty() || subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["payload", "subject"]);
         }
 
-        match self.interactor.generate_access_token(&payload).await {
+        let family_id = if family_id.is_empty() { None } else { Some(family_id) };
+        let ttl = if ttl == 0 { None } else { Some(ttl) };
+        match self.interactor.generate_access_token(&subject, &payload, family_id, ttl).await {
             Ok(token) => Ok(
                 Response::new(
                     GenerateAccessTokenResponse { token }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn generate_refresh_token(&self, request: Request<GenerateRefreshTokenRequest>) -> Result<Response<GenerateRefreshTokenResponse>, Status> {
-        let GenerateRefreshTokenRequest { payload } = request.into_inner();
-        if payload.is_empty() {
-            return status::Status::invalid_arguments(vec!["payload"]);
+        let GenerateRefreshTokenRequest { payload, subject, family_id, ttl } = request.into_inner();
+        if payload.is_empty() || subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["payload", "subject"]);
         }
 
-        match self.interactor.generate_refresh_token(&payload).await {
+        let family_id = if family_id.is_empty() { None } else { Some(family_id) };
+        let ttl = if ttl == 0 { None } else { Some(ttl) };
+        match self.interactor.generate_refresh_token(&subject, &payload, family_id, ttl).await {
             Ok(token) => Ok(
                 Response::new(
                     GenerateRefreshTokenResponse { token }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn generate_token(&self, request: Request<GenerateTokenRequest>) -> Result<Response<GenerateTokenResponse>, Status> {
+        let GenerateTokenRequest { kind, payload, subject, ttl } = request.into_inner();
+        let kind: TokenKind = match TokenKindMessage::try_from(kind) {
+            Ok(TokenKindMessage::Access) | Ok(TokenKindMessage::Refresh) | Err(_) => return status::Status::invalid_arguments(vec!["kind"]),
+            Ok(kind) => kind.into()
+        };
+        if payload.is_empty() || subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["payload", "subject"]);
+        }
+
+        let ttl = if ttl == 0 { None } else { Some(ttl) };
+        match self.interactor.generate_token(kind, &subject, &payload, ttl).await {
+            Ok(token) => Ok(
+                Response::new(
+                    GenerateTokenResponse { token }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
         }
     }
 
     async fn validate_token(&self, request: Request<ValidateTokenRequest>) -> Result<Response<ValidateTokenResponse>, Status> {
-        let ValidateTokenRequest { token } = request.into_inner();
+        let ValidateTokenRequest { token, kind } = request.into_inner();
         if token.is_empty() {
             return status::Status::invalid_arguments(vec!["token"]);
         }
+        let kind: TokenKind = match TokenKindMessage::try_from(kind) {
+            Ok(TokenKindMessage::Refresh) | Err(_) => return status::Status::invalid_arguments(vec!["kind"]),
+            Ok(kind) => kind.into()
+        };
 
-        match self.interactor.validate_token(&token).await {
+        match self.interactor.validate_token(&token, kind).await {
             Ok(payload) => Ok(
                 Response::new(
                     ValidateTokenResponse { payload }
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
         }
     }
 
@@ -76,7 +106,115 @@ impl TokenService for TokenServiceImpl {
                     InvalidateTokenResponse::default()
                 )
             ),
-            Err(error) => status::Status::internal(error)
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+    async fn revoke_tokens(&self, request: Request<RevokeTokensRequest>) -> Result<Response<RevokeTokensResponse>, Status> {
+        let RevokeTokensRequest { subject } = request.into_inner();
+        if subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["subject"]);
+        }
+
+        match self.interactor.revoke_tokens(&subject).await {
+            Ok(_) => Ok(
+                Response::new(
+                    RevokeTokensResponse::default()
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+    async fn rotate_refresh_token(&self, request: Request<RotateRefreshTokenRequest>) -> Result<Response<RotateRefreshTokenResponse>, Status> {
+        let RotateRefreshTokenRequest { token } = request.into_inner();
+        if token.is_empty() {
+            return status::Status::invalid_arguments(vec!["token"]);
+        }
+
+        match self.interactor.rotate_refresh_token(&token).await {
+            Ok((subject, payload, family_id)) => Ok(
+                Response::new(
+                    RotateRefreshTokenResponse {
+                        subject,
+                        payload,
+                        family_id,
+                    }
+                )
+            ),
+            Err(error) => status::Status::unauthenticated(&error.to_string())
+        }
+    }
+    async fn create_session(&self, request: Request<CreateSessionRequest>) -> Result<Response<CreateSessionResponse>, Status> {
+        let CreateSessionRequest { subject, device_name, ip_address, user_agent } = request.into_inner();
+        if subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["subject"]);
+        }
+
+        match self.interactor.create_session(&subject, &device_name, &ip_address, &user_agent).await {
+            Ok(session_id) => Ok(
+                Response::new(
+                    CreateSessionResponse { session_id }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn list_sessions(&self, request: Request<ListSessionsRequest>) -> Result<Response<ListSessionsResponse>, Status> {
+        let ListSessionsRequest { subject } = request.into_inner();
+        if subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["subject"]);
+        }
+
+        match self.interactor.list_sessions(&subject).await {
+            Ok(sessions) => Ok(
+                Response::new(
+                    ListSessionsResponse { sessions: sessions.into_iter().map(|session| session.into()).collect() }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn revoke_session(&self, request: Request<RevokeSessionRequest>) -> Result<Response<RevokeSessionResponse>, Status> {
+        let RevokeSessionRequest { subject, session_id } = request.into_inner();
+        if subject.is_empty() || session_id.is_empty() {
+            return status::Status::invalid_arguments(vec!["subject", "session_id"]);
+        }
+
+        match self.interactor.revoke_session(&subject, &session_id).await {
+            Ok(_) => Ok(
+                Response::new(
+                    RevokeSessionResponse::default()
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn revoke_all_sessions(&self, request: Request<RevokeAllSessionsRequest>) -> Result<Response<RevokeAllSessionsResponse>, Status> {
+        let RevokeAllSessionsRequest { subject, except_session_id } = request.into_inner();
+        if subject.is_empty() {
+            return status::Status::invalid_arguments(vec!["subject"]);
+        }
+
+        match self.interactor.revoke_all_sessions(&subject, except_session_id).await {
+            Ok(_) => Ok(
+                Response::new(
+                    RevokeAllSessionsResponse::default()
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
+        }
+    }
+
+    async fn get_public_keys(&self, _: Request<GetPublicKeysRequest>) -> Result<Response<GetPublicKeysResponse>, Status> {
+        match self.interactor.get_public_keys().await {
+            Ok(keys) => Ok(
+                Response::new(
+                    GetPublicKeysResponse { keys: keys.into_iter().map(|key| key.into()).collect() }
+                )
+            ),
+            Err(error) => status::Status::from_error(error)
         }
     }
 }
\ No newline at end of file